@@ -0,0 +1,82 @@
+use std::hint::black_box;
+
+use buckshot_roulette_gameplay_engine::{
+    action::Action, compact_round::CompactRound, decision::TurnEnd, game_session::GameSession,
+    multiplayer_count::MultiplayerCount,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn new_session() -> GameSession<StdRng> {
+    GameSession::new(MultiplayerCount::Four, StdRng::seed_from_u64(7))
+}
+
+/// Plays the rest of the round, always shooting the next opponent
+fn play_round(mut session: GameSession<StdRng>) -> usize {
+    let mut actions = 0;
+    loop {
+        let round = session.round().unwrap();
+        let actor = round.next_player();
+        let target = round
+            .living_players()
+            .map(|seat| seat.player_number())
+            .find(|player_number| *player_number != actor)
+            .unwrap();
+
+        actions += 1;
+        let outcome = session.submit(Action::Shoot(target)).unwrap();
+        if let Some(TurnEnd::RoundEnds { .. }) = outcome.turn_end {
+            return actions;
+        }
+    }
+}
+
+fn play_compact_round(session: &GameSession<StdRng>) -> usize {
+    let round = session.round().unwrap();
+    let mut compact = CompactRound::from_round(round).unwrap();
+    let mut rng = round.rng().clone();
+
+    let mut actions = 0;
+    while compact.winner().is_none() {
+        let actor = compact.next_player();
+        let target = compact
+            .living_players()
+            .find(|player_number| *player_number != actor)
+            .unwrap();
+
+        actions += 1;
+        compact.apply(Action::Shoot(target), &mut rng).unwrap();
+    }
+
+    actions
+}
+
+fn clone_state(criterion: &mut Criterion) {
+    let session = new_session();
+    let round = session.round().unwrap();
+    let compact = CompactRound::from_round(round).unwrap();
+
+    let mut group = criterion.benchmark_group("clone_state");
+    group.bench_function("round", |bencher| bencher.iter(|| black_box(round).clone()));
+    group.bench_function("compact_round", |bencher| {
+        bencher.iter(|| *black_box(&compact))
+    });
+    group.finish();
+}
+
+fn play_out_round(criterion: &mut Criterion) {
+    let session = new_session();
+    assert!(play_round(session.clone()) == play_compact_round(&session));
+
+    let mut group = criterion.benchmark_group("play_out_round");
+    group.bench_function("game_session", |bencher| {
+        bencher.iter(|| play_round(black_box(&session).clone()))
+    });
+    group.bench_function("compact_round", |bencher| {
+        bencher.iter(|| play_compact_round(black_box(&session)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, clone_state, play_out_round);
+criterion_main!(benches);
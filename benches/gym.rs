@@ -0,0 +1,54 @@
+use std::hint::black_box;
+
+use buckshot_roulette_gameplay_engine::{
+    gym::{GymEnv, RandomOpponent, ACTION_COUNT},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+type Env = GymEnv<StdRng, RandomOpponent<StdRng>>;
+
+fn new_env() -> Env {
+    GymEnv::new(
+        MultiplayerCount::Four,
+        PlayerNumber::One,
+        RandomOpponent::new(StdRng::seed_from_u64(1)),
+    )
+}
+
+/// Takes a random legal step, resetting once the game is over
+fn random_step(env: &mut Env, agent_rng: &mut StdRng, seed: &mut u64) {
+    let mask = env.action_mask();
+    let legal_actions = mask.iter().filter(|legal| **legal).count();
+    if legal_actions == 0 {
+        *seed += 1;
+        env.reset(*seed).unwrap();
+        return;
+    }
+
+    let choice = agent_rng.gen_range(0, legal_actions);
+    let action_id = (0..ACTION_COUNT)
+        .filter(|id| mask[*id])
+        .nth(choice)
+        .unwrap();
+    black_box(env.step(action_id).unwrap());
+}
+
+fn step(criterion: &mut Criterion) {
+    let mut env = new_env();
+    let mut agent_rng = StdRng::seed_from_u64(2);
+    let mut seed = 0;
+    env.reset(seed).unwrap();
+
+    let mut group = criterion.benchmark_group("gym");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("step", |bencher| {
+        bencher.iter(|| random_step(&mut env, &mut agent_rng, &mut seed))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, step);
+criterion_main!(benches);
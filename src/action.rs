@@ -0,0 +1,42 @@
+use crate::{
+    chance::ChanceProvider,
+    item::UnaryItem,
+    player_number::PlayerNumber,
+    turn::{TakenAction, Turn},
+};
+
+/// A single decision that can be applied to a Turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Action {
+    Shoot(PlayerNumber),
+    UseUnaryItem(UnaryItem),
+    UseAdreneline(PlayerNumber, UnaryItem),
+    UseJammer(PlayerNumber),
+    UseAdrenelineThenJammer(PlayerNumber, PlayerNumber),
+    UseHandcuffs(PlayerNumber),
+    UseAdrenelineThenHandcuffs(PlayerNumber, PlayerNumber),
+}
+
+impl Action {
+    pub fn take<TRng>(self, turn: Turn<TRng>) -> TakenAction<TRng>
+    where
+        TRng: ChanceProvider,
+    {
+        match self {
+            Action::Shoot(target) => turn.shoot(target),
+            Action::UseUnaryItem(unary_item) => turn.use_unary_item(unary_item),
+            Action::UseAdreneline(target_player, target_item) => {
+                turn.use_adreneline(target_player, target_item)
+            }
+            Action::UseJammer(target_player) => turn.use_jammer(target_player),
+            Action::UseAdrenelineThenJammer(theive_from, jam_target) => {
+                turn.use_adreneline_then_jammer(theive_from, jam_target)
+            }
+            Action::UseHandcuffs(target_player) => turn.use_handcuffs(target_player),
+            Action::UseAdrenelineThenHandcuffs(theive_from, cuff_target) => {
+                turn.use_adreneline_then_handcuffs(theive_from, cuff_target)
+            }
+        }
+    }
+}
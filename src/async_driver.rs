@@ -0,0 +1,240 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use anyhow::Result;
+
+use crate::{
+    action::Action,
+    chance::ChanceProvider,
+    decision::{ActionOutcome, PendingDecision, SubmitError, MAX_ATTEMPTS},
+    game_session::GameSession,
+    player_number::PlayerNumber,
+    seat::ForfeitedItems,
+};
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// A player whose decisions arrive asynchronously, e.g. over the network or from a UI
+pub trait AsyncAgent {
+    /// Resolves to the action to take, or None to forfeit
+    fn decide(&mut self, decision: PendingDecision) -> BoxFuture<'_, Option<Action>>;
+
+    /// Called with every action successfully taken at the table, including the agent's own
+    fn observe(&mut self, _outcome: &ActionOutcome) {}
+
+    /// Called when the agent's action was rejected, decide will be called again
+    fn rejected(&mut self, _error: SubmitError) {}
+}
+
+/// Provided by the runtime so the driver can time out decisions without depending on one
+pub trait Timer {
+    fn sleep(&mut self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// Stops an AsyncDriver at its next suspension point
+#[derive(Debug, Clone, Default)]
+pub struct Cancellation {
+    inner: Arc<CancellationState>,
+}
+
+#[derive(Debug, Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DriveOutcome {
+    Finished,
+    Cancelled,
+}
+
+/// Drives a GameSession with one AsyncAgent per seat
+pub struct AsyncDriver<TAgent> {
+    agents: Vec<TAgent>,
+    timeout: Option<(Duration, Box<dyn Timer>)>,
+    cancellation: Cancellation,
+}
+
+enum Wait {
+    Decided(Option<Action>),
+    TimedOut,
+    Cancelled,
+}
+
+struct WaitForDecision<'a> {
+    decision: BoxFuture<'a, Option<Action>>,
+    timeout: Option<BoxFuture<'static, ()>>,
+    cancellation: &'a Cancellation,
+}
+
+impl Cancellation {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        if let Some(waker) = self.inner.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn poll_cancelled(&self, context: &mut Context<'_>) -> bool {
+        *self.inner.waker.lock().unwrap() = Some(context.waker().clone());
+
+        // checked after registering so a concurrent cancel can't be missed
+        self.is_cancelled()
+    }
+}
+
+impl Future for WaitForDecision<'_> {
+    type Output = Wait;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.cancellation.poll_cancelled(context) {
+            return Poll::Ready(Wait::Cancelled);
+        }
+
+        if let Poll::Ready(action) = self.decision.as_mut().poll(context) {
+            return Poll::Ready(Wait::Decided(action));
+        }
+
+        if let Some(timeout) = &mut self.timeout {
+            if timeout.as_mut().poll(context).is_ready() {
+                return Poll::Ready(Wait::TimedOut);
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<TAgent> AsyncAgent for Box<TAgent>
+where
+    TAgent: AsyncAgent + ?Sized,
+{
+    fn decide(&mut self, decision: PendingDecision) -> BoxFuture<'_, Option<Action>> {
+        (**self).decide(decision)
+    }
+
+    fn observe(&mut self, outcome: &ActionOutcome) {
+        (**self).observe(outcome)
+    }
+
+    fn rejected(&mut self, error: SubmitError) {
+        (**self).rejected(error)
+    }
+}
+
+impl<TAgent> AsyncDriver<TAgent>
+where
+    TAgent: AsyncAgent,
+{
+    /// The agent at index i plays the player in seat i
+    pub fn new(agents: Vec<TAgent>) -> Self {
+        AsyncDriver {
+            agents,
+            timeout: None,
+            cancellation: Cancellation::new(),
+        }
+    }
+
+    /// Seats that take longer than the duration to decide forfeit
+    pub fn with_timeout<TTimer>(mut self, duration: Duration, timer: TTimer) -> Self
+    where
+        TTimer: Timer + 'static,
+    {
+        self.timeout = Some((duration, Box::new(timer)));
+        self
+    }
+
+    /// Uses an existing handle, e.g. one shared with the agents
+    pub fn with_cancellation(mut self, cancellation: Cancellation) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Handle that can be used to stop the driver from elsewhere
+    pub fn cancellation(&self) -> Cancellation {
+        self.cancellation.clone()
+    }
+
+    pub fn agent(&self, player: PlayerNumber) -> Option<&TAgent> {
+        self.agents.get(player.seat_index())
+    }
+
+    /// Plays the session until it is over or the driver is cancelled. The session is only borrowed between decisions
+    pub async fn run<TRng>(&mut self, session: &mut GameSession<TRng>) -> Result<DriveOutcome>
+    where
+        TRng: ChanceProvider,
+    {
+        let AsyncDriver {
+            agents,
+            timeout,
+            cancellation,
+        } = self;
+
+        let mut attempts = 0;
+        loop {
+            if cancellation.is_cancelled() {
+                return Ok(DriveOutcome::Cancelled);
+            }
+
+            let decision = match session.pending_decision() {
+                Some(decision) => decision,
+                None => return Ok(DriveOutcome::Finished),
+            };
+
+            let player = decision.player;
+            let wait = WaitForDecision {
+                decision: agents[player.seat_index()].decide(decision),
+                timeout: timeout
+                    .as_mut()
+                    .map(|(duration, timer)| timer.sleep(*duration)),
+                cancellation,
+            }
+            .await;
+
+            let action = match wait {
+                Wait::Decided(Some(action)) => action,
+                Wait::Decided(None) | Wait::TimedOut => {
+                    attempts = 0;
+                    session.forfeit(player, ForfeitedItems::Keep)?;
+                    continue;
+                }
+                Wait::Cancelled => return Ok(DriveOutcome::Cancelled),
+            };
+
+            match session.submit(action) {
+                Ok(outcome) => {
+                    attempts = 0;
+                    for agent in agents.iter_mut() {
+                        agent.observe(&outcome);
+                    }
+                }
+                Err(error) => {
+                    attempts += 1;
+                    if attempts < MAX_ATTEMPTS {
+                        agents[player.seat_index()].rejected(error);
+                    } else {
+                        attempts = 0;
+                        session.forfeit(player, ForfeitedItems::Keep)?;
+                    }
+                }
+            }
+        }
+    }
+}
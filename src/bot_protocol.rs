@@ -0,0 +1,594 @@
+use std::{borrow::Cow, fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+use crate::{
+    action::Action,
+    chance::ChanceProvider,
+    item::{CustomItemId, Item, NotAdreneline, UnaryItem, ALL_ITEMS},
+    modifier::{GameModifiers, Modifier},
+    player_number::PlayerNumber,
+    round::Round,
+    round_number::RoundNumber,
+    round_player::{HandcuffState, StunState},
+    seat::SeatView,
+    shell::ShellType,
+    shell_knowledge::ShellKnowledge,
+};
+
+pub mod host;
+
+/// Sent by the engine, one per line
+///
+/// ```text
+/// bsr
+/// newgame <player count> <your player>
+/// state round <n> max_health <n> shells <n> live <n> blank <n> spent_live <n> spent_blank <n> sawn <0|1> inverted <0|1>
+///     (seat <player> health <n|dead> stun <unstunned|stunned|recovering|-> cuffs <free|cuffed|broken|-> items <item,item,...|->)...
+///     (known <relative index> <live|blank|slug|incendiary|dud>)...
+/// go
+/// info <player> <action>
+/// illegal <reason>
+/// quit
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum EngineCommand {
+    Hello,
+    NewGame {
+        player_count: usize,
+        player: PlayerNumber,
+    },
+    State(VisibleState),
+    /// The bot must reply with an action
+    Go,
+    /// An action was successfully taken by someone at the table
+    Info {
+        actor: PlayerNumber,
+        action: Action,
+    },
+    /// The last action was rejected, another one must be sent
+    Illegal(String),
+    Quit,
+}
+
+/// Sent by the bot, one per line
+///
+/// ```text
+/// id name <name>
+/// bsrok
+/// action shoot <player>
+/// action use <item>
+/// action adreneline <player> <item>
+/// action jammer <player>
+/// action adreneline_jammer <player> <player>
+/// action handcuffs <player>
+/// action adreneline_handcuffs <player> <player>
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum BotReply {
+    Id { name: String },
+    Ready,
+    Act(Action),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisibleSeat {
+    pub view: SeatView,
+    pub health: Option<i32>,
+}
+
+/// Everything the acting player knows about the round
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisibleState {
+    pub round_number: RoundNumber,
+    pub max_health: i32,
+    pub shell_count: usize,
+    pub initial_live_rounds: usize,
+    pub initial_blank_rounds: usize,
+    pub spent_live_rounds: usize,
+    pub spent_blank_rounds: usize,
+    pub modifiers: GameModifiers,
+    pub seats: Vec<VisibleSeat>,
+    /// Shells the player has learned, by index relative to the chamber
+    pub known_shells: Vec<(usize, ShellType)>,
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ParseLineError {
+    #[error("Line is empty")]
+    Empty,
+    #[error("Unknown command {0}")]
+    UnknownCommand(String),
+    #[error("Line ended unexpectedly")]
+    MissingToken,
+    #[error("Unexpected token {0}")]
+    InvalidToken(String),
+}
+
+impl VisibleState {
+    pub fn new<TRng>(round: &Round<TRng>, knowledge: &ShellKnowledge, player: PlayerNumber) -> Self
+    where
+        TRng: ChanceProvider,
+    {
+        let shell_count = round.shells().len();
+        VisibleState {
+            round_number: round.number(),
+            max_health: round.max_health(),
+            shell_count,
+            initial_live_rounds: knowledge.initial_live_rounds(),
+            initial_blank_rounds: knowledge.initial_blank_rounds(),
+            spent_live_rounds: knowledge.spent_live_rounds(),
+            spent_blank_rounds: knowledge.spent_blank_rounds(),
+            modifiers: round.game_modifiers().clone(),
+            seats: round
+                .seats()
+                .iter()
+                .map(|seat| VisibleSeat {
+                    view: seat.create_view(round.game_modifiers()),
+                    health: seat.player().map(|player| player.health()),
+                })
+                .collect(),
+            known_shells: (0..shell_count)
+                .filter_map(|relative_index| {
+                    knowledge
+                        .known_shell(player, relative_index)
+                        .map(|shell_type| (relative_index, shell_type))
+                })
+                .collect(),
+        }
+    }
+}
+
+fn unary_item_name(unary_item: UnaryItem) -> Cow<'static, str> {
+    Cow::Borrowed(match unary_item {
+        UnaryItem::Remote => "remote",
+        UnaryItem::Phone => "phone",
+        UnaryItem::Inverter => "inverter",
+        UnaryItem::MagnifyingGlass => "magnifying_glass",
+        UnaryItem::Cigarettes => "cigarettes",
+        UnaryItem::Handsaw => "handsaw",
+        UnaryItem::Beer => "beer",
+        UnaryItem::Custom(id) => return Cow::Owned(format!("custom_{}", id.0)),
+    })
+}
+
+fn item_name(item: Item) -> Cow<'static, str> {
+    match item {
+        Item::NotAdreneline(NotAdreneline::UnaryItem(unary_item)) => unary_item_name(unary_item),
+        Item::NotAdreneline(NotAdreneline::Jammer) => Cow::Borrowed("jammer"),
+        Item::NotAdreneline(NotAdreneline::Handcuffs) => Cow::Borrowed("handcuffs"),
+        Item::Adreneline => Cow::Borrowed("adreneline"),
+    }
+}
+
+fn shell_type_name(shell_type: ShellType) -> &'static str {
+    match shell_type {
+        ShellType::Live => "live",
+        ShellType::Blank => "blank",
+        ShellType::Slug => "slug",
+        ShellType::Incendiary => "incendiary",
+        ShellType::Dud => "dud",
+    }
+}
+
+fn stun_state_name(stun_state: Option<StunState>) -> &'static str {
+    match stun_state {
+        Some(StunState::Unstunned) => "unstunned",
+        Some(StunState::Stunned) => "stunned",
+        Some(StunState::Recovering) => "recovering",
+        None => "-",
+    }
+}
+
+fn handcuff_state_name(handcuff_state: Option<HandcuffState>) -> &'static str {
+    match handcuff_state {
+        Some(HandcuffState::Free) => "free",
+        Some(HandcuffState::Cuffed) => "cuffed",
+        Some(HandcuffState::Broken) => "broken",
+        None => "-",
+    }
+}
+
+fn flag(value: bool) -> &'static str {
+    if value {
+        "1"
+    } else {
+        "0"
+    }
+}
+
+fn format_action(action: &Action) -> String {
+    match action {
+        Action::Shoot(target) => format!("shoot {}", *target as usize),
+        Action::UseUnaryItem(unary_item) => format!("use {}", unary_item_name(*unary_item)),
+        Action::UseAdreneline(target, unary_item) => format!(
+            "adreneline {} {}",
+            *target as usize,
+            unary_item_name(*unary_item)
+        ),
+        Action::UseJammer(target) => format!("jammer {}", *target as usize),
+        Action::UseAdrenelineThenJammer(theive_from, jam_target) => format!(
+            "adreneline_jammer {} {}",
+            *theive_from as usize, *jam_target as usize
+        ),
+        Action::UseHandcuffs(target) => format!("handcuffs {}", *target as usize),
+        Action::UseAdrenelineThenHandcuffs(theive_from, cuff_target) => format!(
+            "adreneline_handcuffs {} {}",
+            *theive_from as usize, *cuff_target as usize
+        ),
+    }
+}
+
+impl Display for VisibleState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "round {} max_health {} shells {} live {} blank {} spent_live {} spent_blank {} sawn {} inverted {}",
+            self.round_number,
+            self.max_health,
+            self.shell_count,
+            self.initial_live_rounds,
+            self.initial_blank_rounds,
+            self.spent_live_rounds,
+            self.spent_blank_rounds,
+            flag(self.modifiers.shotgun_sawn()),
+            flag(self.modifiers.turn_order_inverted()),
+        )?;
+
+        for seat in &self.seats {
+            write!(f, " seat {} health ", seat.view.player_number as usize)?;
+            match seat.health {
+                Some(health) => write!(f, "{}", health)?,
+                None => write!(f, "dead")?,
+            }
+
+            write!(
+                f,
+                " stun {} cuffs {} items ",
+                stun_state_name(seat.view.stun_state),
+                handcuff_state_name(seat.view.handcuff_state)
+            )?;
+            if seat.view.items.is_empty() {
+                write!(f, "-")?;
+            } else {
+                let items: Vec<Cow<str>> = seat
+                    .view
+                    .items
+                    .iter()
+                    .map(|item| item_name(*item))
+                    .collect();
+                write!(f, "{}", items.join(","))?;
+            }
+        }
+
+        for (relative_index, shell_type) in &self.known_shells {
+            write!(
+                f,
+                " known {} {}",
+                relative_index,
+                shell_type_name(*shell_type)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for EngineCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EngineCommand::Hello => write!(f, "bsr"),
+            EngineCommand::NewGame {
+                player_count,
+                player,
+            } => write!(f, "newgame {} {}", player_count, *player as usize),
+            EngineCommand::State(state) => write!(f, "state {}", state),
+            EngineCommand::Go => write!(f, "go"),
+            EngineCommand::Info { actor, action } => {
+                write!(f, "info {} {}", *actor as usize, format_action(action))
+            }
+            EngineCommand::Illegal(reason) => write!(f, "illegal {}", reason),
+            EngineCommand::Quit => write!(f, "quit"),
+        }
+    }
+}
+
+impl Display for BotReply {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BotReply::Id { name } => write!(f, "id name {}", name),
+            BotReply::Ready => write!(f, "bsrok"),
+            BotReply::Act(action) => write!(f, "action {}", format_action(action)),
+        }
+    }
+}
+
+struct Tokens<'line> {
+    inner: std::iter::Peekable<std::str::SplitWhitespace<'line>>,
+}
+
+impl<'line> Tokens<'line> {
+    fn new(line: &'line str) -> Self {
+        Tokens {
+            inner: line.split_whitespace().peekable(),
+        }
+    }
+
+    fn next(&mut self) -> Result<&'line str, ParseLineError> {
+        self.inner.next().ok_or(ParseLineError::MissingToken)
+    }
+
+    fn peek(&mut self) -> Option<&'line str> {
+        self.inner.peek().copied()
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), ParseLineError> {
+        let token = self.next()?;
+        if token != expected {
+            return Err(ParseLineError::InvalidToken(token.to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn number<TNumber>(&mut self) -> Result<TNumber, ParseLineError>
+    where
+        TNumber: FromStr,
+    {
+        let token = self.next()?;
+        token
+            .parse()
+            .map_err(|_| ParseLineError::InvalidToken(token.to_string()))
+    }
+
+    fn flag(&mut self) -> Result<bool, ParseLineError> {
+        match self.next()? {
+            "0" => Ok(false),
+            "1" => Ok(true),
+            token => Err(ParseLineError::InvalidToken(token.to_string())),
+        }
+    }
+
+    fn player_number(&mut self) -> Result<PlayerNumber, ParseLineError> {
+        let token = self.next()?;
+        token
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| number.checked_sub(1))
+            .and_then(PlayerNumber::from_seat_index)
+            .ok_or_else(|| ParseLineError::InvalidToken(token.to_string()))
+    }
+
+    fn round_number(&mut self) -> Result<RoundNumber, ParseLineError> {
+        match self.next()? {
+            "1" => Ok(RoundNumber::One),
+            "2" => Ok(RoundNumber::Two),
+            "3" => Ok(RoundNumber::Three),
+            token => Err(ParseLineError::InvalidToken(token.to_string())),
+        }
+    }
+
+    fn unary_item(&mut self) -> Result<UnaryItem, ParseLineError> {
+        match self.item()? {
+            Item::NotAdreneline(NotAdreneline::UnaryItem(unary_item)) => Ok(unary_item),
+            item => Err(ParseLineError::InvalidToken(item_name(item).to_string())),
+        }
+    }
+
+    fn item(&mut self) -> Result<Item, ParseLineError> {
+        parse_item(self.next()?)
+    }
+
+    fn shell_type(&mut self) -> Result<ShellType, ParseLineError> {
+        match self.next()? {
+            "live" => Ok(ShellType::Live),
+            "blank" => Ok(ShellType::Blank),
+            "slug" => Ok(ShellType::Slug),
+            "incendiary" => Ok(ShellType::Incendiary),
+            "dud" => Ok(ShellType::Dud),
+            token => Err(ParseLineError::InvalidToken(token.to_string())),
+        }
+    }
+
+    fn action(&mut self) -> Result<Action, ParseLineError> {
+        match self.next()? {
+            "shoot" => Ok(Action::Shoot(self.player_number()?)),
+            "use" => Ok(Action::UseUnaryItem(self.unary_item()?)),
+            "adreneline" => Ok(Action::UseAdreneline(
+                self.player_number()?,
+                self.unary_item()?,
+            )),
+            "jammer" => Ok(Action::UseJammer(self.player_number()?)),
+            "adreneline_jammer" => Ok(Action::UseAdrenelineThenJammer(
+                self.player_number()?,
+                self.player_number()?,
+            )),
+            "handcuffs" => Ok(Action::UseHandcuffs(self.player_number()?)),
+            "adreneline_handcuffs" => Ok(Action::UseAdrenelineThenHandcuffs(
+                self.player_number()?,
+                self.player_number()?,
+            )),
+            token => Err(ParseLineError::InvalidToken(token.to_string())),
+        }
+    }
+
+    fn rest(&mut self) -> String {
+        let rest: Vec<&str> = self.inner.by_ref().collect();
+        rest.join(" ")
+    }
+
+    fn end(&mut self) -> Result<(), ParseLineError> {
+        match self.inner.next() {
+            Some(token) => Err(ParseLineError::InvalidToken(token.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_item(token: &str) -> Result<Item, ParseLineError> {
+    if let Some(id) = token.strip_prefix("custom_") {
+        return id
+            .parse()
+            .map(|id| {
+                Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Custom(CustomItemId(
+                    id,
+                ))))
+            })
+            .map_err(|_| ParseLineError::InvalidToken(token.to_string()));
+    }
+
+    ALL_ITEMS
+        .iter()
+        .find(|item| item_name(**item) == token)
+        .copied()
+        .ok_or_else(|| ParseLineError::InvalidToken(token.to_string()))
+}
+
+fn parse_state(tokens: &mut Tokens) -> Result<VisibleState, ParseLineError> {
+    tokens.expect("round")?;
+    let round_number = tokens.round_number()?;
+    tokens.expect("max_health")?;
+    let max_health = tokens.number()?;
+    tokens.expect("shells")?;
+    let shell_count = tokens.number()?;
+    tokens.expect("live")?;
+    let initial_live_rounds = tokens.number()?;
+    tokens.expect("blank")?;
+    let initial_blank_rounds = tokens.number()?;
+    tokens.expect("spent_live")?;
+    let spent_live_rounds = tokens.number()?;
+    tokens.expect("spent_blank")?;
+    let spent_blank_rounds = tokens.number()?;
+    tokens.expect("sawn")?;
+    let shotgun_sawn = tokens.flag()?;
+    tokens.expect("inverted")?;
+    let turn_order_inverted = tokens.flag()?;
+
+    // only the table's modifiers are part of the text protocol
+    let mut modifiers = GameModifiers::default();
+    if shotgun_sawn {
+        modifiers.add(Modifier::sawn_shotgun()).unwrap();
+    }
+    if turn_order_inverted {
+        modifiers.add(Modifier::inverted_turn_order()).unwrap();
+    }
+
+    let mut seats = Vec::new();
+    while tokens.peek() == Some("seat") {
+        tokens.next()?;
+        let player_number = tokens.player_number()?;
+        tokens.expect("health")?;
+        let health = match tokens.peek() {
+            Some("dead") => {
+                tokens.next()?;
+                None
+            }
+            _ => Some(tokens.number()?),
+        };
+        tokens.expect("stun")?;
+        let stun_state = match tokens.next()? {
+            "unstunned" => Some(StunState::Unstunned),
+            "stunned" => Some(StunState::Stunned),
+            "recovering" => Some(StunState::Recovering),
+            "-" => None,
+            token => return Err(ParseLineError::InvalidToken(token.to_string())),
+        };
+        tokens.expect("cuffs")?;
+        let handcuff_state = match tokens.next()? {
+            "free" => Some(HandcuffState::Free),
+            "cuffed" => Some(HandcuffState::Cuffed),
+            "broken" => Some(HandcuffState::Broken),
+            "-" => None,
+            token => return Err(ParseLineError::InvalidToken(token.to_string())),
+        };
+        tokens.expect("items")?;
+        let items = match tokens.next()? {
+            "-" => Vec::new(),
+            token => token
+                .split(',')
+                .map(parse_item)
+                .collect::<Result<Vec<Item>, ParseLineError>>()?,
+        };
+
+        seats.push(VisibleSeat {
+            view: SeatView {
+                stun_state,
+                handcuff_state,
+                player_number,
+                // slots and player modifiers aren't part of the text protocol
+                item_slots: (0..items.len()).collect(),
+                modifiers: Vec::new(),
+                items,
+            },
+            health,
+        });
+    }
+
+    let mut known_shells = Vec::new();
+    while tokens.peek() == Some("known") {
+        tokens.next()?;
+        known_shells.push((tokens.number()?, tokens.shell_type()?));
+    }
+
+    Ok(VisibleState {
+        round_number,
+        max_health,
+        shell_count,
+        initial_live_rounds,
+        initial_blank_rounds,
+        spent_live_rounds,
+        spent_blank_rounds,
+        modifiers,
+        seats,
+        known_shells,
+    })
+}
+
+impl FromStr for EngineCommand {
+    type Err = ParseLineError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut tokens = Tokens::new(line);
+        let command = match tokens.next().map_err(|_| ParseLineError::Empty)? {
+            "bsr" => EngineCommand::Hello,
+            "newgame" => EngineCommand::NewGame {
+                player_count: tokens.number()?,
+                player: tokens.player_number()?,
+            },
+            "state" => EngineCommand::State(parse_state(&mut tokens)?),
+            "go" => EngineCommand::Go,
+            "info" => EngineCommand::Info {
+                actor: tokens.player_number()?,
+                action: tokens.action()?,
+            },
+            "illegal" => EngineCommand::Illegal(tokens.rest()),
+            "quit" => EngineCommand::Quit,
+            command => return Err(ParseLineError::UnknownCommand(command.to_string())),
+        };
+
+        tokens.end()?;
+        Ok(command)
+    }
+}
+
+impl FromStr for BotReply {
+    type Err = ParseLineError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut tokens = Tokens::new(line);
+        let reply = match tokens.next().map_err(|_| ParseLineError::Empty)? {
+            "id" => {
+                tokens.expect("name")?;
+                BotReply::Id {
+                    name: tokens.rest(),
+                }
+            }
+            "bsrok" => BotReply::Ready,
+            "action" => BotReply::Act(tokens.action()?),
+            command => return Err(ParseLineError::UnknownCommand(command.to_string())),
+        };
+
+        tokens.end()?;
+        Ok(reply)
+    }
+}
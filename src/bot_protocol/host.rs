@@ -0,0 +1,348 @@
+use std::{
+    io::{self, BufRead, Read, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use anyhow::Result;
+use thiserror::Error;
+
+use crate::{
+    action::Action, chance::ChanceProvider, decision::MAX_ATTEMPTS, game_session::GameSession,
+    player_number::PlayerNumber, seat::ForfeitedItems, shell_knowledge::ShellKnowledge,
+};
+
+use super::{BotReply, EngineCommand, ParseLineError, VisibleState};
+
+#[derive(Error, Debug)]
+pub enum BotError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Parse(#[from] ParseLineError),
+    #[error("Bot closed its output")]
+    Disconnected,
+    #[error("Bot sent {0} when it was not expected")]
+    Unexpected(BotReply),
+    #[error("{bots} bots were connected for {seats} seats")]
+    SeatCount { bots: usize, seats: usize },
+}
+
+/// One bot speaking the line protocol over a pair of streams
+#[derive(Debug)]
+pub struct BotConnection<TWrite, TRead> {
+    writer: TWrite,
+    reader: TRead,
+    name: Option<String>,
+}
+
+/// Reads on a separate thread so a read can give up with io::ErrorKind::TimedOut once the timeout passes
+#[derive(Debug)]
+pub struct DeadlineReader {
+    chunks: Receiver<io::Result<Vec<u8>>>,
+    buffer: Vec<u8>,
+    position: usize,
+    timeout: Duration,
+}
+
+/// Drives a GameSession with one bot per seat
+#[derive(Debug)]
+pub struct BotHost<TWrite, TRead> {
+    bots: Vec<Option<BotConnection<TWrite, TRead>>>,
+    children: Vec<Child>,
+}
+
+impl<TWrite, TRead> BotConnection<TWrite, TRead>
+where
+    TWrite: Write,
+    TRead: BufRead,
+{
+    pub fn new(writer: TWrite, reader: TRead) -> Self {
+        BotConnection {
+            writer,
+            reader,
+            name: None,
+        }
+    }
+
+    /// The name the bot identified itself with, if any
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn send(&mut self, command: &EngineCommand) -> io::Result<()> {
+        writeln!(self.writer, "{}", command)?;
+        self.writer.flush()
+    }
+
+    /// Reads the next non-empty line from the bot
+    pub fn recv(&mut self) -> Result<BotReply, BotError> {
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err(BotError::Disconnected);
+            }
+
+            match line.parse() {
+                Ok(reply) => return Ok(reply),
+                Err(ParseLineError::Empty) => continue,
+                Err(error) => return Err(error.into()),
+            }
+        }
+    }
+
+    pub fn handshake(&mut self, player_count: usize, player: PlayerNumber) -> Result<(), BotError> {
+        self.send(&EngineCommand::Hello)?;
+        loop {
+            match self.recv()? {
+                BotReply::Id { name } => self.name = Some(name),
+                BotReply::Ready => break,
+                reply => return Err(BotError::Unexpected(reply)),
+            }
+        }
+
+        self.send(&EngineCommand::NewGame {
+            player_count,
+            player,
+        })?;
+        Ok(())
+    }
+
+    pub fn request_action(&mut self, state: VisibleState) -> Result<Action, BotError> {
+        self.send(&EngineCommand::State(state))?;
+        self.send(&EngineCommand::Go)?;
+        match self.recv()? {
+            BotReply::Act(action) => Ok(action),
+            reply => Err(BotError::Unexpected(reply)),
+        }
+    }
+}
+
+impl DeadlineReader {
+    pub fn new<TRead>(mut reader: TRead, timeout: Duration) -> Self
+    where
+        TRead: Read + Send + 'static,
+    {
+        let (sender, chunks) = mpsc::channel();
+        thread::spawn(move || loop {
+            let mut chunk = vec![0; 4096];
+            match reader.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(length) => {
+                    chunk.truncate(length);
+                    if sender.send(Ok(chunk)).is_err() {
+                        break;
+                    }
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    let _ = sender.send(Err(error));
+                    break;
+                }
+            }
+        });
+
+        DeadlineReader {
+            chunks,
+            buffer: Vec::new(),
+            position: 0,
+            timeout,
+        }
+    }
+}
+
+impl Read for DeadlineReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let length = available.len().min(buf.len());
+        buf[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+        Ok(length)
+    }
+}
+
+impl BufRead for DeadlineReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.position == self.buffer.len() {
+            self.position = 0;
+            self.buffer = match self.chunks.recv_timeout(self.timeout) {
+                Ok(chunk) => chunk?,
+                Err(RecvTimeoutError::Timeout) => return Err(io::ErrorKind::TimedOut.into()),
+                // the reading thread only stops at the end of the stream
+                Err(RecvTimeoutError::Disconnected) => Vec::new(),
+            };
+        }
+
+        Ok(&self.buffer[self.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position += amount;
+    }
+}
+
+impl BotHost<ChildStdin, DeadlineReader> {
+    /// Starts one bot process per seat, in seat order. A bot that takes longer than the timeout to send anything forfeits
+    pub fn spawn<TCommands>(commands: TCommands, timeout: Duration) -> io::Result<Self>
+    where
+        TCommands: IntoIterator<Item = Command>,
+    {
+        let mut bots = Vec::new();
+        let mut children = Vec::new();
+        for mut command in commands {
+            let mut child = command
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()?;
+
+            let writer = child.stdin.take().unwrap();
+            let reader = DeadlineReader::new(child.stdout.take().unwrap(), timeout);
+            bots.push(Some(BotConnection::new(writer, reader)));
+            children.push(child);
+        }
+
+        Ok(BotHost { bots, children })
+    }
+}
+
+impl<TWrite, TRead> BotHost<TWrite, TRead>
+where
+    TWrite: Write,
+    TRead: BufRead,
+{
+    /// The connection at index i plays the player in seat i
+    pub fn new(connections: Vec<BotConnection<TWrite, TRead>>) -> Self {
+        BotHost {
+            bots: connections.into_iter().map(Some).collect(),
+            children: Vec::new(),
+        }
+    }
+
+    /// None if the bot was never connected or has forfeited
+    pub fn bot(&self, player: PlayerNumber) -> Option<&BotConnection<TWrite, TRead>> {
+        self.bots.get(player.seat_index()).and_then(Option::as_ref)
+    }
+
+    /// Plays the session until it is over. Bots that disconnect, misbehave, time out or send too many illegal actions forfeit
+    pub fn play<TRng>(&mut self, session: &mut GameSession<TRng>) -> Result<()>
+    where
+        TRng: ChanceProvider,
+    {
+        let player_count = session.players().multiplayer_count as usize;
+        if self.bots.len() != player_count {
+            return Err(BotError::SeatCount {
+                bots: self.bots.len(),
+                seats: player_count,
+            }
+            .into());
+        }
+
+        for seat_index in 0..player_count {
+            let player = PlayerNumber::from_seat_index(seat_index).unwrap();
+            let connected = match self.bots.get_mut(seat_index) {
+                Some(Some(bot)) => bot.handshake(player_count, player).is_ok(),
+                _ => false,
+            };
+
+            if !connected {
+                self.disconnect(session, player)?;
+            }
+        }
+
+        let mut knowledge = match session.round() {
+            Some(round) => ShellKnowledge::new(round.loadout()),
+            None => return Ok(()),
+        };
+        let mut round_number = session.round().unwrap().number();
+        let mut attempts = 0;
+
+        while let Some(round) = session.round() {
+            // forfeits can end rounds outside of apply
+            if round.number() != round_number {
+                round_number = round.number();
+                knowledge.reset(round.loadout());
+            }
+
+            let player = round.next_player();
+            let state = VisibleState::new(round, &knowledge, player);
+            let action = match self.bots.get_mut(player.seat_index()) {
+                Some(Some(bot)) => bot.request_action(state),
+                _ => Err(BotError::Disconnected),
+            };
+
+            let action = match action {
+                Ok(action) => action,
+                Err(_) => {
+                    self.disconnect(session, player)?;
+                    continue;
+                }
+            };
+
+            match session.submit(action) {
+                Ok(outcome) => {
+                    knowledge
+                        .record_outcome(&outcome, session.round().map(|round| round.loadout()));
+                    attempts = 0;
+                    for bot in self.bots.iter_mut().flatten() {
+                        // failed writes are noticed when the bot is next asked for an action
+                        let _ = bot.send(&EngineCommand::Info {
+                            actor: player,
+                            action,
+                        });
+                    }
+                }
+                Err(error) => {
+                    attempts += 1;
+                    let notified = attempts < MAX_ATTEMPTS
+                        && self
+                            .bots
+                            .get_mut(player.seat_index())
+                            .and_then(Option::as_mut)
+                            .is_some_and(|bot| {
+                                bot.send(&EngineCommand::Illegal(error.to_string())).is_ok()
+                            });
+
+                    if !notified {
+                        attempts = 0;
+                        self.disconnect(session, player)?;
+                    }
+                }
+            }
+        }
+
+        for bot in self.bots.iter_mut().flatten() {
+            let _ = bot.send(&EngineCommand::Quit);
+        }
+
+        Ok(())
+    }
+
+    fn disconnect<TRng>(
+        &mut self,
+        session: &mut GameSession<TRng>,
+        player: PlayerNumber,
+    ) -> Result<()>
+    where
+        TRng: ChanceProvider,
+    {
+        if let Some(bot) = self.bots.get_mut(player.seat_index()) {
+            *bot = None;
+        }
+        if session.round().is_some() {
+            session.forfeit(player, ForfeitedItems::Keep)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<TWrite, TRead> Drop for BotHost<TWrite, TRead> {
+    fn drop(&mut self) {
+        for child in &mut self.children {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
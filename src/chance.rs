@@ -0,0 +1,183 @@
+use rand::RngCore;
+
+use crate::{
+    godot_rng::GodotRandom,
+    item::CustomItemId,
+    player_number::PlayerNumber,
+    rng_streams::{RngStream, RngStreams},
+};
+
+/// Every point where the game rolls the dice
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChanceEvent {
+    MaxHealth,
+    /// Which live/blank split the loadout uses
+    LoadoutSequence,
+    NewItemCount,
+    /// Which item from the pool the player is dealt
+    Item(PlayerNumber),
+    /// Whether the next shell loaded is a blank
+    ShellType,
+    /// Which live shell is swapped for a special one
+    SpecialShell,
+    /// Which shell the Phone reveals
+    PhoneShell,
+    /// Anything rolled by a registered item
+    CustomItem(CustomItemId),
+}
+
+/// Decides the outcome of chance events for a Round
+pub trait ChanceProvider {
+    /// Index in [from, to)
+    fn choose(&mut self, event: ChanceEvent, from: usize, to: usize) -> usize;
+
+    /// True with the given probability
+    fn bernoulli(&mut self, event: ChanceEvent, probability: f32) -> bool;
+}
+
+/// One chance event met while following a path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChanceDraw {
+    pub event: ChanceEvent,
+    /// Position of the outcome among the possible ones, for bernoulli 0 is true
+    pub outcome: usize,
+    pub outcome_count: usize,
+    pub probability: f64,
+}
+
+/// Walks every combination of outcomes, depth first, by replaying the same action once per path
+#[derive(Debug, Clone, Default)]
+pub struct EnumeratingChance {
+    path: Vec<usize>,
+    draws: Vec<ChanceDraw>,
+}
+
+impl ChanceEvent {
+    /// The generator the event draws from when using RngStreams
+    pub fn stream(self) -> RngStream {
+        match self {
+            ChanceEvent::MaxHealth => RngStream::Health,
+            ChanceEvent::LoadoutSequence | ChanceEvent::NewItemCount => RngStream::Loadout,
+            ChanceEvent::Item(player_number) => RngStream::Items(player_number),
+            ChanceEvent::ShellType | ChanceEvent::SpecialShell => RngStream::Shells,
+            // items share the phone's stream so they can't shift the other draws
+            ChanceEvent::PhoneShell | ChanceEvent::CustomItem(_) => RngStream::Phone,
+        }
+    }
+}
+
+/// Draws the same numbers the game would
+impl<TRng> ChanceProvider for TRng
+where
+    TRng: RngCore,
+{
+    fn choose(&mut self, _event: ChanceEvent, from: usize, to: usize) -> usize {
+        self.randi_range(from as i32, to as i32 - 1) as usize
+    }
+
+    fn bernoulli(&mut self, _event: ChanceEvent, probability: f32) -> bool {
+        self.randf() < probability
+    }
+}
+
+impl<TChance> ChanceProvider for RngStreams<TChance>
+where
+    TChance: ChanceProvider,
+{
+    fn choose(&mut self, event: ChanceEvent, from: usize, to: usize) -> usize {
+        self.get(event.stream()).choose(event, from, to)
+    }
+
+    fn bernoulli(&mut self, event: ChanceEvent, probability: f32) -> bool {
+        self.get(event.stream()).bernoulli(event, probability)
+    }
+}
+
+impl EnumeratingChance {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Calls the function once per possible combination of outcomes with the probability of reaching it.
+    /// The function must be deterministic apart from the chance events. The count grows quickly across new loadouts
+    pub fn enumerate<F, TResult>(mut func: F) -> Vec<(TResult, f64)>
+    where
+        F: FnMut(&mut EnumeratingChance) -> TResult,
+    {
+        let mut chance = EnumeratingChance::new();
+        let mut results = Vec::new();
+        loop {
+            let result = func(&mut chance);
+            results.push((result, chance.probability()));
+            if !chance.advance() {
+                return results;
+            }
+        }
+    }
+
+    /// Events met since the path was last advanced
+    pub fn draws(&self) -> &[ChanceDraw] {
+        &self.draws
+    }
+
+    /// Probability of the current path
+    pub fn probability(&self) -> f64 {
+        self.draws.iter().map(|draw| draw.probability).product()
+    }
+
+    /// Moves on to the next path. False once every path has been followed
+    pub fn advance(&mut self) -> bool {
+        while let Some(draw) = self.draws.pop() {
+            if draw.outcome + 1 < draw.outcome_count {
+                self.path = self.draws.iter().map(|draw| draw.outcome).collect();
+                self.path.push(draw.outcome + 1);
+                self.draws.clear();
+                return true;
+            }
+        }
+
+        self.path.clear();
+        false
+    }
+
+    fn next_outcome(
+        &mut self,
+        event: ChanceEvent,
+        outcome_count: usize,
+        probability: f64,
+    ) -> usize {
+        // past the end of the path always take the first outcome, advance() moves on from there
+        let outcome = self.path.get(self.draws.len()).copied().unwrap_or(0);
+        assert!(
+            outcome < outcome_count,
+            "Chance event {:?} differs from the one on the path being replayed",
+            event
+        );
+
+        self.draws.push(ChanceDraw {
+            event,
+            outcome,
+            outcome_count,
+            probability,
+        });
+        outcome
+    }
+}
+
+impl ChanceProvider for EnumeratingChance {
+    fn choose(&mut self, event: ChanceEvent, from: usize, to: usize) -> usize {
+        assert!(from < to, "Chance event {:?} has no outcomes", event);
+        let outcome_count = to - from;
+        from + self.next_outcome(event, outcome_count, 1.0 / outcome_count as f64)
+    }
+
+    fn bernoulli(&mut self, event: ChanceEvent, probability: f32) -> bool {
+        let probability = f64::from(probability);
+        let outcome_probability = match self.path.get(self.draws.len()) {
+            Some(1) => 1.0 - probability,
+            _ => probability,
+        };
+
+        self.next_outcome(event, 2, outcome_probability) == 0
+    }
+}
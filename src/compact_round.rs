@@ -0,0 +1,1080 @@
+use std::hash::{Hash, Hasher};
+
+use crate::{
+    action::Action,
+    chance::{ChanceEvent, ChanceProvider},
+    decision::{ActionOutcome, SubmitError, TurnEnd},
+    game_players::GamePlayers,
+    item::{
+        global_item_limit, item_index, player_item_limit, Item, NotAdreneline, UnaryItem,
+        ALL_ITEMS, TOTAL_ITEMS,
+    },
+    loadout::Loadout,
+    modifier::{GameModifiers, Modifier},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    rng_streams::RngStreams,
+    round::{Round, ShotResult},
+    round_number::RoundNumber,
+    round_player::{HandcuffState, StunState},
+    round_rules::{Healing, RoundRules},
+    round_start_info::{RoundStartInfo, MAX_DEALT_HEALTH},
+    shell::{ShellType, ShotgunDamage},
+    turn::{HealResult, InvalidItemUseError, ItemUseResult, LearnedShell, ShotgunRackResult},
+    zobrist::{
+        active_seat_key, handcuff_key, item_key, live_shell_key, round_number_key, seat_key,
+        shell_count_key, shotgun_sawn_key, turn_order_inverted_key,
+    },
+};
+
+pub const MAX_SEATS: usize = 4;
+
+const SHOTGUN_SAWN: u8 = 1;
+const TURN_ORDER_INVERTED: u8 = 1 << 1;
+
+const HEALTH_MASK: u8 = 0b1111;
+const STUN_SHIFT: u8 = 4;
+const STUN_MASK: u8 = 0b11 << STUN_SHIFT;
+const OCCUPIED: u8 = 1 << 6;
+const WIRES_CUT: u8 = 1 << 7;
+
+// the order item pools are built in, same as initialize_item_count_map
+const DRAW_ORDER: [Item; TOTAL_ITEMS] = [
+    Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Remote)),
+    Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Phone)),
+    Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Inverter)),
+    Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::MagnifyingGlass)),
+    Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Cigarettes)),
+    Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Handsaw)),
+    Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Beer)),
+    Item::Adreneline,
+    Item::NotAdreneline(NotAdreneline::Jammer),
+    Item::NotAdreneline(NotAdreneline::Handcuffs),
+];
+
+/// A Round without its generator, packed so it can be copied without allocating. Plays by the same rules.
+/// Items are kept as counts, so the order and slots they were dealt in are lost and states that only differ by them are equal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactRound {
+    round_number: RoundNumber,
+    player_count: MultiplayerCount,
+    starting_player: PlayerNumber,
+    max_health: u8,
+    rules: RoundRules,
+    active_seat_index: u8,
+    first_dead_player: Option<PlayerNumber>,
+    winner: Option<PlayerNumber>,
+    modifiers: u8,
+    initial_blank_rounds: u8,
+    initial_live_rounds: u8,
+    new_items: u8,
+    undealt_items: [u8; MAX_SEATS],
+    shell_count: u8,
+    /// Bit i is set if the shell i from the front is live
+    live_shells: u8,
+    /// Health in the low bits, then stun state and whether the seat is occupied
+    seats: [u8; MAX_SEATS],
+    /// 0 free, 1 cuffed, 2 broken
+    handcuffs: [u8; MAX_SEATS],
+    /// Counts indexed by item_index
+    items: [[u8; TOTAL_ITEMS]; MAX_SEATS],
+    /// Zobrist hash of the above, updated along with it
+    hash: u64,
+}
+
+impl CompactRound {
+    /// None if the round deals custom items, loads special shells, has modifiers other than the Handsaw and Remote ones
+    /// or health that doesn't fit in the packed seats
+    pub fn from_round<TRng>(round: &Round<TRng>) -> Option<Self>
+    where
+        TRng: ChanceProvider,
+    {
+        if !round.item_registry().is_built_in()
+            || round.rules().special_shells.total() != 0
+            || !holds_health(round.max_health(), round.rules().healing)
+        {
+            return None;
+        }
+
+        let mut seats = [0; MAX_SEATS];
+        let mut items = [[0; TOTAL_ITEMS]; MAX_SEATS];
+        let mut handcuffs = [0; MAX_SEATS];
+        for (seat_index, seat) in round.seats().iter().enumerate() {
+            if let Some(player) = seat.player() {
+                if player.health() > i32::from(HEALTH_MASK) {
+                    return None;
+                }
+
+                seats[seat_index] = pack_seat(player.health() as u8, player.stun_state());
+                if player.wires_cut() {
+                    seats[seat_index] |= WIRES_CUT;
+                }
+                handcuffs[seat_index] = pack_handcuffs(player.handcuff_state());
+            }
+
+            for item in seat.items() {
+                items[seat_index][item_index(*item)] += 1;
+            }
+        }
+
+        let mut live_shells = 0;
+        for (index, shell) in round.shells().iter().enumerate() {
+            if shell.shell_type() == ShellType::Live {
+                live_shells |= 1 << index;
+            }
+        }
+
+        let game_modifiers = round.game_modifiers();
+        if !game_modifiers.iter().all(|modifier| {
+            *modifier == Modifier::sawn_shotgun() || *modifier == Modifier::inverted_turn_order()
+        }) {
+            return None;
+        }
+
+        let mut modifiers = 0;
+        if game_modifiers.shotgun_sawn() {
+            modifiers |= SHOTGUN_SAWN;
+        }
+        if game_modifiers.turn_order_inverted() {
+            modifiers |= TURN_ORDER_INVERTED;
+        }
+
+        let loadout = round.loadout();
+        let start_info = round.start_info();
+        let mut compact = CompactRound {
+            round_number: round.number(),
+            player_count: start_info.player_count,
+            starting_player: start_info.starting_player(),
+            max_health: round.max_health() as u8,
+            rules: *round.rules(),
+            active_seat_index: round.next_player().seat_index() as u8,
+            first_dead_player: round.first_dead_player(),
+            winner: None,
+            modifiers,
+            initial_blank_rounds: loadout.initial_blank_rounds as u8,
+            initial_live_rounds: loadout.initial_live_rounds as u8,
+            new_items: loadout.new_items as u8,
+            undealt_items: loadout.undealt_items.map(|count| count as u8),
+            shell_count: round.shells().len() as u8,
+            live_shells,
+            seats,
+            handcuffs,
+            items,
+            hash: 0,
+        };
+
+        compact.hash = compact.compute_hash();
+        Some(compact)
+    }
+
+    /// The first round of a game, dealt with the same draws as Round::with_rules. None if the rules load special shells
+    /// or overheal past what the packed seats hold
+    pub fn new<TChance>(
+        game_players: &GamePlayers,
+        rules: RoundRules,
+        chance: &mut TChance,
+    ) -> Option<Self>
+    where
+        TChance: ChanceProvider,
+    {
+        if rules.special_shells.total() != 0 || !holds_health(MAX_DEALT_HEALTH, rules.healing) {
+            return None;
+        }
+
+        Some(CompactRound::start(
+            game_players,
+            RoundNumber::One,
+            PlayerNumber::One,
+            rules,
+            chance,
+        ))
+    }
+
+    /// The round after this one, dealt with the same draws as Round::new. Panics if the round isn't over or was the last
+    pub fn next_round<TChance>(&self, game_players: &GamePlayers, chance: &mut TChance) -> Self
+    where
+        TChance: ChanceProvider,
+    {
+        assert!(
+            self.winner.is_some(),
+            "Attempted to continue an active round"
+        );
+        let round_number = match self.round_number {
+            RoundNumber::One => RoundNumber::Two,
+            RoundNumber::Two => RoundNumber::Three,
+            RoundNumber::Three => panic!("Attempted to create round from round 3"),
+        };
+
+        CompactRound::start(
+            game_players,
+            round_number,
+            self.first_dead_player.unwrap(),
+            self.rules,
+            chance,
+        )
+    }
+
+    fn start<TChance>(
+        game_players: &GamePlayers,
+        round_number: RoundNumber,
+        starting_player: PlayerNumber,
+        rules: RoundRules,
+        chance: &mut TChance,
+    ) -> Self
+    where
+        TChance: ChanceProvider,
+    {
+        assert!(game_players.remaining_players() > 1);
+
+        let player_count = game_players.multiplayer_count;
+        let seat_count = player_count as usize;
+        let forfeited = |seat_index: usize| {
+            game_players
+                .get(PlayerNumber::from_seat_index(seat_index).unwrap())
+                .unwrap()
+                .forfeited()
+        };
+
+        // forfeited players can't start, pass it on to the next seat
+        let mut active_seat_index = starting_player.seat_index();
+        while forfeited(active_seat_index) {
+            active_seat_index = (active_seat_index + 1) % seat_count;
+        }
+
+        let starting_player = PlayerNumber::from_seat_index(active_seat_index).unwrap();
+        let start_info = RoundStartInfo::new(starting_player, player_count, chance);
+        let max_health = start_info.max_health() as u8;
+
+        let mut seats = [0; MAX_SEATS];
+        for (seat_index, seat) in seats.iter_mut().enumerate().take(seat_count) {
+            if !forfeited(seat_index) {
+                *seat = pack_seat(max_health, StunState::Unstunned);
+            }
+        }
+
+        let mut compact = CompactRound {
+            round_number,
+            player_count,
+            starting_player,
+            max_health,
+            rules,
+            active_seat_index: active_seat_index as u8,
+            first_dead_player: None,
+            winner: None,
+            modifiers: 0,
+            initial_blank_rounds: 0,
+            initial_live_rounds: 0,
+            new_items: 0,
+            undealt_items: [0; MAX_SEATS],
+            shell_count: 0,
+            live_shells: 0,
+            seats,
+            handcuffs: [0; MAX_SEATS],
+            items: [[0; TOTAL_ITEMS]; MAX_SEATS],
+            hash: 0,
+        };
+
+        compact.new_loadout(chance);
+        compact
+    }
+
+    /// Continues the round as a full Round drawing from the streams. Panics if the round is over
+    pub fn into_round<TRng>(self, rng: RngStreams<TRng>) -> Round<TRng>
+    where
+        TRng: ChanceProvider,
+    {
+        assert!(
+            self.winner.is_none(),
+            "Attempted to convert a finished round"
+        );
+        Round::from_compact(&self, rng)
+    }
+
+    pub fn number(&self) -> RoundNumber {
+        self.round_number
+    }
+
+    pub fn player_count(&self) -> MultiplayerCount {
+        self.player_count
+    }
+
+    pub fn starting_player(&self) -> PlayerNumber {
+        self.starting_player
+    }
+
+    pub fn max_health(&self) -> u8 {
+        self.max_health
+    }
+
+    pub fn rules(&self) -> RoundRules {
+        self.rules
+    }
+
+    pub fn next_player(&self) -> PlayerNumber {
+        PlayerNumber::from_seat_index(self.active_seat_index as usize).unwrap()
+    }
+
+    pub fn first_dead_player(&self) -> Option<PlayerNumber> {
+        self.first_dead_player
+    }
+
+    /// Set once the round has ended, after which no more actions can be applied
+    pub fn winner(&self) -> Option<PlayerNumber> {
+        self.winner
+    }
+
+    pub fn modifiers(&self) -> GameModifiers {
+        let mut modifiers = GameModifiers::default();
+        if self.modifiers & SHOTGUN_SAWN != 0 {
+            modifiers.add(Modifier::sawn_shotgun()).unwrap();
+        }
+        if self.modifiers & TURN_ORDER_INVERTED != 0 {
+            modifiers.add(Modifier::inverted_turn_order()).unwrap();
+        }
+        modifiers
+    }
+
+    pub fn shotgun_sawn(&self) -> bool {
+        self.modifiers & SHOTGUN_SAWN != 0
+    }
+
+    pub fn turn_order_inverted(&self) -> bool {
+        self.modifiers & TURN_ORDER_INVERTED != 0
+    }
+
+    pub fn loadout(&self) -> Loadout {
+        Loadout {
+            initial_blank_rounds: self.initial_blank_rounds as usize,
+            initial_live_rounds: self.initial_live_rounds as usize,
+            new_items: self.new_items as usize,
+            undealt_items: self.undealt_items.map(usize::from),
+            special_rounds: Default::default(),
+        }
+    }
+
+    pub fn shell_count(&self) -> usize {
+        self.shell_count as usize
+    }
+
+    /// The shell at the index from the front of the shotgun
+    pub fn shell(&self, index: usize) -> Option<ShellType> {
+        if index >= self.shell_count() {
+            return None;
+        }
+
+        Some(if self.live_shells & (1 << index) != 0 {
+            ShellType::Live
+        } else {
+            ShellType::Blank
+        })
+    }
+
+    /// Zero if the player isn't seated
+    pub fn health(&self, player_number: PlayerNumber) -> u8 {
+        self.seats[player_number.seat_index()] & HEALTH_MASK
+    }
+
+    /// None if the player isn't seated
+    pub fn stun_state(&self, player_number: PlayerNumber) -> Option<StunState> {
+        let seat = self.seats[player_number.seat_index()];
+        if seat & OCCUPIED == 0 {
+            return None;
+        }
+
+        Some(match (seat & STUN_MASK) >> STUN_SHIFT {
+            0 => StunState::Unstunned,
+            1 => StunState::Stunned,
+            _ => StunState::Recovering,
+        })
+    }
+
+    /// None if the player isn't seated
+    pub fn handcuff_state(&self, player_number: PlayerNumber) -> Option<HandcuffState> {
+        let seat_index = player_number.seat_index();
+        if self.seats[seat_index] & OCCUPIED == 0 {
+            return None;
+        }
+
+        Some(match self.handcuffs[seat_index] {
+            0 => HandcuffState::Free,
+            1 => HandcuffState::Cuffed,
+            _ => HandcuffState::Broken,
+        })
+    }
+
+    pub fn wires_cut(&self, player_number: PlayerNumber) -> bool {
+        self.seats[player_number.seat_index()] & WIRES_CUT != 0
+    }
+
+    pub fn living_players(&self) -> impl Iterator<Item = PlayerNumber> + '_ {
+        (0..self.seat_count())
+            .filter(|seat_index| self.seats[*seat_index] & OCCUPIED != 0)
+            .map(|seat_index| PlayerNumber::from_seat_index(seat_index).unwrap())
+    }
+
+    pub fn item_count(&self, player_number: PlayerNumber, item: Item) -> u8 {
+        self.items[player_number.seat_index()][item_index(item)]
+    }
+
+    /// Items on the table in front of the seat, in ALL_ITEMS order
+    pub fn items(&self, player_number: PlayerNumber) -> impl Iterator<Item = Item> + '_ {
+        let counts = &self.items[player_number.seat_index()];
+        ALL_ITEMS
+            .iter()
+            .flat_map(move |item| (0..counts[item_index(*item)]).map(move |_| *item))
+    }
+
+    /// Covers the shells, seats, items, modifiers, active seat and round number. Stable across runs.
+    /// Kept up to date by apply, Round has no hash of its own so search from a CompactRound of it
+    pub fn zobrist_hash(&self) -> u64 {
+        self.hash
+    }
+
+    pub(crate) fn seat_count(&self) -> usize {
+        self.player_count as usize
+    }
+
+    /// Whether apply would accept the action, without applying it
+    pub fn check(&self, action: Action) -> Result<(), SubmitError> {
+        if self.winner.is_some() {
+            return Err(SubmitError::NoRound);
+        }
+
+        let active_seat_index = self.active_seat_index as usize;
+        match action {
+            Action::Shoot(target) => {
+                if !self.is_seated(target) {
+                    return Err(SubmitError::InvalidTarget);
+                }
+            }
+            Action::UseUnaryItem(unary_item) => {
+                self.check_unary_item(active_seat_index, unary_item)?
+            }
+            Action::UseAdreneline(target_player, target_item) => {
+                self.check_adreneline(
+                    target_player,
+                    Item::NotAdreneline(NotAdreneline::UnaryItem(target_item)),
+                )?;
+                self.check_unary_item(target_player.seat_index(), target_item)?;
+            }
+            Action::UseJammer(target_player) => {
+                self.check_can_jam(target_player)?;
+                if !self.has_item(
+                    active_seat_index,
+                    Item::NotAdreneline(NotAdreneline::Jammer),
+                ) {
+                    return Err(InvalidItemUseError::NoItem.into());
+                }
+            }
+            Action::UseAdrenelineThenJammer(theive_from, jam_target) => {
+                self.check_can_jam(jam_target)?;
+                self.check_adreneline(theive_from, Item::NotAdreneline(NotAdreneline::Jammer))?;
+            }
+            Action::UseHandcuffs(target_player) => {
+                self.check_can_handcuff(target_player)?;
+                if !self.has_item(
+                    active_seat_index,
+                    Item::NotAdreneline(NotAdreneline::Handcuffs),
+                ) {
+                    return Err(InvalidItemUseError::NoItem.into());
+                }
+            }
+            Action::UseAdrenelineThenHandcuffs(theive_from, cuff_target) => {
+                self.check_can_handcuff(cuff_target)?;
+                self.check_adreneline(theive_from, Item::NotAdreneline(NotAdreneline::Handcuffs))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies a single action for next_player(), like GameSession::submit but limited to this round.
+    /// Pass the Round's RngStreams to draw the same numbers it would
+    pub fn apply<TChance>(
+        &mut self,
+        action: Action,
+        chance: &mut TChance,
+    ) -> Result<ActionOutcome, SubmitError>
+    where
+        TChance: ChanceProvider,
+    {
+        self.check(action)?;
+
+        let actor = self.next_player();
+        let mut outcome = ActionOutcome {
+            actor,
+            action,
+            item_result: None,
+            shot_result: None,
+            burns: Vec::new(),
+            turn_end: None,
+            // only the session knows about the match
+            match_result: None,
+        };
+
+        let active_seat_index = self.active_seat_index as usize;
+        let item_result = match action {
+            Action::Shoot(target) => {
+                let (shot_result, turn_end) = self.shoot(actor, target, chance);
+                outcome.shot_result = Some(shot_result);
+                outcome.turn_end = Some(turn_end);
+                return Ok(outcome);
+            }
+            Action::UseUnaryItem(unary_item) => {
+                self.use_unary_item(active_seat_index, unary_item, chance)
+            }
+            Action::UseAdreneline(target_player, target_item) => {
+                let result = self.use_unary_item(target_player.seat_index(), target_item, chance);
+                self.remove_item(active_seat_index, Item::Adreneline);
+                result
+            }
+            Action::UseJammer(target_player) => {
+                self.remove_item(
+                    active_seat_index,
+                    Item::NotAdreneline(NotAdreneline::Jammer),
+                );
+                self.set_stun_state(target_player.seat_index(), StunState::Stunned);
+                ItemUseResult::StunnedPlayer(target_player)
+            }
+            Action::UseAdrenelineThenJammer(theive_from, jam_target) => {
+                self.remove_item(active_seat_index, Item::Adreneline);
+                self.remove_item(
+                    theive_from.seat_index(),
+                    Item::NotAdreneline(NotAdreneline::Jammer),
+                );
+                self.set_stun_state(jam_target.seat_index(), StunState::Stunned);
+                ItemUseResult::StunnedPlayer(jam_target)
+            }
+            Action::UseHandcuffs(target_player) => {
+                self.remove_item(
+                    active_seat_index,
+                    Item::NotAdreneline(NotAdreneline::Handcuffs),
+                );
+                self.set_handcuffs(target_player.seat_index(), HandcuffState::Cuffed);
+                ItemUseResult::HandcuffedPlayer(target_player)
+            }
+            Action::UseAdrenelineThenHandcuffs(theive_from, cuff_target) => {
+                self.remove_item(active_seat_index, Item::Adreneline);
+                self.remove_item(
+                    theive_from.seat_index(),
+                    Item::NotAdreneline(NotAdreneline::Handcuffs),
+                );
+                self.set_handcuffs(cuff_target.seat_index(), HandcuffState::Cuffed);
+                ItemUseResult::HandcuffedPlayer(cuff_target)
+            }
+        };
+
+        if let ItemUseResult::ShotgunRacked(ShotgunRackResult { empty: true, .. }) = item_result {
+            self.set_modifiers(self.modifiers & !SHOTGUN_SAWN);
+            self.new_loadout(chance);
+            let next_player = self.advance_turn();
+            outcome.turn_end = Some(TurnEnd::LoadoutEnds { next_player });
+        }
+
+        outcome.item_result = Some(item_result);
+        Ok(outcome)
+    }
+
+    fn is_seated(&self, player_number: PlayerNumber) -> bool {
+        player_number.seat_index() < self.seat_count()
+            && self.seats[player_number.seat_index()] & OCCUPIED != 0
+    }
+
+    fn is_other_seat(&self, player_number: PlayerNumber) -> bool {
+        let seat_index = player_number.seat_index();
+        seat_index < self.seat_count() && seat_index != self.active_seat_index as usize
+    }
+
+    fn has_item(&self, seat_index: usize, item: Item) -> bool {
+        self.items[seat_index][item_index(item)] > 0
+    }
+
+    fn remove_item(&mut self, seat_index: usize, item: Item) {
+        let index = item_index(item);
+        let count = self.items[seat_index][index];
+        self.items[seat_index][index] = count - 1;
+        self.hash ^= item_key(seat_index, index, count) ^ item_key(seat_index, index, count - 1);
+    }
+
+    fn set_seat(&mut self, seat_index: usize, packed_seat: u8) {
+        self.hash ^=
+            seat_key(seat_index, self.seats[seat_index]) ^ seat_key(seat_index, packed_seat);
+        self.seats[seat_index] = packed_seat;
+    }
+
+    fn set_modifiers(&mut self, modifiers: u8) {
+        let changed = self.modifiers ^ modifiers;
+        if changed & SHOTGUN_SAWN != 0 {
+            self.hash ^= shotgun_sawn_key();
+        }
+        if changed & TURN_ORDER_INVERTED != 0 {
+            self.hash ^= turn_order_inverted_key();
+        }
+        self.modifiers = modifiers;
+    }
+
+    /// Position of the shell counted from the first one of the loadout, so it doesn't change as shells are fired
+    fn shell_position(&self, index: usize) -> usize {
+        (self.initial_blank_rounds + self.initial_live_rounds - self.shell_count) as usize + index
+    }
+
+    fn compute_hash(&self) -> u64 {
+        let mut hash = round_number_key(self.round_number)
+            ^ active_seat_key(self.next_player())
+            ^ shell_count_key(self.shell_count());
+
+        for index in 0..self.shell_count() {
+            if self.live_shells & (1 << index) != 0 {
+                hash ^= live_shell_key(self.shell_position(index));
+            }
+        }
+
+        for seat_index in 0..self.seat_count() {
+            hash ^= seat_key(seat_index, self.seats[seat_index]);
+            hash ^= handcuff_key(seat_index, self.handcuffs[seat_index]);
+            for (index, count) in self.items[seat_index].iter().enumerate() {
+                hash ^= item_key(seat_index, index, *count);
+            }
+        }
+
+        if self.modifiers & SHOTGUN_SAWN != 0 {
+            hash ^= shotgun_sawn_key();
+        }
+        if self.modifiers & TURN_ORDER_INVERTED != 0 {
+            hash ^= turn_order_inverted_key();
+        }
+
+        hash
+    }
+
+    fn check_adreneline(
+        &self,
+        target_player: PlayerNumber,
+        target_item: Item,
+    ) -> Result<(), InvalidItemUseError> {
+        if !self.has_item(self.active_seat_index as usize, Item::Adreneline) {
+            return Err(InvalidItemUseError::NoItem);
+        }
+
+        if !self.is_other_seat(target_player)
+            || !self.has_item(target_player.seat_index(), target_item)
+        {
+            return Err(InvalidItemUseError::BadAdrenelineTarget);
+        }
+
+        Ok(())
+    }
+
+    fn check_can_handcuff(&self, target_player: PlayerNumber) -> Result<(), InvalidItemUseError> {
+        if !self.is_other_seat(target_player) {
+            return Err(InvalidItemUseError::InvalidHandcuffTarget);
+        }
+
+        match self.handcuff_state(target_player) {
+            Some(HandcuffState::Free) => Ok(()),
+            Some(HandcuffState::Cuffed | HandcuffState::Broken) => {
+                Err(InvalidItemUseError::DoubleHandcuff)
+            }
+            None => Err(InvalidItemUseError::InvalidHandcuffTarget),
+        }
+    }
+
+    fn check_can_jam(&self, target_player: PlayerNumber) -> Result<(), InvalidItemUseError> {
+        if !self.is_other_seat(target_player) {
+            return Err(InvalidItemUseError::InvalidStunTarget);
+        }
+
+        match self.stun_state(target_player) {
+            Some(StunState::Unstunned) => Ok(()),
+            Some(StunState::Stunned | StunState::Recovering) => {
+                Err(InvalidItemUseError::DoubleStun)
+            }
+            None => Err(InvalidItemUseError::InvalidStunTarget),
+        }
+    }
+
+    /// The item is on the seat's table and the actor can use it
+    fn check_unary_item(
+        &self,
+        owner_seat_index: usize,
+        unary_item: UnaryItem,
+    ) -> Result<(), InvalidItemUseError> {
+        let item = Item::NotAdreneline(NotAdreneline::UnaryItem(unary_item));
+        if matches!(unary_item, UnaryItem::Custom(_)) || !self.has_item(owner_seat_index, item) {
+            return Err(InvalidItemUseError::NoItem);
+        }
+
+        match unary_item {
+            UnaryItem::Cigarettes
+                if self.seats[self.active_seat_index as usize] & WIRES_CUT != 0 =>
+            {
+                Err(InvalidItemUseError::WiresCut)
+            }
+            UnaryItem::Handsaw if self.modifiers & SHOTGUN_SAWN != 0 => {
+                Err(InvalidItemUseError::DoubleSaw)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Uses an item from the seat's table, which loses it. Checked with check_unary_item first
+    fn use_unary_item<TChance>(
+        &mut self,
+        owner_seat_index: usize,
+        unary_item: UnaryItem,
+        chance: &mut TChance,
+    ) -> ItemUseResult
+    where
+        TChance: ChanceProvider,
+    {
+        let item = Item::NotAdreneline(NotAdreneline::UnaryItem(unary_item));
+
+        let mut use_result = ItemUseResult::Default;
+        match unary_item {
+            UnaryItem::Custom(_) => unreachable!("Compact rounds only hold built in items"),
+            UnaryItem::Remote => self.set_modifiers(self.modifiers ^ TURN_ORDER_INVERTED),
+            UnaryItem::Phone => {
+                if self.shell_count > 2 {
+                    let relative_index =
+                        chance.choose(ChanceEvent::PhoneShell, 2, self.shell_count());
+                    use_result = self.learn_shell(relative_index);
+                }
+            }
+            UnaryItem::Inverter => {
+                self.live_shells ^= 1;
+                self.hash ^= live_shell_key(self.shell_position(0));
+            }
+            UnaryItem::MagnifyingGlass => use_result = self.learn_shell(0),
+            UnaryItem::Cigarettes => {
+                let seat_index = self.active_seat_index as usize;
+                let seat = self.seats[seat_index];
+                let health = seat & HEALTH_MASK;
+                let limit = match self.rules.healing {
+                    Healing::Capped => self.max_health,
+                    Healing::Overheal(extra) => self.max_health + extra,
+                    Healing::Disabled => health,
+                };
+                debug_assert!(
+                    limit <= HEALTH_MASK,
+                    "Rounds that heal past the packed health are never compacted"
+                );
+
+                let healed = (health + 1).min(limit).max(health) - health;
+                self.set_seat(seat_index, (seat & !HEALTH_MASK) | (health + healed));
+                use_result = ItemUseResult::Healed(HealResult {
+                    requested: 1,
+                    healed,
+                });
+            }
+            UnaryItem::Handsaw => self.set_modifiers(self.modifiers | SHOTGUN_SAWN),
+            UnaryItem::Beer => {
+                let ejected_shell_type = self.pop_shell();
+                use_result = ItemUseResult::ShotgunRacked(ShotgunRackResult {
+                    empty: self.shell_count == 0,
+                    ejected_shell_type,
+                });
+            }
+        }
+
+        self.remove_item(owner_seat_index, item);
+
+        use_result
+    }
+
+    fn learn_shell(&self, relative_index: usize) -> ItemUseResult {
+        ItemUseResult::LearnedShell(LearnedShell {
+            relative_index,
+            shell_type: self.shell(relative_index).unwrap(),
+        })
+    }
+
+    fn pop_shell(&mut self) -> ShellType {
+        let shell_type = self.shell(0).unwrap();
+        if shell_type == ShellType::Live {
+            self.hash ^= live_shell_key(self.shell_position(0));
+        }
+
+        self.hash ^= shell_count_key(self.shell_count()) ^ shell_count_key(self.shell_count() - 1);
+        self.live_shells >>= 1;
+        self.shell_count -= 1;
+        shell_type
+    }
+
+    fn shoot<TChance>(
+        &mut self,
+        shooting_player: PlayerNumber,
+        target_player: PlayerNumber,
+        chance: &mut TChance,
+    ) -> (ShotResult, TurnEnd)
+    where
+        TChance: ChanceProvider,
+    {
+        let sawn = self.modifiers & SHOTGUN_SAWN != 0;
+        self.set_modifiers(self.modifiers & !SHOTGUN_SAWN);
+
+        let shell_type = self.pop_shell();
+        let target_seat_index = target_player.seat_index();
+        let mut wires_cut = false;
+        let mut health_lost = 0;
+        let damage = match shell_type {
+            ShellType::Live => {
+                let seat = self.seats[target_seat_index];
+                let health = if seat & WIRES_CUT != 0 {
+                    0
+                } else {
+                    (seat & HEALTH_MASK).saturating_sub(if sawn { 2 } else { 1 })
+                };
+                let killed = health == 0;
+                health_lost = (seat & HEALTH_MASK) - health;
+                wires_cut = !killed
+                    && seat & WIRES_CUT == 0
+                    && self.round_number == RoundNumber::Three
+                    && self
+                        .rules
+                        .sudden_death_threshold
+                        .is_some_and(|threshold| health <= threshold);
+                self.set_seat(
+                    target_seat_index,
+                    if killed {
+                        0
+                    } else {
+                        (seat & !HEALTH_MASK) | health | if wires_cut { WIRES_CUT } else { 0 }
+                    },
+                );
+                if killed {
+                    self.set_handcuffs(target_seat_index, HandcuffState::Free);
+                }
+
+                if sawn {
+                    ShotgunDamage::SawedShot(killed)
+                } else {
+                    ShotgunDamage::RegularShot(killed)
+                }
+            }
+            ShellType::Blank => ShotgunDamage::Blank,
+            ShellType::Slug | ShellType::Incendiary | ShellType::Dud => {
+                unreachable!("Compact rounds only hold live and blank shells")
+            }
+        };
+
+        let shot_result = ShotResult {
+            target_player,
+            damage,
+            health_lost,
+            wires_cut,
+        };
+
+        let advance_turn = match damage {
+            ShotgunDamage::RegularShot(killed) | ShotgunDamage::SawedShot(killed) => {
+                if killed {
+                    self.first_dead_player.get_or_insert(target_player);
+
+                    if self.living_players().count() == 1 {
+                        let winner = self.living_players().next().unwrap();
+                        self.winner = Some(winner);
+                        return (shot_result, TurnEnd::RoundEnds { winner });
+                    }
+                }
+
+                true
+            }
+            ShotgunDamage::Blank => target_player != shooting_player,
+            ShotgunDamage::Slug(_) | ShotgunDamage::Incendiary | ShotgunDamage::Jammed => {
+                unreachable!("Compact rounds only hold live and blank shells")
+            }
+        };
+
+        let next_player = if advance_turn {
+            self.advance_turn()
+        } else {
+            shooting_player
+        };
+
+        let turn_end = if self.shell_count == 0 {
+            self.new_loadout(chance);
+            TurnEnd::LoadoutEnds { next_player }
+        } else {
+            TurnEnd::LoadoutContinues
+        };
+
+        (shot_result, turn_end)
+    }
+
+    /// Same as Round::advance_turn
+    fn advance_turn(&mut self) -> PlayerNumber {
+        let seat_count = self.seat_count() as u8;
+        self.hash ^= active_seat_key(self.next_player());
+        loop {
+            self.active_seat_index = if self.modifiers & TURN_ORDER_INVERTED != 0 {
+                (self.active_seat_index + seat_count - 1) % seat_count
+            } else {
+                (self.active_seat_index + 1) % seat_count
+            };
+
+            let player_number = self.next_player();
+            let seat_index = self.active_seat_index as usize;
+            match self.stun_state(player_number) {
+                Some(StunState::Unstunned) => match self.handcuff_state(player_number) {
+                    Some(HandcuffState::Cuffed) => {
+                        self.set_handcuffs(seat_index, HandcuffState::Broken)
+                    }
+                    Some(handcuff_state) => {
+                        if handcuff_state == HandcuffState::Broken {
+                            self.set_handcuffs(seat_index, HandcuffState::Free);
+                        }
+                        self.hash ^= active_seat_key(player_number);
+                        return player_number;
+                    }
+                    None => unreachable!("Seated players have a handcuff state"),
+                },
+                Some(StunState::Stunned) => self.set_stun_state(seat_index, StunState::Recovering),
+                Some(StunState::Recovering) => {
+                    self.set_stun_state(seat_index, StunState::Unstunned)
+                }
+                None => {}
+            }
+        }
+    }
+
+    fn set_handcuffs(&mut self, seat_index: usize, handcuff_state: HandcuffState) {
+        let packed = pack_handcuffs(handcuff_state);
+        self.hash ^=
+            handcuff_key(seat_index, self.handcuffs[seat_index]) ^ handcuff_key(seat_index, packed);
+        self.handcuffs[seat_index] = packed;
+    }
+
+    fn set_stun_state(&mut self, seat_index: usize, stun_state: StunState) {
+        self.set_seat(
+            seat_index,
+            pack_seat(self.seats[seat_index] & HEALTH_MASK, stun_state)
+                | (self.seats[seat_index] & WIRES_CUT),
+        );
+    }
+
+    /// Same draws as Round::new_loadout
+    fn new_loadout<TChance>(&mut self, chance: &mut TChance)
+    where
+        TChance: ChanceProvider,
+    {
+        let loadout = Loadout::new(self.player_count, chance);
+        self.initial_blank_rounds = loadout.initial_blank_rounds as u8;
+        self.initial_live_rounds = loadout.initial_live_rounds as u8;
+        self.new_items = loadout.new_items as u8;
+        self.undealt_items = [0; MAX_SEATS];
+
+        let seat_count = self.seat_count();
+        let remaining_players = self.living_players().count();
+
+        let mut global_item_counts = [0; TOTAL_ITEMS];
+        for seat_items in &self.items[..seat_count] {
+            for (count, seat_count) in global_item_counts.iter_mut().zip(seat_items) {
+                *count += *seat_count as usize;
+            }
+        }
+
+        for _ in 0..loadout.new_items {
+            for seat_index in 0..seat_count {
+                if self.seats[seat_index] & OCCUPIED == 0 {
+                    continue;
+                }
+
+                let player_number = PlayerNumber::from_seat_index(seat_index).unwrap();
+                let held_items: u8 = self.items[seat_index].iter().sum();
+                if held_items as usize >= self.rules.item_capacity(player_number) {
+                    self.undealt_items[seat_index] += 1;
+                    continue;
+                }
+
+                let mut item_pool = [Item::Adreneline; TOTAL_ITEMS];
+                let mut pool_size = 0;
+                for item in DRAW_ORDER {
+                    let index = item_index(item);
+                    if (item == Item::NotAdreneline(NotAdreneline::Handcuffs)
+                        && !self.rules.handcuffs_dealt)
+                        || player_item_limit(item) <= self.items[seat_index][index] as usize
+                        || global_item_limit(item) <= global_item_counts[index]
+                        || (item
+                            == Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Remote))
+                            && remaining_players <= 2)
+                    {
+                        continue;
+                    }
+
+                    item_pool[pool_size] = item;
+                    pool_size += 1;
+                }
+
+                if pool_size == 0 {
+                    self.undealt_items[seat_index] += 1;
+                    continue;
+                }
+
+                let pool_index = chance.choose(ChanceEvent::Item(player_number), 0, pool_size);
+
+                let index = item_index(item_pool[pool_index]);
+                self.items[seat_index][index] += 1;
+                global_item_counts[index] += 1;
+            }
+        }
+
+        let mut blanks_to_load = loadout.initial_blank_rounds;
+        let mut lives_to_load = loadout.initial_live_rounds;
+        self.live_shells = 0;
+        self.shell_count = 0;
+        while blanks_to_load > 0 && lives_to_load > 0 {
+            if chance.bernoulli(ChanceEvent::ShellType, 0.5) {
+                blanks_to_load -= 1;
+            } else {
+                self.live_shells |= 1 << self.shell_count;
+                lives_to_load -= 1;
+            }
+            self.shell_count += 1;
+        }
+
+        self.shell_count += blanks_to_load as u8;
+        for _ in 0..lives_to_load {
+            self.live_shells |= 1 << self.shell_count;
+            self.shell_count += 1;
+        }
+
+        // most of the state changed, cheaper to start over
+        self.hash = self.compute_hash();
+    }
+}
+
+impl Hash for CompactRound {
+    fn hash<THasher>(&self, state: &mut THasher)
+    where
+        THasher: Hasher,
+    {
+        state.write_u64(self.hash);
+    }
+}
+
+fn pack_handcuffs(handcuff_state: HandcuffState) -> u8 {
+    match handcuff_state {
+        HandcuffState::Free => 0,
+        HandcuffState::Cuffed => 1,
+        HandcuffState::Broken => 2,
+    }
+}
+
+/// Everything the healing rule lets a player reach fits in the packed health
+fn holds_health(max_health: i32, healing: Healing) -> bool {
+    let limit = match healing {
+        Healing::Capped | Healing::Disabled => max_health,
+        Healing::Overheal(extra) => max_health + i32::from(extra),
+    };
+
+    limit <= i32::from(HEALTH_MASK)
+}
+
+fn pack_seat(health: u8, stun_state: StunState) -> u8 {
+    let stun_bits = match stun_state {
+        StunState::Unstunned => 0,
+        StunState::Stunned => 1,
+        StunState::Recovering => 2,
+    };
+
+    OCCUPIED | (stun_bits << STUN_SHIFT) | (health & HEALTH_MASK)
+}
@@ -0,0 +1,114 @@
+use thiserror::Error;
+
+use crate::{
+    action::Action,
+    chance::ChanceProvider,
+    item::Item,
+    match_result::MatchResult,
+    modifier::GameModifiers,
+    player_number::PlayerNumber,
+    round::{BurnResult, Round, RoundContinuation, ShotResult, TurnContinuation, TurnSummary},
+    round_number::RoundNumber,
+    round_player::RoundPlayer,
+    seat::SeatView,
+    turn::{InvalidItemUseError, ItemUseResult},
+};
+
+/// Number of rejected actions in a row after which a seat forfeits
+pub const MAX_ATTEMPTS: usize = 3;
+
+/// What the acting player can see when they have to make a decision, mirrors what a Turn exposes
+#[derive(Debug, Clone)]
+pub struct PendingDecision {
+    pub player: PlayerNumber,
+    pub round_number: RoundNumber,
+    pub round_player: RoundPlayer,
+    pub items: Vec<Item>,
+    pub other_seats: Vec<SeatView>,
+    pub shell_count: usize,
+    pub modifiers: GameModifiers,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TurnEnd {
+    LoadoutContinues,
+    LoadoutEnds { next_player: PlayerNumber },
+    RoundEnds { winner: PlayerNumber },
+}
+
+/// The result of one submitted action
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionOutcome {
+    pub actor: PlayerNumber,
+    pub action: Action,
+    pub item_result: Option<ItemUseResult>,
+    pub shot_result: Option<ShotResult>,
+    /// Burns taken as the turn was passed on
+    pub burns: Vec<BurnResult>,
+    /// Present if the action ended the turn
+    pub turn_end: Option<TurnEnd>,
+    /// Present if the action decided the match
+    pub match_result: Option<MatchResult>,
+}
+
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum SubmitError {
+    #[error("No round is active")]
+    NoRound,
+    #[error("The shot target is not a living player")]
+    InvalidTarget,
+    #[error(transparent)]
+    InvalidItemUse(#[from] InvalidItemUseError),
+}
+
+impl PendingDecision {
+    pub fn new<TRng>(round: &Round<TRng>) -> Self
+    where
+        TRng: ChanceProvider,
+    {
+        let player = round.next_player();
+        let seat = &round.seats()[player.seat_index()];
+        PendingDecision {
+            player,
+            round_number: round.number(),
+            round_player: seat.player().unwrap().clone(),
+            items: seat.items().clone(),
+            other_seats: round
+                .seats()
+                .iter()
+                .filter(|other_seat| other_seat.player_number() != player)
+                .map(|other_seat| other_seat.create_view(round.game_modifiers()))
+                .collect(),
+            shell_count: round.shells().len(),
+            modifiers: round.game_modifiers().clone(),
+        }
+    }
+}
+
+impl TurnEnd {
+    pub fn new<TRng>(turn_summary: &TurnSummary<TRng>) -> Self {
+        match &turn_summary.round_continuation {
+            RoundContinuation::RoundContinues(continued_round) => {
+                match continued_round.turn_continuation {
+                    TurnContinuation::LoadoutContinues => TurnEnd::LoadoutContinues,
+                    TurnContinuation::LoadoutEnds(next_player) => {
+                        TurnEnd::LoadoutEnds { next_player }
+                    }
+                }
+            }
+            RoundContinuation::RoundEnds(finished_round) => TurnEnd::RoundEnds {
+                winner: finished_round.winner(),
+            },
+        }
+    }
+}
+
+impl ActionOutcome {
+    /// If the shells were replaced, either by a new loadout or a new round
+    pub fn new_loadout(&self) -> bool {
+        matches!(
+            self.turn_end,
+            Some(TurnEnd::LoadoutEnds { .. }) | Some(TurnEnd::RoundEnds { .. })
+        )
+    }
+}
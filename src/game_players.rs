@@ -68,28 +68,61 @@ impl GamePlayers {
         vec
     }
 
-    pub fn register_win(
+    pub fn get(&self, player_number: PlayerNumber) -> Result<&Player, MissingPlayerError> {
+        match player_number {
+            PlayerNumber::One => Ok(&self.player_1),
+            PlayerNumber::Two => Ok(&self.player_2),
+            PlayerNumber::Three | PlayerNumber::Four => match &self.extra_players {
+                Some(extra_players) => {
+                    if player_number == PlayerNumber::Three {
+                        Ok(&extra_players.player_3)
+                    } else if let Some(player_4) = &extra_players.player_4 {
+                        Ok(player_4)
+                    } else {
+                        Err(MissingPlayerError::MissingPlayer)
+                    }
+                }
+                None => Err(MissingPlayerError::MissingPlayer),
+            },
+        }
+    }
+
+    pub fn get_mut(
         &mut self,
         player_number: PlayerNumber,
-        round_number: RoundNumber,
-    ) -> Result<(), MissingPlayerError> {
+    ) -> Result<&mut Player, MissingPlayerError> {
         match player_number {
-            PlayerNumber::One => self.player_1.register_win(round_number),
-            PlayerNumber::Two => self.player_2.register_win(round_number),
+            PlayerNumber::One => Ok(&mut self.player_1),
+            PlayerNumber::Two => Ok(&mut self.player_2),
             PlayerNumber::Three | PlayerNumber::Four => match &mut self.extra_players {
                 Some(extra_players) => {
                     if player_number == PlayerNumber::Three {
-                        extra_players.player_3.register_win(round_number);
+                        Ok(&mut extra_players.player_3)
                     } else if let Some(player_4) = &mut extra_players.player_4 {
-                        player_4.register_win(round_number);
+                        Ok(player_4)
                     } else {
-                        return Err(MissingPlayerError::MissingPlayer);
+                        Err(MissingPlayerError::MissingPlayer)
                     }
                 }
-                None => return Err(MissingPlayerError::MissingPlayer),
+                None => Err(MissingPlayerError::MissingPlayer),
             },
-        };
+        }
+    }
 
+    pub fn register_win(
+        &mut self,
+        player_number: PlayerNumber,
+        round_number: RoundNumber,
+    ) -> Result<(), MissingPlayerError> {
+        self.get_mut(player_number)?.register_win(round_number);
         Ok(())
     }
+
+    /// Number of players that have not forfeited
+    pub fn remaining_players(&self) -> usize {
+        self.as_vec()
+            .iter()
+            .filter(|player| !player.forfeited())
+            .count()
+    }
 }
@@ -1,18 +1,44 @@
 use crate::{
+    action::Action,
+    chance::ChanceProvider,
+    decision::{ActionOutcome, PendingDecision, SubmitError, TurnEnd},
     game_players::GamePlayers,
+    history::MatchHistory,
+    item_registry::ItemRegistry,
+    match_result::{MatchResult, MatchRules},
     multiplayer_count::MultiplayerCount,
-    round::{FinishedRoundOrRng, Round, RoundContinuation, TurnSummary, TurnSummaryOrRound},
-    round_number::RoundNumber,
-    turn::{TakenAction, Turn},
+    player_number::PlayerNumber,
+    rng_streams::RngStreams,
+    round::{
+        FinishedRound, FinishedRoundOrRng, Round, RoundContinuation, TurnSummary,
+        TurnSummaryOrRound,
+    },
+    round_rules::RoundRules,
+    seat::ForfeitedItems,
+    turn::{TakenAction, TakenTurn, TerminalAction, Turn},
 };
 use anyhow::Result;
-use rand::Rng;
+use rand::SeedableRng;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
 pub struct GameSession<TRng> {
     round: Option<Round<TRng>>,
     players: GamePlayers,
+    turns_taken: usize,
+    /// Kept once the last round is over
+    final_history: MatchHistory,
+    match_rules: MatchRules,
+    match_result: Option<MatchResult>,
+}
+
+/// Configuration for a new GameSession
+#[derive(Debug, Clone)]
+pub struct GameSessionOptions<TRng> {
+    pub rng_streams: RngStreams<TRng>,
+    pub rules: RoundRules,
+    pub item_registry: ItemRegistry,
+    pub match_rules: MatchRules,
 }
 
 #[derive(Error, Debug, Clone, Copy)]
@@ -21,15 +47,67 @@ pub enum NoRoundError {
     NoRound,
 }
 
+#[derive(Error, Debug, Clone, Copy)]
+pub enum ForfeitError {
+    #[error("Player has already forfeited")]
+    AlreadyForfeited,
+}
+
+impl<TRng> GameSessionOptions<TRng> {
+    /// All randomness comes from the one generator, like in the game
+    pub fn new(rng: TRng) -> Self {
+        GameSessionOptions {
+            rng_streams: RngStreams::Shared(rng),
+            rules: Default::default(),
+            item_registry: Default::default(),
+            match_rules: Default::default(),
+        }
+    }
+}
+
+impl<TRng> GameSessionOptions<TRng>
+where
+    TRng: SeedableRng,
+{
+    /// Independent streams per source of randomness, so games with the same seed only diverge where decisions do
+    pub fn with_stream_seed(seed: u64) -> Self {
+        GameSessionOptions {
+            rng_streams: RngStreams::from_seed(seed),
+            rules: Default::default(),
+            item_registry: Default::default(),
+            match_rules: Default::default(),
+        }
+    }
+}
+
 impl<TRng> GameSession<TRng>
 where
-    TRng: Rng,
+    TRng: ChanceProvider,
 {
     pub fn new(multiplayer_count: MultiplayerCount, rng: TRng) -> Self {
+        GameSession::with_options(multiplayer_count, GameSessionOptions::new(rng))
+    }
+
+    pub fn with_options(
+        multiplayer_count: MultiplayerCount,
+        options: GameSessionOptions<TRng>,
+    ) -> Self {
         let players = GamePlayers::new(multiplayer_count);
-        let round = Some(Round::new(&players, FinishedRoundOrRng::Rng(rng)));
+        let round = Some(Round::with_items(
+            &players,
+            FinishedRoundOrRng::Streams(options.rng_streams),
+            options.rules,
+            options.item_registry,
+        ));
 
-        GameSession { players, round }
+        GameSession {
+            players,
+            round,
+            turns_taken: 0,
+            final_history: Default::default(),
+            match_rules: options.match_rules,
+            match_result: None,
+        }
     }
 
     pub fn round(&self) -> Option<&Round<TRng>> {
@@ -59,6 +137,7 @@ where
                     }
                 };
 
+                self.turns_taken += 1;
                 let result = summary_func(&turn_summary);
 
                 match turn_summary.round_continuation {
@@ -66,19 +145,7 @@ where
                         self.round = Some(continued_round.round)
                     }
                     RoundContinuation::RoundEnds(finished_round) => {
-                        let finished_round_number = finished_round.number();
-                        self.players
-                            .register_win(finished_round.winner(), finished_round_number)?;
-
-                        match finished_round_number {
-                            RoundNumber::One | RoundNumber::Two => {
-                                self.round = Some(Round::new(
-                                    &self.players,
-                                    FinishedRoundOrRng::FinishedRound(finished_round),
-                                ))
-                            }
-                            RoundNumber::Three => self.round = None,
-                        }
+                        self.finish_round(finished_round)?
                     }
                 }
 
@@ -88,7 +155,156 @@ where
         }
     }
 
+    /// Who has to act next and what they can see, None if the game is over
+    pub fn pending_decision(&self) -> Option<PendingDecision> {
+        self.round().map(PendingDecision::new)
+    }
+
+    /// Applies a single action for the player of the pending decision
+    pub fn submit(&mut self, action: Action) -> Result<ActionOutcome, SubmitError> {
+        let round = self.round().ok_or(SubmitError::NoRound)?;
+        let actor = round.next_player();
+        if let Action::Shoot(target) = action {
+            if !round
+                .living_players()
+                .any(|seat| seat.player_number() == target)
+            {
+                return Err(SubmitError::InvalidTarget);
+            }
+        }
+
+        let mut item_result = None;
+        let turn_summary = self
+            .with_turn(
+                |turn| {
+                    let taken_action = action.take(turn);
+                    match &taken_action {
+                        TakenAction::Continued(continued_turn) => {
+                            item_result = Some(continued_turn.item_result().clone())
+                        }
+                        TakenAction::Terminal(TakenTurn {
+                            action: TerminalAction::Item(terminal_result),
+                            ..
+                        }) => item_result = Some(Ok(terminal_result.clone())),
+                        TakenAction::Terminal(_) => {}
+                    }
+
+                    taken_action
+                },
+                |turn_summary| {
+                    (
+                        turn_summary.shot_result.clone(),
+                        turn_summary.burns.clone(),
+                        TurnEnd::new(turn_summary),
+                    )
+                },
+            )
+            .expect("Round presence was checked");
+
+        let item_result = match item_result {
+            Some(result) => Some(result?),
+            None => None,
+        };
+
+        let (shot_result, burns, turn_end) = match turn_summary {
+            Some((shot_result, burns, turn_end)) => (shot_result, burns, Some(turn_end)),
+            None => (None, Vec::new(), None),
+        };
+
+        // finish_round only clears the round once the match is decided
+        let match_result = match self.round {
+            Some(_) => None,
+            None => self.match_result.clone(),
+        };
+
+        Ok(ActionOutcome {
+            actor,
+            action,
+            item_result,
+            shot_result,
+            burns,
+            turn_end,
+            match_result,
+        })
+    }
+
+    /// Removes a player from the game. If a round is active and they are still alive in it, their seat is vacated
+    pub fn forfeit(
+        &mut self,
+        player_number: PlayerNumber,
+        forfeited_items: ForfeitedItems,
+    ) -> Result<()> {
+        if self.round.is_none() {
+            Err(NoRoundError::NoRound)?
+        }
+
+        let player = self.players.get_mut(player_number)?;
+        if player.forfeited() {
+            Err(ForfeitError::AlreadyForfeited)?
+        }
+
+        player.forfeit();
+
+        let round = self.round.take().unwrap();
+        let seated = round
+            .living_players()
+            .any(|seat| seat.player_number() == player_number);
+        if !seated {
+            // already dead this round, just keep them out of the following ones
+            self.round = Some(round);
+            return Ok(());
+        }
+
+        match round.forfeit(player_number, forfeited_items) {
+            RoundContinuation::RoundContinues(continued_round) => {
+                self.round = Some(continued_round.round)
+            }
+            RoundContinuation::RoundEnds(finished_round) => self.finish_round(finished_round)?,
+        }
+
+        Ok(())
+    }
+
+    fn finish_round(&mut self, finished_round: FinishedRound<TRng>) -> Result<()> {
+        let finished_round_number = finished_round.number();
+        self.players
+            .register_win(finished_round.winner(), finished_round_number)?;
+
+        // also over once everyone else left
+        if self.match_rules.decided(&self.players) {
+            self.final_history = finished_round.history().clone();
+            self.match_result = Some(self.match_rules.result(&self.players));
+            self.round = None;
+            return Ok(());
+        }
+
+        self.round = Some(Round::new(
+            &self.players,
+            FinishedRoundOrRng::FinishedRound(Box::new(finished_round)),
+        ));
+
+        Ok(())
+    }
+
     pub fn players(&self) -> &GamePlayers {
         &self.players
     }
+
+    /// Set once the session is finished
+    pub fn match_result(&self) -> Option<&MatchResult> {
+        self.match_result.as_ref()
+    }
+
+    /// Everything that happened so far, including after the game is over
+    pub fn history(&self) -> &MatchHistory {
+        match &self.round {
+            Some(round) => round.history(),
+            None => &self.final_history,
+        }
+    }
+
+    /// Number of turns that have ended over the whole game
+    pub fn turns_taken(&self) -> usize {
+        self.turns_taken
+    }
 }
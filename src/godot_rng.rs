@@ -0,0 +1,134 @@
+use rand::{Error, RngCore, SeedableRng};
+
+// https://github.com/godotengine/godot/blob/4.3-stable/core/math/random_pcg.h
+const DEFAULT_INC: u64 = 1442695040888963407;
+const MULTIPLIER: u64 = 6364136223846793005;
+
+/// Godot's RandomNumberGenerator, a PCG32 seeded the same way so seeds reproduce the real game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GodotRng {
+    state: u64,
+    inc: u64,
+    seed: u64,
+}
+
+/// The RandomNumberGenerator methods the game calls, in terms of 32-bit draws
+pub trait GodotRandom: RngCore {
+    /// Unbiased integer in [0, bound), pcg32_boundedrand_r
+    fn rand_bounded(&mut self, bound: u32) -> u32 {
+        if bound & bound.wrapping_sub(1) == 0 {
+            return self.next_u32() & bound.wrapping_sub(1);
+        }
+
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let random = self.next_u32();
+            if random >= threshold {
+                return random % bound;
+            }
+        }
+    }
+
+    /// Integer in the inclusive range between from and to
+    fn randi_range(&mut self, from: i32, to: i32) -> i32 {
+        if from == to {
+            return from;
+        }
+
+        let bound = (from as i64 - to as i64).unsigned_abs() as u32;
+        self.rand_bounded(bound.wrapping_add(1)) as i32 + from.min(to)
+    }
+
+    /// Float in [0, 1]
+    fn randf(&mut self) -> f32 {
+        let proto_exp_offset = self.next_u32();
+        if proto_exp_offset == 0 {
+            return 0.0;
+        }
+
+        let significand = self.next_u32() | 0x80000001;
+        significand as f32 * 2f32.powi(-32 - proto_exp_offset.leading_zeros() as i32)
+    }
+}
+
+impl<TRng> GodotRandom for TRng where TRng: RngCore + ?Sized {}
+
+impl GodotRng {
+    /// Same as setting RandomNumberGenerator.seed
+    pub fn new(seed: u64) -> Self {
+        GodotRng::with_sequence(seed, DEFAULT_INC)
+    }
+
+    /// pcg32_srandom_r with an arbitrary stream
+    pub fn with_sequence(seed: u64, inc: u64) -> Self {
+        let mut rng = GodotRng {
+            state: 0,
+            inc: (inc << 1) | 1,
+            seed,
+        };
+
+        rng.next_pcg32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_pcg32();
+        rng
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Same as RandomNumberGenerator.state
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    pub fn set_state(&mut self, state: u64) {
+        self.state = state;
+    }
+
+    fn next_pcg32(&mut self) -> u32 {
+        let old_state = self.state;
+        self.state = old_state.wrapping_mul(MULTIPLIER).wrapping_add(self.inc);
+
+        let xor_shifted = (((old_state >> 18) ^ old_state) >> 27) as u32;
+        let rotation = (old_state >> 59) as u32;
+        xor_shifted.rotate_right(rotation)
+    }
+}
+
+impl RngCore for GodotRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_pcg32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let low = self.next_u32() as u64;
+        let high = self.next_u32() as u64;
+        (high << 32) | low
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(4) {
+            let bytes = self.next_u32().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for GodotRng {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        GodotRng::new(u64::from_le_bytes(seed))
+    }
+
+    /// Uses the seed as is instead of scrambling it, so it matches the one set in Godot
+    fn seed_from_u64(seed: u64) -> Self {
+        GodotRng::new(seed)
+    }
+}
@@ -0,0 +1,500 @@
+use rand::{Rng, SeedableRng};
+use thiserror::Error;
+
+use crate::{
+    action::Action,
+    compact_round::CompactRound,
+    decision::{SubmitError, TurnEnd},
+    game_players::GamePlayers,
+    item::{
+        item_index, unary_item_index, Item, ALL_ITEMS, ALL_UNARY_ITEMS, TOTAL_ITEMS,
+        TOTAL_UNARY_ITEMS,
+    },
+    match_result::MatchRules,
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    rng_streams::RngStreams,
+    round_player::{HandcuffState, StunState},
+    shell::ShellType,
+    shell_knowledge::ShellKnowledge,
+};
+
+pub const MAX_SEATS: usize = 4;
+
+const SHOOT_OFFSET: usize = 0;
+const UNARY_ITEM_OFFSET: usize = SHOOT_OFFSET + MAX_SEATS;
+const ADRENELINE_OFFSET: usize = UNARY_ITEM_OFFSET + TOTAL_UNARY_ITEMS;
+const JAMMER_OFFSET: usize = ADRENELINE_OFFSET + MAX_SEATS * TOTAL_UNARY_ITEMS;
+const ADRENELINE_JAMMER_OFFSET: usize = JAMMER_OFFSET + MAX_SEATS;
+const HANDCUFFS_OFFSET: usize = ADRENELINE_JAMMER_OFFSET + MAX_SEATS * MAX_SEATS;
+const ADRENELINE_HANDCUFFS_OFFSET: usize = HANDCUFFS_OFFSET + MAX_SEATS;
+
+/// Shoot per seat, each UnaryItem, Adreneline per seat and UnaryItem, Jammer per seat, Adreneline then Jammer per seat pair,
+/// Handcuffs per seat, Adreneline then Handcuffs per seat pair. Seats are relative to the acting player, offset 0 is themselves
+pub const ACTION_COUNT: usize = ADRENELINE_HANDCUFFS_OFFSET + MAX_SEATS * MAX_SEATS;
+
+/// Present, alive, health, stunned, recovering, cuffed, cuffs broken, item counts
+const SEAT_FEATURES: usize = 7 + TOTAL_ITEMS;
+const GLOBAL_OFFSET: usize = MAX_SEATS * SEAT_FEATURES;
+/// Max health, round number, shell count, initial live, initial blank, spent live, spent blank, inversions, sawn, inverted
+const GLOBAL_FEATURES: usize = 10;
+const KNOWN_SHELLS_OFFSET: usize = GLOBAL_OFFSET + GLOBAL_FEATURES;
+const KNOWN_SHELL_SLOTS: usize = 8;
+
+/// Seats relative to the acting player, global state, then one hot live/blank for each shell the player knows
+pub const OBSERVATION_SIZE: usize = KNOWN_SHELLS_OFFSET + KNOWN_SHELL_SLOTS * 2;
+
+pub type Observation = [f32; OBSERVATION_SIZE];
+pub type ActionMask = [bool; ACTION_COUNT];
+
+/// Policy used for every seat not controlled by the agent
+pub trait Opponent {
+    fn choose_action(
+        &mut self,
+        player: PlayerNumber,
+        observation: &Observation,
+        action_mask: &ActionMask,
+    ) -> usize;
+}
+
+/// Picks uniformly among legal actions
+#[derive(Debug, Clone)]
+pub struct RandomOpponent<TRng> {
+    rng: TRng,
+}
+
+#[derive(Debug, Clone)]
+pub struct StepInfo {
+    /// Winner of the last round that ended during the step
+    pub round_winner: Option<PlayerNumber>,
+    pub turns_taken: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct StepResult {
+    pub observation: Observation,
+    pub action_mask: ActionMask,
+    /// +1 for each round the agent wins, -1 for each round someone else wins
+    pub reward: f32,
+    pub done: bool,
+    pub info: StepInfo,
+}
+
+#[derive(Error, Debug, Clone, Copy)]
+pub enum GymError {
+    #[error("The environment must be reset before stepping")]
+    NotReset,
+    #[error("The episode is over")]
+    EpisodeOver,
+    #[error("Action {0} is not legal")]
+    IllegalAction(usize),
+    #[error(transparent)]
+    Submit(#[from] SubmitError),
+}
+
+/// Plays on a CompactRound so steps don't allocate
+#[derive(Debug, Clone)]
+pub struct GymEnv<TRng, TOpponent> {
+    multiplayer_count: MultiplayerCount,
+    agent_player: PlayerNumber,
+    opponent: TOpponent,
+    match_rules: MatchRules,
+    players: GamePlayers,
+    round: Option<CompactRound>,
+    rng: Option<RngStreams<TRng>>,
+    knowledge: Option<ShellKnowledge>,
+    turns_taken: usize,
+}
+
+impl<TRng> RandomOpponent<TRng> {
+    pub fn new(rng: TRng) -> Self {
+        RandomOpponent { rng }
+    }
+}
+
+impl<TRng> Opponent for RandomOpponent<TRng>
+where
+    TRng: Rng,
+{
+    fn choose_action(
+        &mut self,
+        _player: PlayerNumber,
+        _observation: &Observation,
+        action_mask: &ActionMask,
+    ) -> usize {
+        let legal_actions = action_mask.iter().filter(|legal| **legal).count();
+        let choice = self.rng.gen_range(0, legal_actions);
+        action_mask
+            .iter()
+            .enumerate()
+            .filter(|(_, legal)| **legal)
+            .nth(choice)
+            .unwrap()
+            .0
+    }
+}
+
+impl<TOpponent> Opponent for Box<TOpponent>
+where
+    TOpponent: Opponent + ?Sized,
+{
+    fn choose_action(
+        &mut self,
+        player: PlayerNumber,
+        observation: &Observation,
+        action_mask: &ActionMask,
+    ) -> usize {
+        (**self).choose_action(player, observation, action_mask)
+    }
+}
+
+impl<TRng, TOpponent> GymEnv<TRng, TOpponent>
+where
+    TRng: Rng + SeedableRng,
+    TOpponent: Opponent,
+{
+    pub fn new(
+        multiplayer_count: MultiplayerCount,
+        agent_player: PlayerNumber,
+        opponent: TOpponent,
+    ) -> Self {
+        assert!(agent_player.seat_index() < multiplayer_count as usize);
+        GymEnv {
+            multiplayer_count,
+            agent_player,
+            opponent,
+            match_rules: Default::default(),
+            players: GamePlayers::new(multiplayer_count),
+            round: None,
+            rng: None,
+            knowledge: None,
+            turns_taken: 0,
+        }
+    }
+
+    pub fn agent_player(&self) -> PlayerNumber {
+        self.agent_player
+    }
+
+    /// None before the first reset and once the game is over
+    pub fn round(&self) -> Option<&CompactRound> {
+        self.round.as_ref()
+    }
+
+    /// Number of turns that have ended since the last reset
+    pub fn turns_taken(&self) -> usize {
+        self.turns_taken
+    }
+
+    pub fn reset(&mut self, seed: u64) -> Result<Observation, GymError> {
+        self.players = GamePlayers::new(self.multiplayer_count);
+        self.turns_taken = 0;
+
+        // same first round as GameSession::new with the seed
+        let mut rng = RngStreams::Shared(TRng::seed_from_u64(seed));
+        let round = CompactRound::new(&self.players, Default::default(), &mut rng).unwrap();
+        self.knowledge = Some(ShellKnowledge::new(&round.loadout()));
+        self.round = Some(round);
+        self.rng = Some(rng);
+
+        // the reward for anything that happens before the agent's first turn is dropped
+        self.play_opponents()?;
+        Ok(self.observation())
+    }
+
+    pub fn step(&mut self, action_id: usize) -> Result<StepResult, GymError> {
+        if self.rng.is_none() {
+            return Err(GymError::NotReset);
+        }
+        let round = self.round.as_ref().ok_or(GymError::EpisodeOver)?;
+        assert!(round.next_player() == self.agent_player);
+
+        let action = legal_action(round, action_id)?;
+        let mut round_winner = self.apply(action)?;
+        let mut reward = self.reward(round_winner);
+
+        let (opponent_reward, opponent_round_winner) = self.play_opponents()?;
+        reward += opponent_reward;
+        if opponent_round_winner.is_some() {
+            round_winner = opponent_round_winner;
+        }
+
+        Ok(StepResult {
+            observation: self.observation(),
+            action_mask: self.action_mask(),
+            reward,
+            done: self.round.is_none(),
+            info: StepInfo {
+                round_winner,
+                turns_taken: self.turns_taken,
+            },
+        })
+    }
+
+    pub fn observation(&self) -> Observation {
+        match (&self.round, &self.knowledge) {
+            (Some(round), Some(knowledge)) => observe(round, knowledge, self.agent_player),
+            _ => [0.0; OBSERVATION_SIZE],
+        }
+    }
+
+    pub fn action_mask(&self) -> ActionMask {
+        match &self.round {
+            Some(round) => action_mask(round),
+            None => [false; ACTION_COUNT],
+        }
+    }
+
+    fn reward(&self, round_winner: Option<PlayerNumber>) -> f32 {
+        match round_winner {
+            Some(winner) if winner == self.agent_player => 1.0,
+            Some(_) => -1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Lets opponents act until it is the agent's turn or the game is over
+    fn play_opponents(&mut self) -> Result<(f32, Option<PlayerNumber>), GymError> {
+        let mut reward = 0.0;
+        let mut round_winner = None;
+        while let Some(round) = &self.round {
+            let player = round.next_player();
+            if player == self.agent_player {
+                break;
+            }
+
+            let observation = observe(round, self.knowledge.as_ref().unwrap(), player);
+            let action_id = self
+                .opponent
+                .choose_action(player, &observation, &action_mask(round));
+            let action = legal_action(round, action_id)?;
+            let action_round_winner = self.apply(action)?;
+            if action_round_winner.is_some() {
+                reward += self.reward(action_round_winner);
+                round_winner = action_round_winner;
+            }
+        }
+
+        Ok((reward, round_winner))
+    }
+
+    /// Returns the winner of the round if the action ended it
+    fn apply(&mut self, action: Action) -> Result<Option<PlayerNumber>, GymError> {
+        let round = self.round.as_mut().unwrap();
+        let outcome = round.apply(action, self.rng.as_mut().unwrap())?;
+        if outcome.turn_end.is_some() {
+            self.turns_taken += 1;
+        }
+
+        let round_winner = match outcome.turn_end {
+            Some(TurnEnd::RoundEnds { winner }) => {
+                self.next_round();
+                Some(winner)
+            }
+            _ => None,
+        };
+
+        self.knowledge.as_mut().unwrap().record_outcome(
+            &outcome,
+            self.round.as_ref().map(|round| round.loadout()).as_ref(),
+        );
+
+        Ok(round_winner)
+    }
+
+    /// Deals the next round like GameSession does, or ends the game
+    fn next_round(&mut self) {
+        let finished_round = self.round.take().unwrap();
+        self.players
+            .register_win(finished_round.winner().unwrap(), finished_round.number())
+            .unwrap();
+        if self.match_rules.decided(&self.players) {
+            return;
+        }
+
+        self.round = Some(finished_round.next_round(&self.players, self.rng.as_mut().unwrap()));
+    }
+}
+
+/// Decodes the action if the mask allows it
+fn legal_action(round: &CompactRound, action_id: usize) -> Result<Action, GymError> {
+    decode_action(round, round.next_player(), action_id)
+        .filter(|action| round.check(*action).is_ok())
+        .ok_or(GymError::IllegalAction(action_id))
+}
+
+fn relative_player(
+    round: &CompactRound,
+    player: PlayerNumber,
+    offset: usize,
+) -> Option<PlayerNumber> {
+    let seat_count = round.seat_count();
+    if offset >= seat_count {
+        return None;
+    }
+
+    // wraps at most once, cheaper than a division in the mask loop
+    let seat_index = player.seat_index() + offset;
+    PlayerNumber::from_seat_index(if seat_index >= seat_count {
+        seat_index - seat_count
+    } else {
+        seat_index
+    })
+}
+
+pub fn decode_action(
+    round: &CompactRound,
+    player: PlayerNumber,
+    action_id: usize,
+) -> Option<Action> {
+    if action_id < UNARY_ITEM_OFFSET {
+        relative_player(round, player, action_id - SHOOT_OFFSET).map(Action::Shoot)
+    } else if action_id < ADRENELINE_OFFSET {
+        Some(Action::UseUnaryItem(
+            ALL_UNARY_ITEMS[action_id - UNARY_ITEM_OFFSET],
+        ))
+    } else if action_id < JAMMER_OFFSET {
+        let index = action_id - ADRENELINE_OFFSET;
+        relative_player(round, player, index / TOTAL_UNARY_ITEMS)
+            .map(|target| Action::UseAdreneline(target, ALL_UNARY_ITEMS[index % TOTAL_UNARY_ITEMS]))
+    } else if action_id < ADRENELINE_JAMMER_OFFSET {
+        relative_player(round, player, action_id - JAMMER_OFFSET).map(Action::UseJammer)
+    } else if action_id < HANDCUFFS_OFFSET {
+        let index = action_id - ADRENELINE_JAMMER_OFFSET;
+        match (
+            relative_player(round, player, index / MAX_SEATS),
+            relative_player(round, player, index % MAX_SEATS),
+        ) {
+            (Some(theive_from), Some(jam_target)) => {
+                Some(Action::UseAdrenelineThenJammer(theive_from, jam_target))
+            }
+            _ => None,
+        }
+    } else if action_id < ADRENELINE_HANDCUFFS_OFFSET {
+        relative_player(round, player, action_id - HANDCUFFS_OFFSET).map(Action::UseHandcuffs)
+    } else if action_id < ACTION_COUNT {
+        let index = action_id - ADRENELINE_HANDCUFFS_OFFSET;
+        match (
+            relative_player(round, player, index / MAX_SEATS),
+            relative_player(round, player, index % MAX_SEATS),
+        ) {
+            (Some(theive_from), Some(cuff_target)) => {
+                Some(Action::UseAdrenelineThenHandcuffs(theive_from, cuff_target))
+            }
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+pub fn encode_action(round: &CompactRound, player: PlayerNumber, action: &Action) -> usize {
+    let seat_count = round.seat_count();
+    let offset = |target: PlayerNumber| {
+        (target.seat_index() + seat_count - player.seat_index()) % seat_count
+    };
+    match action {
+        Action::Shoot(target) => SHOOT_OFFSET + offset(*target),
+        Action::UseUnaryItem(unary_item) => UNARY_ITEM_OFFSET + unary_item_index(*unary_item),
+        Action::UseAdreneline(target, unary_item) => {
+            ADRENELINE_OFFSET + offset(*target) * TOTAL_UNARY_ITEMS + unary_item_index(*unary_item)
+        }
+        Action::UseJammer(target) => JAMMER_OFFSET + offset(*target),
+        Action::UseAdrenelineThenJammer(theive_from, jam_target) => {
+            ADRENELINE_JAMMER_OFFSET + offset(*theive_from) * MAX_SEATS + offset(*jam_target)
+        }
+        Action::UseHandcuffs(target) => HANDCUFFS_OFFSET + offset(*target),
+        Action::UseAdrenelineThenHandcuffs(theive_from, cuff_target) => {
+            ADRENELINE_HANDCUFFS_OFFSET + offset(*theive_from) * MAX_SEATS + offset(*cuff_target)
+        }
+    }
+}
+
+/// Which action ids the next player can take without the engine rejecting them
+pub fn action_mask(round: &CompactRound) -> ActionMask {
+    let mut mask = [false; ACTION_COUNT];
+    let player = round.next_player();
+    let has_adreneline = round.item_count(player, Item::Adreneline) > 0;
+    for (action_id, legal) in mask.iter_mut().enumerate() {
+        // most of the space needs Adreneline, skip it without decoding
+        let steal = (ADRENELINE_OFFSET..JAMMER_OFFSET).contains(&action_id)
+            || (ADRENELINE_JAMMER_OFFSET..HANDCUFFS_OFFSET).contains(&action_id)
+            || action_id >= ADRENELINE_HANDCUFFS_OFFSET;
+        if steal && !has_adreneline {
+            continue;
+        }
+
+        *legal = decode_action(round, player, action_id)
+            .is_some_and(|action| round.check(action).is_ok());
+    }
+
+    mask
+}
+
+/// The information set of the player, flattened
+pub fn observe(
+    round: &CompactRound,
+    knowledge: &ShellKnowledge,
+    player: PlayerNumber,
+) -> Observation {
+    let mut observation = [0.0; OBSERVATION_SIZE];
+
+    for offset in 0..MAX_SEATS {
+        let seat_player = match relative_player(round, player, offset) {
+            Some(seat_player) => seat_player,
+            None => continue,
+        };
+
+        let features = &mut observation[offset * SEAT_FEATURES..(offset + 1) * SEAT_FEATURES];
+        features[0] = 1.0;
+        if let Some(stun_state) = round.stun_state(seat_player) {
+            features[1] = 1.0;
+            features[2] = f32::from(round.health(seat_player));
+            match stun_state {
+                StunState::Unstunned => {}
+                StunState::Stunned => features[3] = 1.0,
+                StunState::Recovering => features[4] = 1.0,
+            }
+            match round.handcuff_state(seat_player).unwrap() {
+                HandcuffState::Free => {}
+                HandcuffState::Cuffed => features[5] = 1.0,
+                HandcuffState::Broken => features[6] = 1.0,
+            }
+        }
+
+        for item in &ALL_ITEMS {
+            features[7 + item_index(*item)] = f32::from(round.item_count(seat_player, *item));
+        }
+    }
+
+    let global_features = &mut observation[GLOBAL_OFFSET..KNOWN_SHELLS_OFFSET];
+    global_features[0] = f32::from(round.max_health());
+    global_features[1] = round.number() as usize as f32;
+    global_features[2] = round.shell_count() as f32;
+    global_features[3] = knowledge.initial_live_rounds() as f32;
+    global_features[4] = knowledge.initial_blank_rounds() as f32;
+    global_features[5] = knowledge.spent_live_rounds() as f32;
+    global_features[6] = knowledge.spent_blank_rounds() as f32;
+    global_features[7] = knowledge.inversions() as f32;
+    global_features[8] = if round.shotgun_sawn() { 1.0 } else { 0.0 };
+    global_features[9] = if round.turn_order_inverted() {
+        1.0
+    } else {
+        0.0
+    };
+
+    for relative_index in 0..KNOWN_SHELL_SLOTS.min(round.shell_count()) {
+        if let Some(shell_type) = knowledge.known_shell(player, relative_index) {
+            let slot = KNOWN_SHELLS_OFFSET + relative_index * 2;
+            match shell_type {
+                ShellType::Blank => observation[slot + 1] = 1.0,
+                // special shells share the live feature
+                ShellType::Live | ShellType::Slug | ShellType::Incendiary | ShellType::Dud => {
+                    observation[slot] = 1.0
+                }
+            }
+        }
+    }
+
+    observation
+}
@@ -0,0 +1,139 @@
+use crate::{
+    action::Action,
+    loadout::{DealtItem, Loadout},
+    player_number::PlayerNumber,
+    round::{BurnResult, ShotResult},
+    round_number::RoundNumber,
+    shell::ShellType,
+    turn::ItemUseResult,
+};
+
+/// Everything that happened in a match so far, carried from round to round
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchHistory {
+    entries: Vec<HistoryEntry>,
+    turn_index: usize,
+    loadout_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoryEntry {
+    pub round_number: RoundNumber,
+    /// Counts loadouts over the whole match
+    pub loadout_index: usize,
+    /// Counts every time play passed to a player over the whole match, including the first turn of each round
+    pub turn_index: usize,
+    pub event: HistoryEvent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HistoryEvent {
+    /// The shotgun was loaded and items dealt
+    Loadout {
+        loadout: Loadout,
+        dealt_items: Vec<DealtItem>,
+    },
+    /// Only successful uses, failed ones change nothing
+    ItemUsed {
+        player: PlayerNumber,
+        action: Action,
+        result: ItemUseResult,
+    },
+    Shot {
+        shooter: PlayerNumber,
+        shell_type: ShellType,
+        result: ShotResult,
+    },
+    Burned(BurnResult),
+    Forfeited {
+        player: PlayerNumber,
+    },
+    RoundEnded {
+        winner: PlayerNumber,
+        first_dead_player: PlayerNumber,
+        /// The winner's health when the round ended
+        health: i32,
+    },
+}
+
+impl HistoryEvent {
+    /// The player acted, was shot or was dealt an item
+    pub fn involves(&self, player_number: PlayerNumber) -> bool {
+        match self {
+            HistoryEvent::Loadout { dealt_items, .. } => dealt_items
+                .iter()
+                .any(|dealt_item| dealt_item.player_number == player_number),
+            HistoryEvent::ItemUsed { player, .. } | HistoryEvent::Forfeited { player } => {
+                *player == player_number
+            }
+            HistoryEvent::Shot {
+                shooter, result, ..
+            } => *shooter == player_number || result.target_player == player_number,
+            HistoryEvent::Burned(burn) => burn.player == player_number,
+            HistoryEvent::RoundEnded { winner, .. } => *winner == player_number,
+        }
+    }
+}
+
+impl MatchHistory {
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn by_player(&self, player_number: PlayerNumber) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.event.involves(player_number))
+    }
+
+    pub fn by_loadout(&self, loadout_index: usize) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.loadout_index == loadout_index)
+    }
+
+    pub fn by_round(&self, round_number: RoundNumber) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.round_number == round_number)
+    }
+
+    /// Loadouts so far, the index of each is its position
+    pub fn loadouts(&self) -> impl Iterator<Item = (&Loadout, &[DealtItem])> {
+        self.entries.iter().filter_map(|entry| match &entry.event {
+            HistoryEvent::Loadout {
+                loadout,
+                dealt_items,
+            } => Some((loadout, dealt_items.as_slice())),
+            HistoryEvent::ItemUsed { .. }
+            | HistoryEvent::Shot { .. }
+            | HistoryEvent::Burned(_)
+            | HistoryEvent::Forfeited { .. }
+            | HistoryEvent::RoundEnded { .. } => None,
+        })
+    }
+
+    pub fn turn_index(&self) -> usize {
+        self.turn_index
+    }
+
+    pub(crate) fn next_turn(&mut self) {
+        self.turn_index += 1;
+    }
+
+    pub(crate) fn record(&mut self, round_number: RoundNumber, event: HistoryEvent) {
+        if let HistoryEvent::Loadout { .. } = event {
+            self.loadout_count += 1;
+        }
+
+        self.entries.push(HistoryEntry {
+            round_number,
+            loadout_index: self.loadout_count.saturating_sub(1),
+            turn_index: self.turn_index,
+            event,
+        });
+    }
+}
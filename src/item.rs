@@ -2,12 +2,13 @@ use std::fmt::Display;
 
 use indexmap::IndexMap;
 
-pub const TOTAL_ITEMS: usize = 9;
+pub const TOTAL_ITEMS: usize = 10;
 
 pub const TOTAL_UNARY_ITEMS: usize = 7;
 
 // https://github.com/thecatontheceiling/buckshotroulette_multiplayer/blob/aed4aecb7fd7f6cec14a7bd17239e736039915c0/global%20scripts/MP_MatchCustomization.gd#L18
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UnaryItem {
     Remote,
     Phone,
@@ -16,15 +17,25 @@ pub enum UnaryItem {
     Cigarettes,
     Handsaw,
     Beer,
+    /// Registered in an ItemRegistry, behaves however it says
+    Custom(CustomItemId),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CustomItemId(pub u16);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NotAdreneline {
     UnaryItem(UnaryItem),
     Jammer,
+    /// From singleplayer, only dealt when the round rules ask for it
+    Handcuffs,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Item {
     NotAdreneline(NotAdreneline),
     Adreneline,
@@ -33,6 +44,9 @@ pub enum Item {
 impl Display for Item {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let str = match self {
+            Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Custom(id))) => {
+                return write!(f, "Custom item {}", id.0);
+            }
             Item::NotAdreneline(not_adreneline) => match not_adreneline {
                 NotAdreneline::UnaryItem(unary_item) => match unary_item {
                     UnaryItem::Remote => "Remote",
@@ -42,8 +56,10 @@ impl Display for Item {
                     UnaryItem::Cigarettes => "Cigarettes",
                     UnaryItem::Handsaw => "Handsaw",
                     UnaryItem::Beer => "Beer",
+                    UnaryItem::Custom(_) => unreachable!(),
                 },
                 NotAdreneline::Jammer => "Jammer",
+                NotAdreneline::Handcuffs => "Handcuffs",
             },
             Item::Adreneline => "Adreneline",
         };
@@ -62,8 +78,48 @@ pub static ALL_ITEMS: [Item; TOTAL_ITEMS] = [
     Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Beer)),
     Item::NotAdreneline(NotAdreneline::Jammer),
     Item::Adreneline,
+    Item::NotAdreneline(NotAdreneline::Handcuffs),
+];
+
+pub static ALL_UNARY_ITEMS: [UnaryItem; TOTAL_UNARY_ITEMS] = [
+    UnaryItem::Remote,
+    UnaryItem::Phone,
+    UnaryItem::Inverter,
+    UnaryItem::MagnifyingGlass,
+    UnaryItem::Cigarettes,
+    UnaryItem::Handsaw,
+    UnaryItem::Beer,
 ];
 
+/// Position of the item in ALL_ITEMS, custom items come after it
+pub fn item_index(item: Item) -> usize {
+    match item {
+        Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Custom(id))) => {
+            TOTAL_ITEMS + id.0 as usize
+        }
+        Item::NotAdreneline(not_adreneline) => match not_adreneline {
+            NotAdreneline::UnaryItem(unary_item) => unary_item_index(unary_item),
+            NotAdreneline::Jammer => 7,
+            NotAdreneline::Handcuffs => 9,
+        },
+        Item::Adreneline => 8,
+    }
+}
+
+/// Position of the item in ALL_UNARY_ITEMS, custom items come after it
+pub fn unary_item_index(unary_item: UnaryItem) -> usize {
+    match unary_item {
+        UnaryItem::Remote => 0,
+        UnaryItem::Phone => 1,
+        UnaryItem::Inverter => 2,
+        UnaryItem::MagnifyingGlass => 3,
+        UnaryItem::Cigarettes => 4,
+        UnaryItem::Handsaw => 5,
+        UnaryItem::Beer => 6,
+        UnaryItem::Custom(id) => TOTAL_UNARY_ITEMS + id.0 as usize,
+    }
+}
+
 pub fn initialize_item_count_map() -> IndexMap<Item, usize> {
     let mut map = IndexMap::with_capacity(TOTAL_ITEMS);
     map.insert(
@@ -97,11 +153,14 @@ pub fn initialize_item_count_map() -> IndexMap<Item, usize> {
     assert!(map.len() == TOTAL_UNARY_ITEMS);
     map.insert(Item::Adreneline, 0);
     map.insert(Item::NotAdreneline(NotAdreneline::Jammer), 0);
+    // last so the draws for the other items are the same whether or not it is dealt
+    map.insert(Item::NotAdreneline(NotAdreneline::Handcuffs), 0);
 
     assert!(map.len() == TOTAL_ITEMS);
     map
 }
 
+/// Custom items take their limits from the ItemRegistry
 pub fn global_item_limit(item: Item) -> usize {
     match item {
         Item::NotAdreneline(not_adreneline) => match not_adreneline {
@@ -113,8 +172,9 @@ pub fn global_item_limit(item: Item) -> usize {
                 | UnaryItem::Cigarettes
                 | UnaryItem::Handsaw
                 | UnaryItem::Beer => 32,
+                UnaryItem::Custom(_) => 0,
             },
-            NotAdreneline::Jammer => 1,
+            NotAdreneline::Jammer | NotAdreneline::Handcuffs => 1,
         },
         Item::Adreneline => 32,
     }
@@ -128,8 +188,9 @@ pub fn player_item_limit(item: Item) -> usize {
                 UnaryItem::MagnifyingGlass | UnaryItem::Handsaw => 2,
                 UnaryItem::Inverter => 4,
                 UnaryItem::Phone | UnaryItem::Beer => 8,
+                UnaryItem::Custom(_) => 0,
             },
-            NotAdreneline::Jammer => 1,
+            NotAdreneline::Jammer | NotAdreneline::Handcuffs => 1,
         },
         Item::Adreneline => 4,
     }
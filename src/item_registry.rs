@@ -0,0 +1,255 @@
+use std::{collections::VecDeque, fmt::Debug, sync::Arc};
+
+use indexmap::IndexMap;
+
+use crate::{
+    chance::{ChanceEvent, ChanceProvider},
+    item::{
+        global_item_limit, initialize_item_count_map, player_item_limit, CustomItemId, Item,
+        NotAdreneline, UnaryItem,
+    },
+    modifier::{GameModifiers, Modifier},
+    round_player::RoundPlayer,
+    round_rules::RoundRules,
+    shell::Shell,
+    turn::{HealResult, InvalidItemUseError, ItemUseResult, LearnedShell, ShotgunRackResult},
+    LOG_RNG,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemLimits {
+    /// Most one player can hold
+    pub player: usize,
+    /// Most on the whole table
+    pub global: usize,
+}
+
+/// What an item can change when it is used
+pub struct ItemContext<'context> {
+    pub shells: &'context mut VecDeque<Shell>,
+    pub modifiers: &'context mut GameModifiers,
+    /// The acting player, who may have stolen the item
+    pub player: &'context mut RoundPlayer,
+    /// The acting player's own items
+    pub items: &'context [Item],
+    pub rules: &'context RoundRules,
+    pub chance: &'context mut dyn ChanceProvider,
+}
+
+/// How an item is dealt and what it does
+pub trait ItemBehavior: Debug + Send + Sync {
+    fn name(&self) -> &str;
+
+    fn limits(&self) -> ItemLimits;
+
+    /// Whether the item goes in the pool for a loadout
+    fn can_deal(&self, remaining_players: usize, rules: &RoundRules) -> bool {
+        let _ = (remaining_players, rules);
+        true
+    }
+
+    /// Only called for unary items, the item is removed afterwards if this succeeds
+    fn use_item(&self, context: ItemContext) -> Result<ItemUseResult, InvalidItemUseError>;
+}
+
+/// Every item that can be dealt, in the order the pool is built
+#[derive(Debug, Clone)]
+pub struct ItemRegistry {
+    /// Shared so rounds stay cheap to clone
+    behaviors: Arc<IndexMap<Item, Arc<dyn ItemBehavior>>>,
+    built_in: bool,
+}
+
+#[derive(Debug)]
+struct BuiltInItem(Item);
+
+impl Default for ItemRegistry {
+    fn default() -> Self {
+        ItemRegistry::built_in()
+    }
+}
+
+impl ItemRegistry {
+    /// The game's items, in the order it deals them
+    pub fn built_in() -> Self {
+        let behaviors = initialize_item_count_map()
+            .into_keys()
+            .map(|item| (item, Arc::new(BuiltInItem(item)) as Arc<dyn ItemBehavior>))
+            .collect();
+
+        ItemRegistry {
+            behaviors: Arc::new(behaviors),
+            built_in: true,
+        }
+    }
+
+    /// Adds an item after every other one in the pool. Players use it like any other unary item
+    pub fn register<TBehavior>(&mut self, behavior: TBehavior) -> CustomItemId
+    where
+        TBehavior: ItemBehavior + 'static,
+    {
+        let id = CustomItemId(
+            self.items()
+                .filter(|item| {
+                    matches!(
+                        item,
+                        Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Custom(_)))
+                    )
+                })
+                .count() as u16,
+        );
+        self.built_in = false;
+        Arc::make_mut(&mut self.behaviors).insert(
+            Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Custom(id))),
+            Arc::new(behavior),
+        );
+        id
+    }
+
+    /// Changes what an already registered item does, keeping its place in the pool. False if it isn't registered
+    pub fn replace<TBehavior>(&mut self, item: Item, behavior: TBehavior) -> bool
+    where
+        TBehavior: ItemBehavior + 'static,
+    {
+        match Arc::make_mut(&mut self.behaviors).get_mut(&item) {
+            Some(entry) => {
+                *entry = Arc::new(behavior);
+                self.built_in = false;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// True if the registry still only has the game's items, doing what they do in the game
+    pub fn is_built_in(&self) -> bool {
+        self.built_in
+    }
+
+    pub fn behavior(&self, item: Item) -> Option<&dyn ItemBehavior> {
+        self.behaviors.get(&item).map(|behavior| behavior.as_ref())
+    }
+
+    pub fn limits(&self, item: Item) -> Option<ItemLimits> {
+        self.behavior(item).map(|behavior| behavior.limits())
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = Item> + '_ {
+        self.behaviors.keys().copied()
+    }
+
+    /// Items that go in the pool this loadout, in order
+    pub fn dealable_items<'registry>(
+        &'registry self,
+        remaining_players: usize,
+        rules: &'registry RoundRules,
+    ) -> impl Iterator<Item = Item> + 'registry {
+        self.behaviors
+            .iter()
+            .filter(move |(_, behavior)| behavior.can_deal(remaining_players, rules))
+            .map(|(item, _)| *item)
+    }
+}
+
+impl ItemBehavior for BuiltInItem {
+    fn name(&self) -> &str {
+        match self.0 {
+            Item::NotAdreneline(NotAdreneline::UnaryItem(unary_item)) => match unary_item {
+                UnaryItem::Remote => "Remote",
+                UnaryItem::Phone => "Phone",
+                UnaryItem::Inverter => "Inverter",
+                UnaryItem::MagnifyingGlass => "Magnifying Glass",
+                UnaryItem::Cigarettes => "Cigarettes",
+                UnaryItem::Handsaw => "Handsaw",
+                UnaryItem::Beer => "Beer",
+                UnaryItem::Custom(_) => unreachable!("Custom items aren't built in"),
+            },
+            Item::NotAdreneline(NotAdreneline::Jammer) => "Jammer",
+            Item::NotAdreneline(NotAdreneline::Handcuffs) => "Handcuffs",
+            Item::Adreneline => "Adreneline",
+        }
+    }
+
+    fn limits(&self) -> ItemLimits {
+        ItemLimits {
+            player: player_item_limit(self.0),
+            global: global_item_limit(self.0),
+        }
+    }
+
+    fn can_deal(&self, remaining_players: usize, rules: &RoundRules) -> bool {
+        match self.0 {
+            Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Remote)) => {
+                remaining_players > 2
+            }
+            Item::NotAdreneline(NotAdreneline::Handcuffs) => rules.handcuffs_dealt,
+            _ => true,
+        }
+    }
+
+    fn use_item(&self, context: ItemContext) -> Result<ItemUseResult, InvalidItemUseError> {
+        let unary_item = match self.0 {
+            Item::NotAdreneline(NotAdreneline::UnaryItem(unary_item)) => unary_item,
+            _ => unreachable!("Targeted items are used through their own actions"),
+        };
+
+        let mut use_result = None;
+        match unary_item {
+            UnaryItem::Remote => context
+                .modifiers
+                .add(Modifier::inverted_turn_order())
+                .expect("Toggled modifiers are never refused"),
+            UnaryItem::Phone => {
+                if context.shells.len() > 2 {
+                    let relative_index =
+                        context
+                            .chance
+                            .choose(ChanceEvent::PhoneShell, 2, context.shells.len());
+
+                    if LOG_RNG {
+                        println!("Phone revealed shell {}", relative_index);
+                    }
+
+                    use_result = learn_shell(context.shells, relative_index)
+                }
+            }
+            UnaryItem::Inverter => context.shells[0].invert(),
+            UnaryItem::MagnifyingGlass => use_result = learn_shell(context.shells, 0),
+            UnaryItem::Cigarettes => {
+                if context.player.wires_cut() {
+                    return Err(InvalidItemUseError::WiresCut);
+                }
+
+                let healed = context.player.gain_health(1, context.rules.healing);
+                use_result = Some(ItemUseResult::Healed(HealResult {
+                    requested: 1,
+                    healed,
+                }));
+            }
+            UnaryItem::Handsaw => {
+                context
+                    .modifiers
+                    .add(Modifier::sawn_shotgun())
+                    .map_err(|_| InvalidItemUseError::DoubleSaw)?;
+            }
+            UnaryItem::Beer => {
+                let ejected_shell = context.shells.pop_front().unwrap();
+
+                use_result = Some(ItemUseResult::ShotgunRacked(ShotgunRackResult {
+                    empty: context.shells.is_empty(),
+                    ejected_shell_type: ejected_shell.visible_type(),
+                }));
+            }
+            UnaryItem::Custom(_) => unreachable!("Custom items aren't built in"),
+        }
+
+        Ok(use_result.unwrap_or(ItemUseResult::Default))
+    }
+}
+
+fn learn_shell(shells: &VecDeque<Shell>, relative_index: usize) -> Option<ItemUseResult> {
+    Some(ItemUseResult::LearnedShell(LearnedShell {
+        relative_index,
+        shell_type: shells[relative_index].visible_type(),
+    }))
+}
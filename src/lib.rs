@@ -1,16 +1,42 @@
+pub mod action;
+pub mod async_driver;
+pub mod bot_protocol;
+pub mod chance;
+pub mod compact_round;
+pub mod decision;
 pub mod game_players;
 pub mod game_session;
+pub mod godot_rng;
+pub mod gym;
+pub mod history;
 pub mod item;
+pub mod item_registry;
 pub mod loadout;
+pub mod match_result;
+pub mod modifier;
 pub mod multiplayer_count;
 pub mod player;
 pub mod player_number;
+#[cfg(feature = "protocol")]
+pub mod protocol;
+#[cfg(feature = "provably_fair")]
+pub mod provably_fair;
+pub mod rng_streams;
 pub mod round;
 pub mod round_number;
 pub mod round_player;
+pub mod round_rules;
 pub mod round_start_info;
+#[cfg(feature = "scripting")]
+pub mod scripting;
 pub mod seat;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod shell;
+pub mod shell_knowledge;
+pub mod spectator;
+pub mod stats;
 pub mod turn;
+pub mod zobrist;
 
 pub(crate) const LOG_RNG: bool = cfg!(feature = "print_rng_to_stdout");
@@ -1,6 +1,11 @@
-use rand::Rng;
-
-use crate::{multiplayer_count::MultiplayerCount, LOG_RNG};
+use crate::{
+    chance::{ChanceEvent, ChanceProvider},
+    item::Item,
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    shell::SpecialShells,
+    LOG_RNG,
+};
 
 pub const MAX_SHELLS: u32 = 8;
 
@@ -10,26 +15,40 @@ struct Sequence {
     num_blank: usize,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Loadout {
     pub initial_blank_rounds: usize,
     pub initial_live_rounds: usize,
     pub new_items: usize,
+    /// Items each seat missed out on because it was full, indexed by seat
+    pub undealt_items: [usize; 4],
+    /// Special shells loaded in place of live ones, not counted in initial_live_rounds
+    pub special_rounds: SpecialShells,
+}
+
+/// An item given to a player when a loadout started
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DealtItem {
+    pub player_number: PlayerNumber,
+    pub item: Item,
+    pub slot: usize,
 }
 
 impl Loadout {
-    pub fn new<TRng>(multiplayer_count: MultiplayerCount, rng: &mut TRng) -> Self
+    pub fn new<TChance>(multiplayer_count: MultiplayerCount, chance: &mut TChance) -> Self
     where
-        TRng: Rng,
+        TChance: ChanceProvider,
     {
-        let sequence = get_sequence(multiplayer_count, rng);
+        let sequence = get_sequence(multiplayer_count, chance);
 
         // https://github.com/thecatontheceiling/buckshotroulette_multiplayer/blob/aed4aecb7fd7f6cec14a7bd17239e736039915c0/global%20scripts/MP_RoundManager.gd#L528
         // #4 overridden in mp_main.tscn
         let new_items = match multiplayer_count {
-            MultiplayerCount::Two => rng.gen_range(2, 5),
-            MultiplayerCount::Three => rng.gen_range(3, 6),
-            MultiplayerCount::Four => rng.gen_range(2, 5),
+            MultiplayerCount::Two => chance.choose(ChanceEvent::NewItemCount, 2, 5),
+            MultiplayerCount::Three => chance.choose(ChanceEvent::NewItemCount, 3, 6),
+            MultiplayerCount::Four => chance.choose(ChanceEvent::NewItemCount, 2, 5),
         };
 
         if LOG_RNG {
@@ -40,54 +59,62 @@ impl Loadout {
             initial_blank_rounds: sequence.num_blank,
             initial_live_rounds: sequence.num_live,
             new_items,
+            undealt_items: [0; 4],
+            special_rounds: Default::default(),
         }
     }
 }
 
-fn get_sequence<TRng>(multiplayer_count: MultiplayerCount, rng: &mut TRng) -> Sequence
+// found in mp_main.tscn
+static TWO_PLAYER_SEQUENCES: [Sequence; 9] = [
+    s(1, 1),
+    s(1, 2),
+    s(2, 1),
+    s(2, 2),
+    s(2, 3),
+    s(3, 1),
+    s(3, 2),
+    s(3, 3),
+    s(4, 2),
+];
+
+static THREE_PLAYER_SEQUENCES: [Sequence; 10] = [
+    s(1, 1),
+    s(2, 2),
+    s(2, 3),
+    s(3, 1),
+    s(3, 2),
+    s(3, 3),
+    s(3, 4),
+    s(4, 2),
+    s(4, 3),
+    s(4, 4),
+];
+
+static FOUR_PLAYER_SEQUENCES: [Sequence; 10] = [
+    s(2, 1),
+    s(2, 2),
+    s(3, 1),
+    s(3, 2),
+    s(3, 3),
+    s(3, 4),
+    s(3, 4),
+    s(4, 2),
+    s(4, 3),
+    s(4, 4),
+];
+
+fn get_sequence<TChance>(multiplayer_count: MultiplayerCount, chance: &mut TChance) -> Sequence
 where
-    TRng: Rng,
+    TChance: ChanceProvider,
 {
-    // found in mp_main.tscn
-    let sequences = match multiplayer_count {
-        MultiplayerCount::Two => vec![
-            s(1, 1),
-            s(1, 2),
-            s(2, 1),
-            s(2, 2),
-            s(2, 3),
-            s(3, 1),
-            s(3, 2),
-            s(3, 3),
-            s(4, 2),
-        ],
-        MultiplayerCount::Three => vec![
-            s(1, 1),
-            s(2, 2),
-            s(2, 3),
-            s(3, 1),
-            s(3, 2),
-            s(3, 3),
-            s(3, 4),
-            s(4, 2),
-            s(4, 3),
-            s(4, 4),
-        ],
-        MultiplayerCount::Four => vec![
-            s(2, 1),
-            s(2, 2),
-            s(3, 1),
-            s(3, 2),
-            s(3, 3),
-            s(3, 4),
-            s(3, 4),
-            s(4, 2),
-            s(4, 3),
-            s(4, 4),
-        ],
+    let sequences: &[Sequence] = match multiplayer_count {
+        MultiplayerCount::Two => &TWO_PLAYER_SEQUENCES,
+        MultiplayerCount::Three => &THREE_PLAYER_SEQUENCES,
+        MultiplayerCount::Four => &FOUR_PLAYER_SEQUENCES,
     };
 
-    let sequence_index = rng.gen_range(0, sequences.len());
+    let sequence_index = chance.choose(ChanceEvent::LoadoutSequence, 0, sequences.len());
     if LOG_RNG {
         println!(
             "Selecting sequence index {}/{}",
@@ -98,7 +125,7 @@ where
     sequences[sequence_index]
 }
 
-fn s(num_live: usize, num_blank: usize) -> Sequence {
+const fn s(num_live: usize, num_blank: usize) -> Sequence {
     Sequence {
         num_live,
         num_blank,
@@ -0,0 +1,159 @@
+use crate::{game_players::GamePlayers, player_number::PlayerNumber, round_number::RoundNumber};
+
+const TOTAL_ROUNDS: usize = 3;
+
+/// How the winner of the match is picked from the round winners
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WinCriterion {
+    #[default]
+    MostRounds,
+    /// The match stops as soon as a player has won two rounds
+    FirstToTwo,
+    /// Only the winner of the last round counts
+    FinalRound,
+}
+
+/// Picks between players with the same number of round wins
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tiebreak {
+    /// The tied player who won the latest round
+    #[default]
+    LatestWin,
+    /// The tied player who won the earliest round
+    EarliestWin,
+    /// Nobody wins
+    Draw,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchRules {
+    pub criterion: WinCriterion,
+    pub tiebreak: Tiebreak,
+    /// Stops once the rounds left can't change the winner
+    pub end_early: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchResult {
+    /// None on a draw
+    pub winner: Option<PlayerNumber>,
+    /// Indexed by seat
+    pub rounds_won: Vec<usize>,
+    /// Winner of each round played, in order
+    pub round_winners: Vec<PlayerNumber>,
+    /// Fewer than three rounds were played
+    pub ended_early: bool,
+}
+
+impl MatchResult {
+    pub fn rounds_won(&self, player_number: PlayerNumber) -> usize {
+        self.rounds_won
+            .get(player_number.seat_index())
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl MatchRules {
+    /// True if the match is over after the rounds played so far
+    pub fn decided(&self, players: &GamePlayers) -> bool {
+        let round_winners = round_winners(players);
+        let rounds_left = TOTAL_ROUNDS - round_winners.len();
+        if rounds_left == 0 || players.remaining_players() < 2 {
+            return true;
+        }
+
+        let mut rounds_won = rounds_won(players, &round_winners);
+        rounds_won.sort_unstable_by(|a, b| b.cmp(a));
+        match self.criterion {
+            WinCriterion::FirstToTwo => rounds_won[0] >= 2,
+            WinCriterion::MostRounds => {
+                self.end_early && rounds_won[0] > rounds_won[1] + rounds_left
+            }
+            WinCriterion::FinalRound => false,
+        }
+    }
+
+    pub fn result(&self, players: &GamePlayers) -> MatchResult {
+        let round_winners = round_winners(players);
+        let rounds_won = rounds_won(players, &round_winners);
+
+        let remaining: Vec<PlayerNumber> = players
+            .as_vec()
+            .into_iter()
+            .filter(|player| !player.forfeited())
+            .map(|player| player.number())
+            .collect();
+
+        let winner = if let [last_player] = remaining.as_slice() {
+            Some(*last_player)
+        } else {
+            match self.criterion {
+                WinCriterion::FinalRound => round_winners.last().copied(),
+                WinCriterion::MostRounds | WinCriterion::FirstToTwo => {
+                    let most = remaining
+                        .iter()
+                        .map(|player_number| rounds_won[player_number.seat_index()])
+                        .max()
+                        .unwrap_or(0);
+                    let tied: Vec<PlayerNumber> = remaining
+                        .into_iter()
+                        .filter(|player_number| rounds_won[player_number.seat_index()] == most)
+                        .collect();
+                    self.break_tie(&tied, &round_winners)
+                }
+            }
+        };
+
+        MatchResult {
+            winner,
+            ended_early: round_winners.len() < TOTAL_ROUNDS,
+            rounds_won,
+            round_winners,
+        }
+    }
+
+    fn break_tie(
+        &self,
+        tied: &[PlayerNumber],
+        round_winners: &[PlayerNumber],
+    ) -> Option<PlayerNumber> {
+        if let [winner] = tied {
+            return Some(*winner);
+        }
+
+        let mut tied_winners = round_winners
+            .iter()
+            .filter(|round_winner| tied.contains(round_winner));
+        match self.tiebreak {
+            Tiebreak::LatestWin => tied_winners.next_back().copied(),
+            Tiebreak::EarliestWin => tied_winners.next().copied(),
+            Tiebreak::Draw => None,
+        }
+    }
+}
+
+fn round_winners(players: &GamePlayers) -> Vec<PlayerNumber> {
+    [RoundNumber::One, RoundNumber::Two, RoundNumber::Three]
+        .into_iter()
+        .filter_map(|round_number| {
+            players
+                .as_vec()
+                .into_iter()
+                .find(|player| player.wins().contains(&round_number))
+                .map(|player| player.number())
+        })
+        .collect()
+}
+
+fn rounds_won(players: &GamePlayers, round_winners: &[PlayerNumber]) -> Vec<usize> {
+    let mut rounds_won = vec![0; players.as_vec().len()];
+    for round_winner in round_winners {
+        rounds_won[round_winner.seat_index()] += 1;
+    }
+    rounds_won
+}
@@ -0,0 +1,225 @@
+use thiserror::Error;
+
+use crate::{player_number::PlayerNumber, shell::ShellType};
+
+/// What a modifier does while it is active
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModifierEffect {
+    /// Doubles the damage of live and incendiary shells
+    Sawn,
+    /// Added to the damage of any shell that hits
+    ExtraDamage(u8),
+    /// Turns pass to the previous seat. An even number of these cancel out
+    InvertedTurnOrder,
+}
+
+/// Who a modifier applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModifierScope {
+    Table,
+    /// Only shots fired by the player and turns passed on by them
+    Player(PlayerNumber),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModifierLifetime {
+    /// Until the next shot, or the end of the turn if no shot is fired
+    NextShot,
+    EndOfTurn,
+    EndOfLoadout,
+    /// Until the given number of turns have passed, counting the current one
+    Turns(u8),
+    /// Until the round ends
+    Round,
+}
+
+/// What happens when a modifier with the same effect and scope is already active
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Stacking {
+    Stack,
+    /// Refused
+    Exclusive,
+    /// Removes the active one instead
+    Toggle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Modifier {
+    pub effect: ModifierEffect,
+    pub scope: ModifierScope,
+    pub lifetime: ModifierLifetime,
+    pub stacking: Stacking,
+}
+
+/// Active modifiers, in the order they were added
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameModifiers {
+    modifiers: Vec<Modifier>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModifierError {
+    #[error("An exclusive modifier with the same effect and scope is already active")]
+    AlreadyActive,
+}
+
+impl Modifier {
+    /// The Handsaw's effect
+    pub fn sawn_shotgun() -> Self {
+        Modifier {
+            effect: ModifierEffect::Sawn,
+            scope: ModifierScope::Table,
+            lifetime: ModifierLifetime::NextShot,
+            stacking: Stacking::Exclusive,
+        }
+    }
+
+    /// The Remote's effect
+    pub fn inverted_turn_order() -> Self {
+        Modifier {
+            effect: ModifierEffect::InvertedTurnOrder,
+            scope: ModifierScope::Table,
+            lifetime: ModifierLifetime::Round,
+            stacking: Stacking::Toggle,
+        }
+    }
+
+    pub fn applies_to(&self, player_number: PlayerNumber) -> bool {
+        match self.scope {
+            ModifierScope::Table => true,
+            ModifierScope::Player(scoped_player) => scoped_player == player_number,
+        }
+    }
+
+    fn same_kind(&self, other: &Modifier) -> bool {
+        std::mem::discriminant(&self.effect) == std::mem::discriminant(&other.effect)
+            && self.scope == other.scope
+    }
+}
+
+impl GameModifiers {
+    pub fn add(&mut self, modifier: Modifier) -> Result<(), ModifierError> {
+        let active = self
+            .modifiers
+            .iter()
+            .position(|active| active.same_kind(&modifier));
+
+        match (modifier.stacking, active) {
+            (Stacking::Exclusive, Some(_)) => return Err(ModifierError::AlreadyActive),
+            (Stacking::Toggle, Some(index)) => {
+                self.modifiers.remove(index);
+            }
+            (Stacking::Stack, _) | (Stacking::Exclusive | Stacking::Toggle, None) => {
+                self.modifiers.push(modifier)
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes every modifier with the effect and scope. Returns false if there were none
+    pub fn remove(&mut self, effect: ModifierEffect, scope: ModifierScope) -> bool {
+        let count = self.modifiers.len();
+        self.modifiers
+            .retain(|modifier| !(modifier.effect == effect && modifier.scope == scope));
+        self.modifiers.len() != count
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Modifier> {
+        self.modifiers.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modifiers.is_empty()
+    }
+
+    /// Modifiers scoped to the player alone
+    pub fn for_player(&self, player_number: PlayerNumber) -> impl Iterator<Item = &Modifier> {
+        self.modifiers
+            .iter()
+            .filter(move |modifier| modifier.scope == ModifierScope::Player(player_number))
+    }
+
+    /// Sawn for everyone
+    pub fn shotgun_sawn(&self) -> bool {
+        self.modifiers.iter().any(|modifier| {
+            modifier.effect == ModifierEffect::Sawn && modifier.scope == ModifierScope::Table
+        })
+    }
+
+    /// Inverted for everyone
+    pub fn turn_order_inverted(&self) -> bool {
+        self.count_inversions(|modifier| modifier.scope == ModifierScope::Table) % 2 == 1
+    }
+
+    pub fn sawn_for(&self, shooter: PlayerNumber) -> bool {
+        self.modifiers
+            .iter()
+            .any(|modifier| modifier.effect == ModifierEffect::Sawn && modifier.applies_to(shooter))
+    }
+
+    pub fn turn_order_inverted_for(&self, player_number: PlayerNumber) -> bool {
+        self.count_inversions(|modifier| modifier.applies_to(player_number)) % 2 == 1
+    }
+
+    /// Damage a shell fired by the shooter deals
+    pub fn damage(&self, shell_type: ShellType, shooter: PlayerNumber) -> u8 {
+        let damage = shell_type.damage(self.sawn_for(shooter));
+        if damage == 0 {
+            return 0;
+        }
+
+        self.modifiers
+            .iter()
+            .filter(|modifier| modifier.applies_to(shooter))
+            .fold(damage, |damage, modifier| match modifier.effect {
+                ModifierEffect::ExtraDamage(extra) => damage.saturating_add(extra),
+                ModifierEffect::Sawn | ModifierEffect::InvertedTurnOrder => damage,
+            })
+    }
+
+    fn count_inversions<F>(&self, filter: F) -> usize
+    where
+        F: Fn(&Modifier) -> bool,
+    {
+        self.modifiers
+            .iter()
+            .filter(|modifier| modifier.effect == ModifierEffect::InvertedTurnOrder)
+            .filter(|modifier| filter(modifier))
+            .count()
+    }
+
+    pub(crate) fn after_shot(&mut self) {
+        self.modifiers
+            .retain(|modifier| modifier.lifetime != ModifierLifetime::NextShot);
+    }
+
+    pub(crate) fn after_turn(&mut self) {
+        self.modifiers
+            .retain_mut(|modifier| match &mut modifier.lifetime {
+                ModifierLifetime::NextShot | ModifierLifetime::EndOfTurn => false,
+                ModifierLifetime::Turns(turns) => {
+                    *turns = turns.saturating_sub(1);
+                    *turns > 0
+                }
+                ModifierLifetime::EndOfLoadout | ModifierLifetime::Round => true,
+            });
+    }
+
+    /// The reloaded shotgun loses any shot modifiers too
+    pub(crate) fn after_loadout(&mut self) {
+        self.modifiers.retain(|modifier| {
+            !matches!(
+                modifier.lifetime,
+                ModifierLifetime::NextShot | ModifierLifetime::EndOfLoadout
+            )
+        });
+    }
+}
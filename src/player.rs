@@ -6,6 +6,7 @@ use crate::{player_number::PlayerNumber, round_number::RoundNumber};
 pub struct Player {
     player_number: PlayerNumber,
     wins: HashSet<RoundNumber>,
+    forfeited: bool,
 }
 
 impl Player {
@@ -13,6 +14,7 @@ impl Player {
         Player {
             player_number,
             wins: HashSet::with_capacity(3),
+            forfeited: false,
         }
     }
 
@@ -27,4 +29,14 @@ impl Player {
     pub fn wins(&self) -> &HashSet<RoundNumber> {
         &self.wins
     }
+
+    pub fn forfeit(&mut self) {
+        assert!(!self.forfeited);
+        self.forfeited = true;
+    }
+
+    /// A forfeited player no longer occupies a seat in the current or any following round
+    pub fn forfeited(&self) -> bool {
+        self.forfeited
+    }
 }
@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PlayerNumber {
     One = 1,
     Two = 2,
@@ -8,6 +9,27 @@ pub enum PlayerNumber {
     Four = 4,
 }
 
+impl PlayerNumber {
+    pub fn seat_index(self) -> usize {
+        match self {
+            PlayerNumber::One => 0,
+            PlayerNumber::Two => 1,
+            PlayerNumber::Three => 2,
+            PlayerNumber::Four => 3,
+        }
+    }
+
+    pub fn from_seat_index(seat_index: usize) -> Option<Self> {
+        match seat_index {
+            0 => Some(PlayerNumber::One),
+            1 => Some(PlayerNumber::Two),
+            2 => Some(PlayerNumber::Three),
+            3 => Some(PlayerNumber::Four),
+            _ => None,
+        }
+    }
+}
+
 impl Display for PlayerNumber {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let str = match self {
@@ -0,0 +1,327 @@
+use rand::Rng;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    action::Action,
+    decision::{ActionOutcome, SubmitError, TurnEnd},
+    game_session::GameSession,
+    loadout::Loadout,
+    modifier::GameModifiers,
+    player_number::PlayerNumber,
+    round::{BurnResult, ShotResult, TurnSummary},
+    round_number::RoundNumber,
+    seat::SeatView,
+    turn::{InvalidItemUseError, ItemUseResult},
+};
+
+/// Bumped whenever the shape of any message changes
+pub const PROTOCOL_VERSION: u16 = 11;
+
+pub type GameId = u32;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Envelope<TMessage> {
+    pub version: u16,
+    pub message: TMessage,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ClientMessage {
+    Join {
+        game_id: GameId,
+        player: PlayerNumber,
+    },
+    Act(Action),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ServerMessage {
+    Joined {
+        game_id: GameId,
+        player: PlayerNumber,
+        table: TableView,
+    },
+    Rejected(RejectionReason),
+    Event(Event),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Event {
+    ActionTaken(ActionNotification),
+    Forfeited {
+        player: PlayerNumber,
+        table: TableView,
+    },
+    /// Sent after the ActionTaken of the shot that triggered it
+    WiresCut {
+        player: PlayerNumber,
+    },
+}
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RejectionReason {
+    #[error("Message could not be parsed")]
+    Malformed,
+    #[error("Message was sent with an unsupported protocol version")]
+    UnsupportedVersion,
+    #[error("No game is hosted with the requested id")]
+    NoGame,
+    #[error("Requested player is not part of the game")]
+    NoPlayer,
+    #[error("Requested player has forfeited")]
+    Forfeited,
+    #[error("Another connection is already playing as the requested player")]
+    SeatTaken,
+    #[error("The connection has not joined a game")]
+    NotJoined,
+    #[error("The connection has already joined a game")]
+    AlreadyJoined,
+    #[error("The game is over")]
+    GameOver,
+    #[error("It is not the player's turn")]
+    NotYourTurn,
+    #[error("The shot target is not a living player")]
+    InvalidTarget,
+    #[error("The item is not present in the player's inventory")]
+    NoItem,
+    #[error("Cannot use adreneline due to being provided a target that doesn't exist, is the current player, or does not have the NotAdreneline item")]
+    BadAdrenelineTarget,
+    #[error("Shotgun is already sawn")]
+    DoubleSaw,
+    #[error("Player is recovering from previous stun and cannot be restunned")]
+    DoubleStun,
+    #[error("Player targeted by stun is dead, non-existent, or self")]
+    InvalidStunTarget,
+    #[error("Player's wires are cut and they can't heal")]
+    WiresCut,
+    #[error("Player is already handcuffed or the broken cuffs haven't come off yet")]
+    DoubleHandcuff,
+    #[error("Player targeted by handcuffs is dead, non-existent, or self")]
+    InvalidHandcuffTarget,
+    #[error("The item's script failed or ran out of instructions")]
+    ScriptFailed,
+}
+
+/// ItemUseResults that reveal shells are only shown to the player that used the item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RedactedItemResult {
+    Visible(ItemUseResult),
+    Hidden,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ContinuationNotification {
+    LoadoutContinues,
+    LoadoutEnds { next_player: PlayerNumber },
+    RoundEnds { winner: PlayerNumber },
+}
+
+/// The public parts of a TurnSummary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TurnOutcome {
+    pub shot_result: Option<ShotResult>,
+    pub burns: Vec<BurnResult>,
+    pub continuation: ContinuationNotification,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionNotification {
+    pub actor: PlayerNumber,
+    pub action: Action,
+    pub item_result: Option<RedactedItemResult>,
+    /// Present if the action ended the turn
+    pub turn_outcome: Option<TurnOutcome>,
+    pub table: TableView,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatState {
+    pub view: SeatView,
+    pub health: Option<i32>,
+    pub wires_cut: bool,
+}
+
+/// Everything about the table that all players can see
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableView {
+    pub round_number: Option<RoundNumber>,
+    pub next_player: Option<PlayerNumber>,
+    pub shell_count: usize,
+    pub loadout: Option<Loadout>,
+    pub modifiers: GameModifiers,
+    pub seats: Vec<SeatState>,
+}
+
+#[derive(Error, Debug)]
+pub enum ProtocolError {
+    #[error("Protocol version {0} is not supported")]
+    UnsupportedVersion(u16),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Binary(#[from] postcard::Error),
+}
+
+#[derive(Deserialize)]
+struct VersionProbe {
+    version: u16,
+}
+
+impl<TMessage> Envelope<TMessage> {
+    pub fn new(message: TMessage) -> Self {
+        Envelope {
+            version: PROTOCOL_VERSION,
+            message,
+        }
+    }
+}
+
+impl From<InvalidItemUseError> for RejectionReason {
+    fn from(error: InvalidItemUseError) -> Self {
+        match error {
+            InvalidItemUseError::NoItem => RejectionReason::NoItem,
+            InvalidItemUseError::BadAdrenelineTarget => RejectionReason::BadAdrenelineTarget,
+            InvalidItemUseError::DoubleSaw => RejectionReason::DoubleSaw,
+            InvalidItemUseError::DoubleStun => RejectionReason::DoubleStun,
+            InvalidItemUseError::InvalidStunTarget => RejectionReason::InvalidStunTarget,
+            InvalidItemUseError::WiresCut => RejectionReason::WiresCut,
+            InvalidItemUseError::DoubleHandcuff => RejectionReason::DoubleHandcuff,
+            InvalidItemUseError::InvalidHandcuffTarget => RejectionReason::InvalidHandcuffTarget,
+            InvalidItemUseError::ScriptFailed => RejectionReason::ScriptFailed,
+        }
+    }
+}
+
+impl From<SubmitError> for RejectionReason {
+    fn from(error: SubmitError) -> Self {
+        match error {
+            SubmitError::NoRound => RejectionReason::GameOver,
+            SubmitError::InvalidTarget => RejectionReason::InvalidTarget,
+            SubmitError::InvalidItemUse(error) => error.into(),
+        }
+    }
+}
+
+impl RedactedItemResult {
+    pub fn new(item_result: ItemUseResult, visible: bool) -> Self {
+        match item_result {
+            ItemUseResult::LearnedShell(_) if !visible => RedactedItemResult::Hidden,
+            ItemUseResult::Default
+            | ItemUseResult::ShotgunRacked(_)
+            | ItemUseResult::LearnedShell(_)
+            | ItemUseResult::StunnedPlayer(_)
+            | ItemUseResult::Healed(_)
+            | ItemUseResult::HandcuffedPlayer(_) => RedactedItemResult::Visible(item_result),
+        }
+    }
+}
+
+impl From<TurnEnd> for ContinuationNotification {
+    fn from(turn_end: TurnEnd) -> Self {
+        match turn_end {
+            TurnEnd::LoadoutContinues => ContinuationNotification::LoadoutContinues,
+            TurnEnd::LoadoutEnds { next_player } => {
+                ContinuationNotification::LoadoutEnds { next_player }
+            }
+            TurnEnd::RoundEnds { winner } => ContinuationNotification::RoundEnds { winner },
+        }
+    }
+}
+
+impl TurnOutcome {
+    pub fn new<TRng>(turn_summary: &TurnSummary<TRng>) -> Self {
+        TurnOutcome {
+            shot_result: turn_summary.shot_result.clone(),
+            burns: turn_summary.burns.clone(),
+            continuation: TurnEnd::new(turn_summary).into(),
+        }
+    }
+
+    /// None if the action did not end the turn
+    pub fn from_action_outcome(outcome: &ActionOutcome) -> Option<Self> {
+        outcome.turn_end.clone().map(|turn_end| TurnOutcome {
+            shot_result: outcome.shot_result.clone(),
+            burns: outcome.burns.clone(),
+            continuation: turn_end.into(),
+        })
+    }
+}
+
+impl TableView {
+    pub fn new<TRng>(session: &GameSession<TRng>) -> Self
+    where
+        TRng: Rng,
+    {
+        match session.round() {
+            Some(round) => TableView {
+                round_number: Some(round.number()),
+                next_player: Some(round.next_player()),
+                shell_count: round.shells().len(),
+                loadout: Some(round.loadout().clone()),
+                modifiers: round.game_modifiers().clone(),
+                seats: round
+                    .seats()
+                    .iter()
+                    .map(|seat| SeatState {
+                        view: seat.create_view(round.game_modifiers()),
+                        health: seat.player().map(|player| player.health()),
+                        wires_cut: seat.player().is_some_and(|player| player.wires_cut()),
+                    })
+                    .collect(),
+            },
+            None => TableView {
+                round_number: None,
+                next_player: None,
+                shell_count: 0,
+                loadout: None,
+                modifiers: Default::default(),
+                seats: Vec::new(),
+            },
+        }
+    }
+}
+
+pub fn encode_json<TMessage>(message: &TMessage) -> Result<String, ProtocolError>
+where
+    TMessage: Serialize,
+{
+    Ok(serde_json::to_string(&Envelope::new(message))?)
+}
+
+pub fn decode_json<TMessage>(json: &str) -> Result<TMessage, ProtocolError>
+where
+    TMessage: DeserializeOwned,
+{
+    let probe: VersionProbe = serde_json::from_str(json)?;
+    check_version(probe.version)?;
+
+    let envelope: Envelope<TMessage> = serde_json::from_str(json)?;
+    Ok(envelope.message)
+}
+
+/// Compact encoding, a varint version followed by the postcard serialized message
+pub fn encode_binary<TMessage>(message: &TMessage) -> Result<Vec<u8>, ProtocolError>
+where
+    TMessage: Serialize,
+{
+    Ok(postcard::to_stdvec(&Envelope::new(message))?)
+}
+
+pub fn decode_binary<TMessage>(bytes: &[u8]) -> Result<TMessage, ProtocolError>
+where
+    TMessage: DeserializeOwned,
+{
+    let (version, remaining_bytes) = postcard::take_from_bytes::<u16>(bytes)?;
+    check_version(version)?;
+
+    Ok(postcard::from_bytes(remaining_bytes)?)
+}
+
+fn check_version(version: u16) -> Result<(), ProtocolError> {
+    if version != PROTOCOL_VERSION {
+        return Err(ProtocolError::UnsupportedVersion(version));
+    }
+
+    Ok(())
+}
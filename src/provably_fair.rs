@@ -0,0 +1,189 @@
+use rand::{Rng, RngCore};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::{
+    game_session::GameSession, item::item_index, loadout::DealtItem, round_number::RoundNumber,
+    shell::ShellType,
+};
+
+const DOMAIN: &[u8] = b"buckshot-roulette-loadout-v1";
+
+/// Published when a loadout starts, before anyone acts
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoadoutCommitment {
+    pub round_number: RoundNumber,
+    /// Counts loadouts over the whole game
+    pub loadout_index: usize,
+    pub digest: [u8; 32],
+}
+
+/// Published when the loadout ends, hashes to the digest of its commitment
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoadoutReveal {
+    pub round_number: RoundNumber,
+    pub loadout_index: usize,
+    /// In firing order, before any were inverted
+    pub shells: Vec<ShellType>,
+    pub dealt_items: Vec<DealtItem>,
+    pub salt: [u8; 32],
+}
+
+/// A shell as a client saw it leave the shotgun
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObservedShell {
+    pub shell_type: ShellType,
+    /// If an Inverter was used on it an odd number of times
+    pub inverted: bool,
+}
+
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum VerificationError {
+    #[error("The reveal is for a different loadout than the commitment")]
+    WrongLoadout,
+    #[error("The reveal does not hash to the committed digest")]
+    DigestMismatch,
+    #[error("More shells were fired than were committed")]
+    TooManyShells,
+    #[error("Shell {0} does not match the committed sequence")]
+    ShellMismatch(usize),
+    #[error("Dealt items do not match the committed draws")]
+    ItemMismatch,
+}
+
+/// Commits to every loadout of a GameSession and reveals it afterwards
+#[derive(Debug, Clone)]
+pub struct FairDealer<TSaltRng> {
+    salt_rng: TSaltRng,
+    next_loadout_index: usize,
+    pending: Option<LoadoutReveal>,
+}
+
+impl LoadoutReveal {
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(DOMAIN);
+        hasher.update([self.round_number as u8]);
+        hasher.update((self.loadout_index as u64).to_le_bytes());
+        hasher.update((self.shells.len() as u32).to_le_bytes());
+        for shell_type in &self.shells {
+            hasher.update([match shell_type {
+                ShellType::Blank => 0,
+                ShellType::Live => 1,
+                ShellType::Slug => 2,
+                ShellType::Incendiary => 3,
+                ShellType::Dud => 4,
+            }]);
+        }
+
+        hasher.update((self.dealt_items.len() as u32).to_le_bytes());
+        for dealt_item in &self.dealt_items {
+            hasher.update([
+                dealt_item.player_number as u8,
+                item_index(dealt_item.item) as u8,
+            ]);
+        }
+
+        hasher.update(self.salt);
+        hasher.finalize().into()
+    }
+}
+
+impl<TSaltRng> FairDealer<TSaltRng>
+where
+    TSaltRng: RngCore,
+{
+    /// The salt generator must be unpredictable to clients, e.g. OsRng, and separate from the game's
+    pub fn new(salt_rng: TSaltRng) -> Self {
+        FairDealer {
+            salt_rng,
+            next_loadout_index: 0,
+            pending: None,
+        }
+    }
+
+    /// Call whenever a new loadout has started. None if the game is over
+    pub fn commit<TRng>(&mut self, session: &GameSession<TRng>) -> Option<LoadoutCommitment>
+    where
+        TRng: Rng,
+    {
+        let round = session.round()?;
+
+        let mut salt = [0; 32];
+        self.salt_rng.fill_bytes(&mut salt);
+
+        let reveal = LoadoutReveal {
+            round_number: round.number(),
+            loadout_index: self.next_loadout_index,
+            shells: round
+                .shells()
+                .iter()
+                .map(|shell| shell.shell_type())
+                .collect(),
+            dealt_items: round.dealt_items().clone(),
+            salt,
+        };
+        self.next_loadout_index += 1;
+
+        let commitment = LoadoutCommitment {
+            round_number: reveal.round_number,
+            loadout_index: reveal.loadout_index,
+            digest: reveal.digest(),
+        };
+        self.pending = Some(reveal);
+        Some(commitment)
+    }
+
+    /// Call when the committed loadout has ended, either by running out of shells or the round ending
+    pub fn reveal(&mut self) -> Option<LoadoutReveal> {
+        self.pending.take()
+    }
+
+    pub fn pending_commitment_index(&self) -> Option<usize> {
+        self.pending.as_ref().map(|reveal| reveal.loadout_index)
+    }
+}
+
+/// Checks a reveal against its commitment, the shells fired or racked during the loadout, and the items the client saw dealt
+pub fn verify(
+    commitment: &LoadoutCommitment,
+    reveal: &LoadoutReveal,
+    observed_shells: &[ObservedShell],
+    observed_items: &[DealtItem],
+) -> Result<(), VerificationError> {
+    if commitment.round_number != reveal.round_number
+        || commitment.loadout_index != reveal.loadout_index
+    {
+        return Err(VerificationError::WrongLoadout);
+    }
+
+    if commitment.digest != reveal.digest() {
+        return Err(VerificationError::DigestMismatch);
+    }
+
+    // the round can end before every shell is fired
+    if observed_shells.len() > reveal.shells.len() {
+        return Err(VerificationError::TooManyShells);
+    }
+
+    for (index, (observed, committed)) in observed_shells.iter().zip(&reveal.shells).enumerate() {
+        let expected = if observed.inverted {
+            committed.inverted()
+        } else {
+            *committed
+        };
+
+        if observed.shell_type != expected {
+            return Err(VerificationError::ShellMismatch(index));
+        }
+    }
+
+    if observed_items != reveal.dealt_items.as_slice() {
+        return Err(VerificationError::ItemMismatch);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use std::array;
+
+use rand::SeedableRng;
+
+use crate::player_number::PlayerNumber;
+
+/// Each source of randomness in a round
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RngStream {
+    Health,
+    Loadout,
+    Shells,
+    Items(PlayerNumber),
+    Phone,
+}
+
+#[derive(Debug, Clone)]
+pub struct IndependentStreams<TRng> {
+    health: TRng,
+    loadout: TRng,
+    shells: TRng,
+    items: [TRng; 4],
+    phone: TRng,
+}
+
+/// Where a Round draws its random numbers from
+#[derive(Debug, Clone)]
+pub enum RngStreams<TRng> {
+    /// Every source draws from the same generator, in the order the game does
+    Shared(TRng),
+    /// Every source has its own generator, so a decision that draws from one doesn't shift the others
+    Independent(Box<IndependentStreams<TRng>>),
+}
+
+impl<TRng> IndependentStreams<TRng> {
+    pub fn new(health: TRng, loadout: TRng, shells: TRng, items: [TRng; 4], phone: TRng) -> Self {
+        IndependentStreams {
+            health,
+            loadout,
+            shells,
+            items,
+            phone,
+        }
+    }
+}
+
+impl<TRng> RngStreams<TRng> {
+    pub fn get(&mut self, stream: RngStream) -> &mut TRng {
+        match self {
+            RngStreams::Shared(rng) => rng,
+            RngStreams::Independent(streams) => match stream {
+                RngStream::Health => &mut streams.health,
+                RngStream::Loadout => &mut streams.loadout,
+                RngStream::Shells => &mut streams.shells,
+                RngStream::Items(player_number) => &mut streams.items[player_number.seat_index()],
+                RngStream::Phone => &mut streams.phone,
+            },
+        }
+    }
+}
+
+impl<TRng> RngStreams<TRng>
+where
+    TRng: SeedableRng,
+{
+    /// Independent streams, each seeded from the seed and the stream it is for
+    pub fn from_seed(seed: u64) -> Self {
+        let stream_rng = |stream_index: u64| TRng::seed_from_u64(derive_seed(seed, stream_index));
+        RngStreams::Independent(Box::new(IndependentStreams {
+            health: stream_rng(0),
+            loadout: stream_rng(1),
+            shells: stream_rng(2),
+            items: array::from_fn(|seat_index| stream_rng(3 + seat_index as u64)),
+            phone: stream_rng(7),
+        }))
+    }
+}
+
+// splitmix64, so neighbouring seeds and streams don't produce related generators
+fn derive_seed(seed: u64, stream_index: u64) -> u64 {
+    let mut z = seed.wrapping_add(
+        stream_index
+            .wrapping_add(1)
+            .wrapping_mul(0x9E3779B97F4A7C15),
+    );
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
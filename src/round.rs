@@ -1,18 +1,26 @@
 use std::{collections::VecDeque, ops::IndexMut};
 
-use rand::Rng;
+use indexmap::IndexMap;
 
 use crate::{
+    action::Action,
+    chance::{ChanceEvent, ChanceProvider},
+    compact_round::CompactRound,
     game_players::GamePlayers,
-    item::initialize_item_count_map,
-    loadout::Loadout,
+    history::{HistoryEvent, MatchHistory},
+    item::Item,
+    item_registry::ItemRegistry,
+    loadout::{DealtItem, Loadout},
+    modifier::{GameModifiers, Modifier, ModifierError},
     player_number::PlayerNumber,
+    rng_streams::RngStreams,
     round_number::RoundNumber,
     round_player::RoundPlayer,
+    round_rules::RoundRules,
     round_start_info::RoundStartInfo,
-    seat::Seat,
-    shell::{Shell, ShellType, ShotgunDamage},
-    turn::{GameModifiers, ItemUseResult, TakenAction, TerminalAction, Turn},
+    seat::{ForfeitedItems, Seat},
+    shell::{Shell, ShellType, ShotgunDamage, SpecialShells, SPECIAL_SHELL_TYPES},
+    turn::{ItemUseResult, TakenAction, TerminalAction, Turn},
     LOG_RNG,
 };
 #[derive(Debug, Clone)]
@@ -23,15 +31,35 @@ pub struct Round<TRng> {
     active_seat_index: usize,
     first_dead_player: Option<PlayerNumber>,
     start_info: RoundStartInfo,
+    rules: RoundRules,
+    item_registry: ItemRegistry,
     loadout: Loadout,
+    dealt_items: Vec<DealtItem>,
+    history: MatchHistory,
     shells: VecDeque<Shell>,
-    rng: TRng,
+    rng: RngStreams<TRng>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShotResult {
     pub target_player: PlayerNumber,
     pub damage: ShotgunDamage,
+    /// Health the target actually lost to the shell
+    pub health_lost: u8,
+    /// The shot left the target in sudden death
+    pub wires_cut: bool,
+}
+
+/// A burning player took the damage when the turn reached them
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BurnResult {
+    pub player: PlayerNumber,
+    pub damage: u8,
+    pub killed: bool,
+    /// The burn left the player in sudden death
+    pub wires_cut: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -56,9 +84,17 @@ pub enum RoundContinuation<TRng> {
 #[derive(Debug, Clone)]
 pub struct TurnSummary<TRng> {
     pub shot_result: Option<ShotResult>,
+    /// Burns taken as the turn was passed on
+    pub burns: Vec<BurnResult>,
     pub round_continuation: RoundContinuation<TRng>,
 }
 
+enum PassedTurn {
+    Next(PlayerNumber),
+    /// A burn killed everyone but the winner
+    RoundWon(PlayerNumber),
+}
+
 #[derive(Debug, Clone)]
 pub struct FinishedRound<TRng> {
     round: Round<TRng>,
@@ -68,8 +104,9 @@ pub struct FinishedRound<TRng> {
 
 #[derive(Debug, Clone)]
 pub enum FinishedRoundOrRng<TRng> {
-    FinishedRound(FinishedRound<TRng>),
+    FinishedRound(Box<FinishedRound<TRng>>),
     Rng(TRng),
+    Streams(RngStreams<TRng>),
 }
 
 #[derive(Debug, Clone)]
@@ -86,22 +123,55 @@ impl<TRng> FinishedRound<TRng> {
     pub fn number(&self) -> RoundNumber {
         self.round.round_number
     }
+
+    pub fn history(&self) -> &MatchHistory {
+        &self.round.history
+    }
 }
 
 impl<TRng> Round<TRng>
 where
-    TRng: Rng,
+    TRng: ChanceProvider,
 {
+    /// Keeps the rules and items of the finished round, or uses the defaults
     pub fn new(game_players: &GamePlayers, round_or_rng: FinishedRoundOrRng<TRng>) -> Self {
+        let (rules, item_registry) = match &round_or_rng {
+            FinishedRoundOrRng::FinishedRound(finished_round) => (
+                finished_round.round.rules,
+                finished_round.round.item_registry.clone(),
+            ),
+            _ => Default::default(),
+        };
+
+        Round::with_items(game_players, round_or_rng, rules, item_registry)
+    }
+
+    pub fn with_rules(
+        game_players: &GamePlayers,
+        round_or_rng: FinishedRoundOrRng<TRng>,
+        rules: RoundRules,
+    ) -> Self {
+        Round::with_items(game_players, round_or_rng, rules, Default::default())
+    }
+
+    /// Deals and uses items through the registry
+    pub fn with_items(
+        game_players: &GamePlayers,
+        round_or_rng: FinishedRoundOrRng<TRng>,
+        rules: RoundRules,
+        item_registry: ItemRegistry,
+    ) -> Self {
         let starting_player;
 
         let players = game_players.as_vec();
 
         let player_count = players.len();
         assert!(player_count != 0);
+        assert!(game_players.remaining_players() > 1);
 
         let round_number;
         let mut rng;
+        let mut history = MatchHistory::default();
         match round_or_rng {
             FinishedRoundOrRng::FinishedRound(finished_round) => {
                 round_number = match finished_round.number() {
@@ -111,27 +181,43 @@ where
                 };
                 starting_player = finished_round.first_dead_player;
                 rng = finished_round.round.rng;
+                history = finished_round.round.history;
+                history.next_turn();
             }
             FinishedRoundOrRng::Rng(inital_rng) => {
                 round_number = RoundNumber::One;
                 starting_player = PlayerNumber::One;
-                rng = inital_rng;
+                rng = RngStreams::Shared(inital_rng);
+            }
+            FinishedRoundOrRng::Streams(initial_streams) => {
+                round_number = RoundNumber::One;
+                starting_player = PlayerNumber::One;
+                rng = initial_streams;
             }
         }
 
+        // forfeited players can't start, pass it on to the next seat
+        let mut turn_index = players
+            .iter()
+            .position(|player| player.number() == starting_player)
+            .unwrap();
+        while players[turn_index].forfeited() {
+            turn_index = (turn_index + 1) % player_count;
+        }
+
+        let starting_player = players[turn_index].number();
+
         let start_info =
             RoundStartInfo::new(starting_player, game_players.multiplayer_count, &mut rng);
 
-        let mut turn_index = 0;
-
         let seats: Vec<Seat> = players
             .iter()
-            .enumerate()
-            .map(|(index, player)| {
-                if player.number() == starting_player {
-                    turn_index = index;
+            .map(|player| {
+                if player.forfeited() {
+                    Seat::new_vacant(player.number())
+                } else {
+                    Seat::new(RoundPlayer::new(player, &start_info))
                 }
-                Seat::new(RoundPlayer::new(player, &start_info))
             })
             .collect();
 
@@ -144,10 +230,14 @@ where
             game_modifiers: Default::default(),
             seats,
             start_info,
+            rules,
+            item_registry,
             shells,
             rng,
             active_seat_index: turn_index,
             loadout,
+            dealt_items: Vec::new(),
+            history,
         };
 
         round.new_loadout(true);
@@ -155,10 +245,77 @@ where
         round
     }
 
+    /// Items are placed in consecutive slots, the compact form doesn't keep them
+    pub(crate) fn from_compact(compact: &CompactRound, rng: RngStreams<TRng>) -> Self {
+        let max_health = i32::from(compact.max_health());
+        let seats = (0..compact.seat_count())
+            .map(|seat_index| {
+                let player_number = PlayerNumber::from_seat_index(seat_index).unwrap();
+                let player = compact.stun_state(player_number).map(|stun_state| {
+                    RoundPlayer::from_parts(
+                        player_number,
+                        i32::from(compact.health(player_number)),
+                        max_health,
+                        stun_state,
+                        compact.handcuff_state(player_number).unwrap(),
+                        compact.wires_cut(player_number),
+                    )
+                });
+
+                Seat::from_parts(
+                    player_number,
+                    player,
+                    compact.items(player_number).collect(),
+                )
+            })
+            .collect();
+
+        let shells = (0..compact.shell_count())
+            .map(|index| Shell::new(compact.shell(index).unwrap()))
+            .collect();
+
+        Round {
+            round_number: compact.number(),
+            seats,
+            game_modifiers: compact.modifiers(),
+            active_seat_index: compact.next_player().seat_index(),
+            first_dead_player: compact.first_dead_player(),
+            start_info: RoundStartInfo::from_parts(
+                max_health,
+                compact.starting_player(),
+                compact.player_count(),
+            ),
+            rules: compact.rules(),
+            item_registry: Default::default(),
+            loadout: compact.loadout(),
+            dealt_items: Vec::new(),
+            history: Default::default(),
+            shells,
+            rng,
+        }
+    }
+
     pub fn number(&self) -> RoundNumber {
         self.round_number
     }
 
+    pub fn start_info(&self) -> &RoundStartInfo {
+        &self.start_info
+    }
+
+    pub fn rules(&self) -> &RoundRules {
+        &self.rules
+    }
+
+    pub fn item_registry(&self) -> &ItemRegistry {
+        &self.item_registry
+    }
+
+    /// The generators the round will draw from next
+    pub fn rng(&self) -> &RngStreams<TRng> {
+        &self.rng
+    }
+
     pub fn max_health(&self) -> i32 {
         self.start_info.max_health()
     }
@@ -173,6 +330,7 @@ where
     }
 
     fn new_loadout(&mut self, pre_generated: bool) {
+        self.game_modifiers.after_loadout();
         if !pre_generated {
             self.loadout = Loadout::new(self.start_info.player_count, &mut self.rng)
         };
@@ -185,22 +343,45 @@ where
             .filter(|seat| seat.player().is_some())
             .count();
 
-        let mut global_item_counts = initialize_item_count_map();
+        let mut global_item_counts: IndexMap<Item, usize> = self
+            .item_registry
+            .dealable_items(remaining_players, &self.rules)
+            .map(|item| (item, 0))
+            .collect();
         for seat in &self.seats {
             for item in seat.items() {
-                let count = global_item_counts.get_mut(item).unwrap();
-                *count += 1
+                if let Some(count) = global_item_counts.get_mut(item) {
+                    *count += 1
+                }
             }
         }
 
         // round robin because of global item limits
+        self.dealt_items.clear();
         for _ in 0..loadout.new_items {
             for seat in &mut self.seats {
-                if let Some(added_item) =
-                    seat.get_new_item(remaining_players, &global_item_counts, &mut self.rng)
+                if seat.player().is_some()
+                    && seat.items().len() >= self.rules.item_capacity(seat.player_number())
                 {
-                    let count = global_item_counts.get_mut(&added_item).unwrap();
-                    *count += 1
+                    loadout.undealt_items[seat.player_number().seat_index()] += 1;
+                    continue;
+                }
+
+                match seat.get_new_item(&global_item_counts, &self.item_registry, &mut self.rng) {
+                    Some(added_item) => {
+                        let count = global_item_counts.get_mut(&added_item).unwrap();
+                        *count += 1;
+                        self.dealt_items.push(DealtItem {
+                            player_number: seat.player_number(),
+                            item: added_item,
+                            slot: *seat.item_slots().last().unwrap(),
+                        });
+                    }
+                    // every item is at its limit
+                    None if seat.player().is_some() => {
+                        loadout.undealt_items[seat.player_number().seat_index()] += 1
+                    }
+                    None => {}
                 }
             }
         }
@@ -213,7 +394,7 @@ where
             println!("Loading shells...");
         }
         while blanks_to_load > 0 && lives_to_load > 0 {
-            if self.rng.gen_bool(0.5) {
+            if self.rng.bernoulli(ChanceEvent::ShellType, 0.5) {
                 if LOG_RNG {
                     println!("Blank");
                 }
@@ -240,12 +421,57 @@ where
                 println!("Live");
             }
         }
+
+        // no draws unless the rules ask for special shells
+        let mut special_rounds = SpecialShells::default();
+        for shell_type in SPECIAL_SHELL_TYPES {
+            for _ in 0..self.rules.special_shells.get(shell_type) {
+                let live_positions: Vec<usize> = self
+                    .shells
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, shell)| shell.shell_type() == ShellType::Live)
+                    .map(|(index, _)| index)
+                    .collect();
+                if live_positions.is_empty() {
+                    break;
+                }
+
+                let index = live_positions[self.rng.choose(
+                    ChanceEvent::SpecialShell,
+                    0,
+                    live_positions.len(),
+                )];
+                if LOG_RNG {
+                    println!("Shell {} is a {:?}", index, shell_type);
+                }
+
+                self.shells[index] = Shell::new(shell_type);
+                special_rounds.add(shell_type);
+            }
+        }
+
+        self.loadout.initial_live_rounds -= special_rounds.total();
+        self.loadout.special_rounds = special_rounds;
+
+        self.history.record(
+            self.round_number,
+            HistoryEvent::Loadout {
+                loadout: self.loadout.clone(),
+                dealt_items: self.dealt_items.clone(),
+            },
+        );
     }
 
     pub fn loadout(&self) -> &Loadout {
         &self.loadout
     }
 
+    /// Items handed out when the current loadout started, in the order they were drawn
+    pub fn dealt_items(&self) -> &Vec<DealtItem> {
+        &self.dealt_items
+    }
+
     pub fn living_players(&self) -> impl Iterator<Item = &Seat> {
         self.seats.iter().filter(|&seat| seat.player().is_some())
     }
@@ -266,15 +492,31 @@ where
         &self.game_modifiers
     }
 
+    /// For game modes that add effects outside of items
+    pub fn add_modifier(&mut self, modifier: Modifier) -> Result<(), ModifierError> {
+        self.game_modifiers.add(modifier)
+    }
+
+    /// The whole match up to now, not just this round
+    pub fn history(&self) -> &MatchHistory {
+        &self.history
+    }
+
     pub fn first_dead_player(&self) -> Option<PlayerNumber> {
         self.first_dead_player
     }
 
-    /// Advances to next alive character to play, considering and updating stun states. Returns next_player()
-    fn advance_turn(&mut self) -> PlayerNumber {
+    /// Advances to next alive character to play, considering and updating stun states. Returns next_player().
+    /// Burning players take their damage as the turn reaches them, even if they then sit it out
+    fn advance_turn(&mut self, burns: &mut Vec<BurnResult>) -> PassedTurn {
+        let passing_player = self.seats[self.active_seat_index].player_number();
+        let inverted = self.game_modifiers.turn_order_inverted_for(passing_player);
+        self.game_modifiers.after_turn();
+        self.history.next_turn();
+
         loop {
             let last_seat_index = self.seats.len() - 1;
-            if self.game_modifiers.turn_order_inverted {
+            if inverted {
                 if self.active_seat_index == 0 {
                     self.active_seat_index = last_seat_index;
                 } else {
@@ -287,13 +529,199 @@ where
             }
 
             let seat = self.seats.index_mut(self.active_seat_index);
+            let player_number = seat.player_number();
 
             if let Some(occupied_seat) = seat.create_occupied_seat() {
-                if occupied_seat.player.update_stunned() {
-                    return occupied_seat.player.player_number();
+                let damage = occupied_seat.player.burn();
+                if damage > 0 {
+                    let killed = occupied_seat.player.burn_out();
+                    let burn = BurnResult {
+                        player: player_number,
+                        damage,
+                        killed,
+                        wires_cut: !killed && self.check_sudden_death(player_number),
+                    };
+                    self.history
+                        .record(self.round_number, HistoryEvent::Burned(burn));
+                    burns.push(burn);
+
+                    if burn.killed {
+                        match self.bury(player_number) {
+                            Some(winner) => return PassedTurn::RoundWon(winner),
+                            None => continue,
+                        }
+                    }
                 }
             }
+
+            let seat = self.seats.index_mut(self.active_seat_index);
+            if let Some(occupied_seat) = seat.create_occupied_seat() {
+                // cuffs only break on a turn the player would otherwise have taken
+                if occupied_seat.player.update_stunned() && occupied_seat.player.update_handcuffed()
+                {
+                    return PassedTurn::Next(player_number);
+                }
+            }
+        }
+    }
+
+    /// Applies what the turn did to other seats
+    fn apply_turn_effects(
+        &mut self,
+        handcuffed_players: &[PlayerNumber],
+        stunned_players: &[PlayerNumber],
+        stolen_items: &[(PlayerNumber, Item)],
+    ) {
+        for (player_number, item) in stolen_items {
+            self.seats[player_number.seat_index()]
+                .take_item(*item)
+                .expect("Stolen items were checked against the turn's seat views");
+        }
+
+        for player_number in stunned_players {
+            if let Some(occupied_seat) =
+                self.seats[player_number.seat_index()].create_occupied_seat()
+            {
+                occupied_seat
+                    .player
+                    .stun()
+                    .expect("Stuns were checked against the turn's seat views");
+            }
+        }
+
+        for player_number in handcuffed_players {
+            if let Some(occupied_seat) =
+                self.seats[player_number.seat_index()].create_occupied_seat()
+            {
+                occupied_seat
+                    .player
+                    .handcuff()
+                    .expect("Handcuffs were checked against the turn's seat views");
+            }
+        }
+    }
+
+    /// Cuts the wires of a player left at or below the threshold by a hit in the final round. True if they were cut now
+    fn check_sudden_death(&mut self, player_number: PlayerNumber) -> bool {
+        let Some(threshold) = self.rules.sudden_death_threshold else {
+            return false;
+        };
+        if self.round_number != RoundNumber::Three {
+            return false;
+        }
+
+        match self.seats[player_number.seat_index()].create_occupied_seat() {
+            Some(occupied_seat)
+                if occupied_seat.player.health() > 0
+                    && occupied_seat.player.health() <= i32::from(threshold)
+                    && !occupied_seat.player.wires_cut() =>
+            {
+                occupied_seat.player.cut_wires();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Clears the seat of a player who was just killed. Returns the winner if only one player is left
+    fn bury(&mut self, player_number: PlayerNumber) -> Option<PlayerNumber> {
+        self.first_dead_player.get_or_insert(player_number);
+        self.seats[player_number.seat_index()].empty_dead_body();
+
+        if self.living_players().count() == 1 {
+            return Some(self.living_players().next().unwrap().player_number());
+        }
+
+        None
+    }
+
+    fn record_item_uses(&mut self, player: PlayerNumber, item_uses: Vec<(Action, ItemUseResult)>) {
+        for (action, result) in item_uses {
+            self.history.record(
+                self.round_number,
+                HistoryEvent::ItemUsed {
+                    player,
+                    action,
+                    result,
+                },
+            );
+        }
+    }
+
+    fn record_shot(
+        &mut self,
+        shooter: PlayerNumber,
+        shell_type: ShellType,
+        shot_result: &Option<ShotResult>,
+    ) {
+        if let Some(shot_result) = shot_result {
+            self.history.record(
+                self.round_number,
+                HistoryEvent::Shot {
+                    shooter,
+                    shell_type,
+                    result: shot_result.clone(),
+                },
+            );
+        }
+    }
+
+    fn finish(mut self, winner: PlayerNumber) -> FinishedRound<TRng> {
+        let health = self.seats[winner.seat_index()].player().unwrap().health();
+        self.history.record(
+            self.round_number,
+            HistoryEvent::RoundEnded {
+                winner,
+                first_dead_player: self.first_dead_player.unwrap(),
+                health,
+            },
+        );
+
+        FinishedRound {
+            first_dead_player: self.first_dead_player.unwrap(),
+            winner,
+            round: self,
+        }
+    }
+
+    /// Removes a living player from the round. Items held by the player are kept on the table or discarded
+    pub fn forfeit(
+        mut self,
+        player_number: PlayerNumber,
+        forfeited_items: ForfeitedItems,
+    ) -> RoundContinuation<TRng> {
+        let forfeited_seat_index = player_number.seat_index();
+        let seat = self.seats.index_mut(forfeited_seat_index);
+        assert!(
+            seat.player().is_some(),
+            "Attempted to forfeit a player that isn't seated"
+        );
+
+        seat.vacate(forfeited_items);
+        self.history.record(
+            self.round_number,
+            HistoryEvent::Forfeited {
+                player: player_number,
+            },
+        );
+
+        self.first_dead_player.get_or_insert(player_number);
+
+        if self.living_players().count() == 1 {
+            let winner = self.living_players().next().unwrap().player_number();
+            return RoundContinuation::RoundEnds(self.finish(winner));
+        }
+
+        if forfeited_seat_index == self.active_seat_index {
+            if let PassedTurn::RoundWon(winner) = self.advance_turn(&mut Vec::new()) {
+                return RoundContinuation::RoundEnds(self.finish(winner));
+            }
         }
+
+        RoundContinuation::RoundContinues(ContinuedRound {
+            turn_continuation: TurnContinuation::LoadoutContinues,
+            round: self,
+        })
     }
 
     pub fn with_turn<F>(mut self, mut func: F) -> TurnSummaryOrRound<TRng>
@@ -311,7 +739,7 @@ where
                 if index == self.active_seat_index {
                     None
                 } else {
-                    Some(seat.create_view())
+                    Some(seat.create_view(&self.game_modifiers))
                 }
             })
             .collect();
@@ -324,24 +752,35 @@ where
             occupied_seat,
             other_seats,
             &mut self.shells,
-            &mut self.rng,
+            self.rng.get(ChanceEvent::PhoneShell.stream()),
             self.game_modifiers.clone(),
+            self.rules,
+            &self.item_registry,
         );
 
         let taken_action = func(turn);
 
         let taken_turn = match taken_action {
             TakenAction::Continued(continued_turn) => {
+                let item_uses = continued_turn.item_uses().clone();
                 self.game_modifiers = continued_turn.modifiers().clone();
+                let handcuffed_players = continued_turn.handcuffed_players().clone();
+                let stunned_players = continued_turn.stunned_players().clone();
+                let stolen_items = continued_turn.stolen_items().clone();
+                self.record_item_uses(shooting_player, item_uses);
+                self.apply_turn_effects(&handcuffed_players, &stunned_players, &stolen_items);
                 return TurnSummaryOrRound::Round(self);
             }
             TakenAction::Terminal(taken_turn) => taken_turn,
         };
+        self.apply_turn_effects(
+            &taken_turn.handcuffed_players,
+            &taken_turn.stunned_players,
+            &taken_turn.stolen_items,
+        );
+        self.record_item_uses(shooting_player, taken_turn.item_uses);
 
-        // unsaw
-        let was_sawn = taken_turn.modifiers.shotgun_sawn;
         self.game_modifiers = taken_turn.modifiers;
-        self.game_modifiers.shotgun_sawn = false;
 
         match taken_turn.action {
             TerminalAction::Item(item_use_result) => {
@@ -352,42 +791,59 @@ where
                     }
                     ItemUseResult::Default
                     | ItemUseResult::LearnedShell(_)
-                    | ItemUseResult::StunnedPlayer(_) => unreachable!("The only terminal action for item use should be racking the shotgun empty!"),
+                    | ItemUseResult::StunnedPlayer(_)
+                    | ItemUseResult::Healed(_)
+                    | ItemUseResult::HandcuffedPlayer(_) => unreachable!("The only terminal action for item use should be racking the shotgun empty!"),
                 }
 
                 self.new_loadout(false);
-                let next_player = self.advance_turn();
-                let round_continuation = RoundContinuation::RoundContinues(ContinuedRound {
-                    turn_continuation: TurnContinuation::LoadoutEnds(next_player),
-                    round: self,
-                });
+                let mut burns = Vec::new();
+                let round_continuation = match self.advance_turn(&mut burns) {
+                    PassedTurn::Next(next_player) => {
+                        RoundContinuation::RoundContinues(ContinuedRound {
+                            turn_continuation: TurnContinuation::LoadoutEnds(next_player),
+                            round: self,
+                        })
+                    }
+                    PassedTurn::RoundWon(winner) => {
+                        RoundContinuation::RoundEnds(self.finish(winner))
+                    }
+                };
 
                 TurnSummaryOrRound::TurnSummary(TurnSummary {
                     shot_result: None,
+                    burns,
                     round_continuation,
                 })
             }
             TerminalAction::Shot(target_player_number) => {
                 let shell = self.shells.pop_front().unwrap();
+                let shell_type = shell.shell_type();
 
-                let target_seat_index = match target_player_number {
-                    PlayerNumber::One => 0,
-                    PlayerNumber::Two => 1,
-                    PlayerNumber::Three => 2,
-                    PlayerNumber::Four => 3,
-                };
-
-                let target_seat = self.seats.index_mut(target_seat_index);
+                let target_seat = self.seats.index_mut(target_player_number.seat_index());
 
                 let mut occupied_seat = target_seat.create_occupied_seat().unwrap();
 
-                let shotgun_damage = occupied_seat.shoot(shell, was_sawn);
+                let sawn = self.game_modifiers.sawn_for(shooting_player);
+                let damage = self.game_modifiers.damage(shell_type, shooting_player);
+                self.game_modifiers.after_shot();
+                let health_before = occupied_seat.player.health();
+                let shotgun_damage = occupied_seat.shoot(shell, sawn, damage);
+                let health_lost = (health_before - occupied_seat.player.health()) as u8;
+                let wires_cut = health_lost > 0 && self.check_sudden_death(target_player_number);
+
                 let advance_turn;
                 let outer_killed = match shotgun_damage {
-                    ShotgunDamage::RegularShot(killed) | ShotgunDamage::SawedShot(killed) => {
+                    ShotgunDamage::RegularShot(killed)
+                    | ShotgunDamage::SawedShot(killed)
+                    | ShotgunDamage::Slug(killed) => {
                         advance_turn = true;
                         killed
                     }
+                    ShotgunDamage::Incendiary | ShotgunDamage::Jammed => {
+                        advance_turn = true;
+                        false
+                    }
                     ShotgunDamage::Blank => {
                         advance_turn = target_player_number != shooting_player;
                         false
@@ -397,34 +853,38 @@ where
                 let shot_result = Some(ShotResult {
                     target_player: target_player_number,
                     damage: shotgun_damage,
+                    health_lost,
+                    wires_cut,
                 });
 
                 if outer_killed {
-                    let first_dead_player = self
-                        .first_dead_player
-                        .unwrap_or_else(|| occupied_seat.player.player_number());
-
-                    self.first_dead_player = Some(first_dead_player);
-
-                    target_seat.empty_dead_body();
-
-                    if self.living_players().count() == 1 {
-                        let winner = self.living_players().next().unwrap().player_number();
+                    if let Some(winner) = self.bury(target_player_number) {
+                        self.record_shot(shooting_player, shell_type, &shot_result);
                         return TurnSummaryOrRound::TurnSummary(TurnSummary {
                             shot_result,
-                            round_continuation: RoundContinuation::RoundEnds(FinishedRound {
-                                first_dead_player,
-                                winner,
-                                round: self,
-                            }),
+                            burns: Vec::new(),
+                            round_continuation: RoundContinuation::RoundEnds(self.finish(winner)),
                         });
                     }
                 }
 
+                self.record_shot(shooting_player, shell_type, &shot_result);
                 let new_loadout = self.shells().is_empty();
 
+                let mut burns = Vec::new();
                 let next_player = if advance_turn {
-                    self.advance_turn()
+                    match self.advance_turn(&mut burns) {
+                        PassedTurn::Next(next_player) => next_player,
+                        PassedTurn::RoundWon(winner) => {
+                            return TurnSummaryOrRound::TurnSummary(TurnSummary {
+                                shot_result,
+                                burns,
+                                round_continuation: RoundContinuation::RoundEnds(
+                                    self.finish(winner),
+                                ),
+                            });
+                        }
+                    }
                 } else {
                     shooting_player
                 };
@@ -441,6 +901,7 @@ where
 
                 TurnSummaryOrRound::TurnSummary(TurnSummary {
                     shot_result,
+                    burns,
                     round_continuation,
                 })
             }
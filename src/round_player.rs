@@ -2,15 +2,36 @@ use std::cmp::max;
 
 use thiserror::Error;
 
-use crate::{player::Player, player_number::PlayerNumber, round_start_info::RoundStartInfo};
+use crate::{
+    player::Player, player_number::PlayerNumber, round_rules::Healing,
+    round_start_info::RoundStartInfo,
+};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StunState {
     Unstunned,
     Stunned,
     Recovering,
 }
 
+/// Handcuffs skip one turn, then break on the turn that is skipped and come off on the player's next turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HandcuffState {
+    Free,
+    Cuffed,
+    Broken,
+}
+
+#[derive(Error, Debug, Clone, Copy)]
+pub enum AlreadyHandcuffedError {
+    #[error("Player is already handcuffed")]
+    AlreadyHandcuffed,
+    #[error("Player can't be handcuffed until the broken cuffs come off")]
+    CantHandcuffWhileBroken,
+}
+
 #[derive(Error, Debug, Clone, Copy)]
 pub enum AlreadyStunnedError {
     #[error("Player is already stunned")]
@@ -25,6 +46,9 @@ pub struct RoundPlayer {
     health: i32,
     max_health: i32,
     stun_state: StunState,
+    handcuff_state: HandcuffState,
+    wires_cut: bool,
+    burn: u8,
 }
 
 impl RoundPlayer {
@@ -35,6 +59,28 @@ impl RoundPlayer {
             health: max_health,
             max_health,
             stun_state: StunState::Unstunned,
+            handcuff_state: HandcuffState::Free,
+            wires_cut: false,
+            burn: 0,
+        }
+    }
+
+    pub(crate) fn from_parts(
+        player_number: PlayerNumber,
+        health: i32,
+        max_health: i32,
+        stun_state: StunState,
+        handcuff_state: HandcuffState,
+        wires_cut: bool,
+    ) -> Self {
+        RoundPlayer {
+            player_number,
+            health,
+            max_health,
+            stun_state,
+            handcuff_state,
+            wires_cut,
+            burn: 0,
         }
     }
 
@@ -50,16 +96,64 @@ impl RoundPlayer {
         self.stun_state
     }
 
-    pub fn take_damage(&mut self, sawn: bool) -> bool {
-        let damage = if sawn { 2 } else { 1 };
+    pub fn handcuff_state(&self) -> HandcuffState {
+        self.handcuff_state
+    }
 
-        self.health = max(0, self.health - damage);
+    /// Set during sudden death, the player can't heal and the next hit is lethal
+    pub fn wires_cut(&self) -> bool {
+        self.wires_cut
+    }
+
+    pub(crate) fn cut_wires(&mut self) {
+        self.wires_cut = true;
+    }
+
+    /// Damage the player takes when the turn next reaches them
+    pub fn burn(&self) -> u8 {
+        self.burn
+    }
+
+    pub(crate) fn ignite(&mut self, damage: u8) {
+        self.burn = self.burn.saturating_add(damage);
+    }
+
+    /// Takes the whole burn as damage
+    pub(crate) fn burn_out(&mut self) -> bool {
+        let damage = std::mem::take(&mut self.burn);
+        self.take_damage(damage)
+    }
+
+    pub fn take_damage(&mut self, damage: u8) -> bool {
+        self.health = if self.wires_cut {
+            0
+        } else {
+            max(0, self.health - i32::from(damage))
+        };
 
         self.health == 0
     }
 
-    pub fn gain_health(&mut self, amount: u8) {
-        self.health = max(self.max_health, self.health + i32::from(amount));
+    pub fn max_health(&self) -> i32 {
+        self.max_health
+    }
+
+    /// Heals as far as the rule allows and returns the health actually gained. Nothing heals a player whose wires are cut
+    pub fn gain_health(&mut self, amount: u8, healing: Healing) -> u8 {
+        if self.wires_cut {
+            return 0;
+        }
+
+        let limit = match healing {
+            Healing::Capped => self.max_health,
+            Healing::Overheal(extra) => self.max_health + i32::from(extra),
+            Healing::Disabled => self.health,
+        };
+
+        let health = max(self.health, (self.health + i32::from(amount)).min(limit));
+        let healed = health - self.health;
+        self.health = health;
+        healed as u8
     }
 
     pub fn stun(&mut self) -> Result<(), AlreadyStunnedError> {
@@ -73,6 +167,32 @@ impl RoundPlayer {
         }
     }
 
+    pub fn handcuff(&mut self) -> Result<(), AlreadyHandcuffedError> {
+        match self.handcuff_state {
+            HandcuffState::Free => {
+                self.handcuff_state = HandcuffState::Cuffed;
+                Ok(())
+            }
+            HandcuffState::Cuffed => Err(AlreadyHandcuffedError::AlreadyHandcuffed),
+            HandcuffState::Broken => Err(AlreadyHandcuffedError::CantHandcuffWhileBroken),
+        }
+    }
+
+    /// Updates the player's handcuff_state and returns true if the player can take their turn. Should only be called once prior to the player's turn
+    pub fn update_handcuffed(&mut self) -> bool {
+        match self.handcuff_state {
+            HandcuffState::Free => true,
+            HandcuffState::Cuffed => {
+                self.handcuff_state = HandcuffState::Broken;
+                false
+            }
+            HandcuffState::Broken => {
+                self.handcuff_state = HandcuffState::Free;
+                true
+            }
+        }
+    }
+
     /// Updates the player's stun_state and returns true if the player can take their turn. Should only be called once prior to the player's turn
     pub fn update_stunned(&mut self) -> bool {
         match self.stun_state {
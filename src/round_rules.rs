@@ -0,0 +1,73 @@
+use crate::{player_number::PlayerNumber, seat::MAX_ITEMS, shell::SpecialShells};
+
+/// Adjustments to the rules, carried from round to round
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundRules {
+    /// Most items each seat can hold, indexed by seat. Items dealt to a full seat are skipped
+    pub item_capacities: [usize; 4],
+    pub healing: Healing,
+    /// Health at or below which a player's wires are cut in the final round. None turns sudden death off
+    pub sudden_death_threshold: Option<u8>,
+    /// Adds Handcuffs to the item pool, they aren't part of multiplayer
+    pub handcuffs_dealt: bool,
+    /// Live shells swapped for special ones each loadout, as far as there are live shells
+    pub special_shells: SpecialShells,
+}
+
+/// How far healing items can raise health
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Healing {
+    /// Up to max health, like the game
+    #[default]
+    Capped,
+    /// Up to max health plus the given amount
+    Overheal(u8),
+    /// Healing items are still used up but do nothing
+    Disabled,
+}
+
+impl Default for RoundRules {
+    fn default() -> Self {
+        RoundRules {
+            item_capacities: [MAX_ITEMS; 4],
+            healing: Default::default(),
+            sudden_death_threshold: None,
+            handcuffs_dealt: false,
+            special_shells: Default::default(),
+        }
+    }
+}
+
+impl RoundRules {
+    /// Same capacity for every seat
+    pub fn with_item_capacity(mut self, item_capacity: usize) -> Self {
+        self.item_capacities = [item_capacity; 4];
+        self
+    }
+
+    pub fn with_healing(mut self, healing: Healing) -> Self {
+        self.healing = healing;
+        self
+    }
+
+    pub fn with_sudden_death(mut self, health_threshold: u8) -> Self {
+        self.sudden_death_threshold = Some(health_threshold);
+        self
+    }
+
+    pub fn with_handcuffs(mut self) -> Self {
+        self.handcuffs_dealt = true;
+        self
+    }
+
+    pub fn with_special_shells(mut self, special_shells: SpecialShells) -> Self {
+        self.special_shells = special_shells;
+        self
+    }
+
+    pub fn item_capacity(&self, player_number: PlayerNumber) -> usize {
+        self.item_capacities[player_number.seat_index()]
+    }
+}
@@ -1,6 +1,9 @@
-use rand::Rng;
-
-use crate::{multiplayer_count::MultiplayerCount, player_number::PlayerNumber, LOG_RNG};
+use crate::{
+    chance::{ChanceEvent, ChanceProvider},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    LOG_RNG,
+};
 
 #[derive(Debug, Clone)]
 pub struct RoundStartInfo {
@@ -9,21 +12,24 @@ pub struct RoundStartInfo {
     pub player_count: MultiplayerCount,
 }
 
+/// Highest max health a round can be dealt
+pub(crate) const MAX_DEALT_HEALTH: i32 = 5;
+
 impl RoundStartInfo {
-    pub fn new<TRng>(
+    pub fn new<TChance>(
         starting_player: PlayerNumber,
         player_count: MultiplayerCount,
-        rng: &mut TRng,
+        chance: &mut TChance,
     ) -> Self
     where
-        TRng: Rng,
+        TChance: ChanceProvider,
     {
         // https://github.com/thecatontheceiling/buckshotroulette_multiplayer/blob/aed4aecb7fd7f6cec14a7bd17239e736039915c0/global%20scripts/MP_RoundManager.gd#L427
         let max_health = match player_count {
-            MultiplayerCount::Two => rng.gen_range(3, 5),
-            MultiplayerCount::Three => rng.gen_range(4, 6),
-            MultiplayerCount::Four => rng.gen_range(3, 6),
-        };
+            MultiplayerCount::Two => chance.choose(ChanceEvent::MaxHealth, 3, 5),
+            MultiplayerCount::Three => chance.choose(ChanceEvent::MaxHealth, 4, 6),
+            MultiplayerCount::Four => chance.choose(ChanceEvent::MaxHealth, 3, 6),
+        } as i32;
 
         if LOG_RNG {
             println!("{} health this round", max_health);
@@ -36,6 +42,18 @@ impl RoundStartInfo {
         }
     }
 
+    pub(crate) fn from_parts(
+        max_health: i32,
+        starting_player: PlayerNumber,
+        player_count: MultiplayerCount,
+    ) -> Self {
+        RoundStartInfo {
+            max_health,
+            starting_player,
+            player_count,
+        }
+    }
+
     pub fn max_health(&self) -> i32 {
         self.max_health
     }
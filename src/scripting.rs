@@ -0,0 +1,371 @@
+use std::{
+    collections::VecDeque,
+    fs,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use rhai::{Array, CallFnOptions, Dynamic, Engine, EvalAltResult, ParseError, Scope, AST};
+use thiserror::Error;
+
+use crate::{
+    item::Item,
+    item_registry::{ItemBehavior, ItemContext, ItemLimits},
+    modifier::{GameModifiers, Modifier, ModifierEffect, ModifierScope},
+    round_player::RoundPlayer,
+    round_rules::{Healing, RoundRules},
+    shell::{Shell, ShellType},
+    turn::{HealResult, InvalidItemUseError, ItemUseResult, LearnedShell, ShotgunRackResult},
+};
+
+/// Compiles and runs Rhai scripts, stopping any that run past the instruction budget
+///
+/// Item scripts define `NAME`, `PLAYER_LIMIT` and `GLOBAL_LIMIT` constants and a `use_item(round)` function.
+/// `round` exposes `shell_count()`, `is_live(i)`, `invert(i)`, `rack()`, `reveal(i)`, `heal(n)`, `has_item(name)`
+/// and the `health`, `max_health`, `wires_cut`, `items`, `sawn` and `inverted` properties.
+///
+/// Rule scripts change the `rules` variable through the `item_capacity`, `sudden_death` and `handcuffs` properties
+/// and the `cap_healing()`, `overheal(n)` and `disable_healing()` functions.
+#[derive(Debug, Clone)]
+pub struct ScriptEngine {
+    engine: Arc<Engine>,
+}
+
+/// An item whose effect is a script
+#[derive(Debug, Clone)]
+pub struct ScriptedItem {
+    name: String,
+    limits: ItemLimits,
+    engine: Arc<Engine>,
+    ast: Arc<AST>,
+}
+
+#[derive(Error, Debug)]
+pub enum ScriptError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error(transparent)]
+    Eval(#[from] Box<EvalAltResult>),
+    #[error("Script doesn't define {0}")]
+    Missing(&'static str),
+    #[error("Script's {0} is out of range")]
+    OutOfRange(&'static str),
+}
+
+/// What a script sees of the round, written back once it returns
+#[derive(Debug)]
+struct ScriptState {
+    shells: VecDeque<Shell>,
+    modifiers: GameModifiers,
+    player: RoundPlayer,
+    items: Vec<Item>,
+    healing: Healing,
+    result: Option<ItemUseResult>,
+}
+
+#[derive(Debug, Clone)]
+struct ScriptRound(Arc<Mutex<ScriptState>>);
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+impl ScriptEngine {
+    pub fn new(max_operations: u64) -> Self {
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(max_operations)
+            .set_max_call_levels(32)
+            .set_max_expr_depths(64, 32)
+            .set_max_string_size(1024)
+            .set_max_array_size(1024)
+            .set_max_map_size(1024)
+            .disable_symbol("eval")
+            .on_print(|_| {})
+            .on_debug(|_, _, _| {});
+
+        register_round(&mut engine);
+        register_rules(&mut engine);
+
+        ScriptEngine {
+            engine: Arc::new(engine),
+        }
+    }
+
+    pub fn load_item<TPath>(&self, path: TPath) -> Result<ScriptedItem, ScriptError>
+    where
+        TPath: AsRef<Path>,
+    {
+        self.item_from_source(&fs::read_to_string(path)?)
+    }
+
+    pub fn item_from_source(&self, source: &str) -> Result<ScriptedItem, ScriptError> {
+        let ast = self.engine.compile(source)?;
+        if !ast
+            .iter_functions()
+            .any(|function| function.name == "use_item" && function.params.len() == 1)
+        {
+            return Err(ScriptError::Missing("use_item(round)"));
+        }
+
+        let mut scope = Scope::new();
+        self.engine.run_ast_with_scope(&mut scope, &ast)?;
+
+        let name = scope
+            .get_value::<String>("NAME")
+            .ok_or(ScriptError::Missing("NAME"))?;
+        let limit = |constant| {
+            scope
+                .get_value::<i64>(constant)
+                .ok_or(ScriptError::Missing(constant))
+                .and_then(|limit| {
+                    usize::try_from(limit).map_err(|_| ScriptError::OutOfRange(constant))
+                })
+        };
+
+        Ok(ScriptedItem {
+            limits: ItemLimits {
+                player: limit("PLAYER_LIMIT")?,
+                global: limit("GLOBAL_LIMIT")?,
+            },
+            name,
+            engine: self.engine.clone(),
+            ast: Arc::new(ast),
+        })
+    }
+
+    /// Runs the script on top of the given rules
+    pub fn load_rules<TPath>(
+        &self,
+        path: TPath,
+        rules: RoundRules,
+    ) -> Result<RoundRules, ScriptError>
+    where
+        TPath: AsRef<Path>,
+    {
+        self.rules_from_source(&fs::read_to_string(path)?, rules)
+    }
+
+    pub fn rules_from_source(
+        &self,
+        source: &str,
+        rules: RoundRules,
+    ) -> Result<RoundRules, ScriptError> {
+        let mut scope = Scope::new();
+        scope.push("rules", rules);
+        self.engine.run_with_scope(&mut scope, source)?;
+
+        scope
+            .get_value::<RoundRules>("rules")
+            .ok_or(ScriptError::Missing("rules"))
+    }
+}
+
+impl ItemBehavior for ScriptedItem {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn limits(&self) -> ItemLimits {
+        self.limits
+    }
+
+    fn use_item(&self, context: ItemContext) -> Result<ItemUseResult, InvalidItemUseError> {
+        let state = Arc::new(Mutex::new(ScriptState {
+            shells: context.shells.clone(),
+            modifiers: context.modifiers.clone(),
+            player: context.player.clone(),
+            items: context.items.to_vec(),
+            healing: context.rules.healing,
+            result: None,
+        }));
+
+        let call_result = self.engine.call_fn_with_options::<Dynamic>(
+            CallFnOptions::new().eval_ast(false),
+            &mut Scope::new(),
+            &self.ast,
+            "use_item",
+            (ScriptRound(state.clone()),),
+        );
+        if call_result.is_err() {
+            return Err(InvalidItemUseError::ScriptFailed);
+        }
+
+        // nothing is changed unless the script finishes
+        let mut state = state.lock().unwrap();
+        *context.shells = std::mem::take(&mut state.shells);
+        *context.modifiers = state.modifiers.clone();
+        *context.player = state.player.clone();
+        Ok(state.result.take().unwrap_or(ItemUseResult::Default))
+    }
+}
+
+impl ScriptRound {
+    fn with_state<F, TRet>(&mut self, func: F) -> TRet
+    where
+        F: FnOnce(&mut ScriptState) -> TRet,
+    {
+        func(&mut self.0.lock().unwrap())
+    }
+
+    fn shell_index(&mut self, relative_index: i64) -> ScriptResult<usize> {
+        let shell_count = self.with_state(|state| state.shells.len());
+        usize::try_from(relative_index)
+            .ok()
+            .filter(|index| *index < shell_count)
+            .ok_or_else(|| format!("No shell at {}", relative_index).into())
+    }
+}
+
+fn register_round(engine: &mut Engine) {
+    engine
+        .register_type_with_name::<ScriptRound>("Round")
+        .register_fn("shell_count", |round: &mut ScriptRound| {
+            round.with_state(|state| state.shells.len() as i64)
+        })
+        .register_fn(
+            "is_live",
+            |round: &mut ScriptRound, relative_index: i64| -> ScriptResult<bool> {
+                let index = round.shell_index(relative_index)?;
+                Ok(round.with_state(|state| state.shells[index].visible_type() == ShellType::Live))
+            },
+        )
+        .register_fn(
+            "invert",
+            |round: &mut ScriptRound, relative_index: i64| -> ScriptResult<()> {
+                let index = round.shell_index(relative_index)?;
+                round.with_state(|state| state.shells[index].invert());
+                Ok(())
+            },
+        )
+        .register_fn("rack", |round: &mut ScriptRound| -> ScriptResult<bool> {
+            round.with_state(|state| {
+                let ejected_shell = state.shells.pop_front().ok_or("No shell to rack")?;
+                state.result = Some(ItemUseResult::ShotgunRacked(ShotgunRackResult {
+                    empty: state.shells.is_empty(),
+                    ejected_shell_type: ejected_shell.visible_type(),
+                }));
+                Ok(ejected_shell.visible_type() == ShellType::Live)
+            })
+        })
+        .register_fn(
+            "reveal",
+            |round: &mut ScriptRound, relative_index: i64| -> ScriptResult<bool> {
+                let index = round.shell_index(relative_index)?;
+                Ok(round.with_state(|state| {
+                    let shell_type = state.shells[index].visible_type();
+                    state.result = Some(ItemUseResult::LearnedShell(LearnedShell {
+                        relative_index: index,
+                        shell_type,
+                    }));
+                    shell_type == ShellType::Live
+                }))
+            },
+        )
+        .register_fn(
+            "heal",
+            |round: &mut ScriptRound, amount: i64| -> ScriptResult<i64> {
+                let requested = u8::try_from(amount).map_err(|_| "Heal amount out of range")?;
+                Ok(round.with_state(|state| {
+                    let healed = state.player.gain_health(requested, state.healing);
+                    state.result = Some(ItemUseResult::Healed(HealResult { requested, healed }));
+                    i64::from(healed)
+                }))
+            },
+        )
+        .register_fn("has_item", |round: &mut ScriptRound, name: &str| {
+            round.with_state(|state| state.items.iter().any(|item| item.to_string() == name))
+        })
+        .register_get("health", |round: &mut ScriptRound| {
+            round.with_state(|state| i64::from(state.player.health()))
+        })
+        .register_get("max_health", |round: &mut ScriptRound| {
+            round.with_state(|state| i64::from(state.player.max_health()))
+        })
+        .register_get("wires_cut", |round: &mut ScriptRound| {
+            round.with_state(|state| state.player.wires_cut())
+        })
+        .register_get("items", |round: &mut ScriptRound| {
+            round.with_state(|state| {
+                state
+                    .items
+                    .iter()
+                    .map(|item| Dynamic::from(item.to_string()))
+                    .collect::<Array>()
+            })
+        })
+        .register_get_set(
+            "sawn",
+            |round: &mut ScriptRound| round.with_state(|state| state.modifiers.shotgun_sawn()),
+            |round: &mut ScriptRound, sawn: bool| {
+                round.with_state(|state| {
+                    if sawn {
+                        // already sawn is fine here
+                        let _ = state.modifiers.add(Modifier::sawn_shotgun());
+                    } else {
+                        state
+                            .modifiers
+                            .remove(ModifierEffect::Sawn, ModifierScope::Table);
+                    }
+                })
+            },
+        )
+        .register_get_set(
+            "inverted",
+            |round: &mut ScriptRound| {
+                round.with_state(|state| state.modifiers.turn_order_inverted())
+            },
+            |round: &mut ScriptRound, inverted: bool| {
+                round.with_state(|state| {
+                    if state.modifiers.turn_order_inverted() != inverted {
+                        state
+                            .modifiers
+                            .add(Modifier::inverted_turn_order())
+                            .unwrap();
+                    }
+                })
+            },
+        );
+}
+
+fn register_rules(engine: &mut Engine) {
+    engine
+        .register_type_with_name::<RoundRules>("Rules")
+        .register_set(
+            "item_capacity",
+            |rules: &mut RoundRules, item_capacity: i64| -> ScriptResult<()> {
+                let item_capacity =
+                    usize::try_from(item_capacity).map_err(|_| "Item capacity out of range")?;
+                *rules = rules.with_item_capacity(item_capacity);
+                Ok(())
+            },
+        )
+        .register_set(
+            "sudden_death",
+            |rules: &mut RoundRules, health_threshold: i64| -> ScriptResult<()> {
+                let health_threshold = u8::try_from(health_threshold)
+                    .map_err(|_| "Sudden death threshold out of range")?;
+                *rules = rules.with_sudden_death(health_threshold);
+                Ok(())
+            },
+        )
+        .register_get_set(
+            "handcuffs",
+            |rules: &mut RoundRules| rules.handcuffs_dealt,
+            |rules: &mut RoundRules, handcuffs_dealt: bool| rules.handcuffs_dealt = handcuffs_dealt,
+        )
+        .register_fn("cap_healing", |rules: &mut RoundRules| {
+            rules.healing = Healing::Capped
+        })
+        .register_fn(
+            "overheal",
+            |rules: &mut RoundRules, extra: i64| -> ScriptResult<()> {
+                let extra = u8::try_from(extra).map_err(|_| "Overheal out of range")?;
+                rules.healing = Healing::Overheal(extra);
+                Ok(())
+            },
+        )
+        .register_fn("disable_healing", |rules: &mut RoundRules| {
+            rules.healing = Healing::Disabled
+        });
+}
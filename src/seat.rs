@@ -1,37 +1,52 @@
 use indexmap::IndexMap;
-use rand::Rng;
 
 use crate::{
-    item::{
-        global_item_limit, initialize_item_count_map, player_item_limit, Item, NotAdreneline,
-        UnaryItem,
-    },
+    chance::{ChanceEvent, ChanceProvider},
+    item::Item,
+    item_registry::ItemRegistry,
+    modifier::{GameModifiers, Modifier},
     player_number::PlayerNumber,
-    round_player::{RoundPlayer, StunState},
-    shell::{Shell, ShotgunDamage},
+    round_player::{HandcuffState, RoundPlayer, StunState},
+    shell::{Shell, ShellType, ShotgunDamage},
     LOG_RNG,
 };
 
-const MAX_ITEMS: usize = 8;
+/// Item slots on the table in front of each seat
+pub const MAX_ITEMS: usize = 8;
 
 #[derive(Debug, Clone)]
 pub struct Seat {
     player_number: PlayerNumber,
     player: Option<RoundPlayer>,
     items: Vec<Item>,
+    item_slots: Vec<usize>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ForfeitedItems {
+    /// Items stay on the table like a dead body's
+    Keep,
+    Discard,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SeatView {
     pub stun_state: Option<StunState>,
+    pub handcuff_state: Option<HandcuffState>,
     pub player_number: PlayerNumber,
     pub items: Vec<Item>,
+    /// Slot each item occupies, in the same order as items
+    pub item_slots: Vec<usize>,
+    /// Modifiers scoped to the seat's player
+    pub modifiers: Vec<Modifier>,
 }
 
 #[derive(Debug)]
 pub struct OccupiedSeat<'seat> {
     pub player: &'seat mut RoundPlayer,
     pub items: &'seat mut Vec<Item>,
+    pub item_slots: &'seat mut Vec<usize>,
 }
 
 impl Seat {
@@ -40,6 +55,30 @@ impl Seat {
             player_number: player.player_number(),
             player: Some(player),
             items: Vec::with_capacity(MAX_ITEMS),
+            item_slots: Vec::with_capacity(MAX_ITEMS),
+        }
+    }
+
+    pub fn new_vacant(player_number: PlayerNumber) -> Self {
+        Seat {
+            player_number,
+            player: None,
+            items: Vec::with_capacity(MAX_ITEMS),
+            item_slots: Vec::with_capacity(MAX_ITEMS),
+        }
+    }
+
+    /// Items are placed in consecutive slots
+    pub(crate) fn from_parts(
+        player_number: PlayerNumber,
+        player: Option<RoundPlayer>,
+        items: Vec<Item>,
+    ) -> Self {
+        Seat {
+            player_number,
+            player,
+            item_slots: (0..items.len()).collect(),
+            items,
         }
     }
 
@@ -54,12 +93,13 @@ impl Seat {
         }
     }
 
-    pub fn create_occupied_seat(&mut self) -> Option<OccupiedSeat> {
+    pub fn create_occupied_seat(&mut self) -> Option<OccupiedSeat<'_>> {
         match &mut self.player {
             Some(player) => {
                 let occupied_seat = OccupiedSeat {
                     player,
                     items: &mut self.items,
+                    item_slots: &mut self.item_slots,
                 };
                 Some(occupied_seat)
             }
@@ -73,51 +113,70 @@ impl Seat {
         self.player = None;
     }
 
+    pub fn vacate(&mut self, forfeited_items: ForfeitedItems) {
+        assert!(self.player.is_some());
+        self.player = None;
+        if forfeited_items == ForfeitedItems::Discard {
+            self.items.clear();
+            self.item_slots.clear();
+        }
+    }
+
     pub fn items(&self) -> &Vec<Item> {
         &self.items
     }
 
-    pub fn create_view(&self) -> SeatView {
+    /// Slot each item occupies, in the same order as items()
+    pub fn item_slots(&self) -> &Vec<usize> {
+        &self.item_slots
+    }
+
+    /// Removes the first matching item and frees its slot
+    pub(crate) fn take_item(&mut self, item: Item) -> Option<Item> {
+        let index = self.items.iter().position(|held| *held == item)?;
+        self.item_slots.remove(index);
+        Some(self.items.remove(index))
+    }
+
+    pub fn create_view(&self, modifiers: &GameModifiers) -> SeatView {
         SeatView {
+            modifiers: modifiers.for_player(self.player_number).copied().collect(),
             stun_state: self.player.as_ref().map(|player| player.stun_state()),
+            handcuff_state: self.player.as_ref().map(|player| player.handcuff_state()),
             player_number: self.player_number,
             items: self.items.clone(),
+            item_slots: self.item_slots.clone(),
         }
     }
 
-    pub fn get_new_item<TRng>(
+    /// Draws from the items in current_table_item_counts that are under the registry's limits.
+    /// None if the seat is vacant or every item is at its limit
+    pub fn get_new_item<TChance>(
         &mut self,
-        remaining_players: usize,
         current_table_item_counts: &IndexMap<Item, usize>,
-        rng: &mut TRng,
+        item_registry: &ItemRegistry,
+        chance: &mut TChance,
     ) -> Option<Item>
     where
-        TRng: Rng,
+        TChance: ChanceProvider,
     {
         self.player.as_ref()?;
 
         let mut item_pool = Vec::with_capacity(current_table_item_counts.len());
 
-        let mut player_item_counts = initialize_item_count_map();
-        self.items.iter().for_each(|item| {
-            let count = player_item_counts.get_mut(item).unwrap();
-            *count += 1;
-        });
-
-        current_table_item_counts.keys().for_each(|item| {
-            add_item_to_pool_checked(
-                &mut item_pool,
-                *item,
-                current_table_item_counts,
-                &mut player_item_counts,
-                || {
-                    *item != Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Remote))
-                        || remaining_players > 2
-                },
-            )
-        });
-
-        let index = rng.gen_range(0, item_pool.len());
+        for (item, global_count) in current_table_item_counts {
+            let limits = item_registry.limits(*item).unwrap();
+            let player_count = self.items.iter().filter(|held| *held == item).count();
+            if player_count < limits.player && *global_count < limits.global {
+                item_pool.push(*item);
+            }
+        }
+
+        if item_pool.is_empty() {
+            return None;
+        }
+
+        let index = chance.choose(ChanceEvent::Item(self.player_number), 0, item_pool.len());
 
         let item = item_pool[index];
 
@@ -125,50 +184,40 @@ impl Seat {
             println!("Player {} grabbed item {}", self.player_number, item);
         }
 
+        // items go in the first free slot
+        let slot = (0..).find(|slot| !self.item_slots.contains(slot)).unwrap();
+
         self.items.push(item);
+        self.item_slots.push(slot);
         Some(item)
     }
 }
 
 impl<'seat> OccupiedSeat<'seat> {
-    pub fn shoot(&mut self, shell: Shell, sawn: bool) -> ShotgunDamage {
-        if shell.fire() {
-            let killed = self.player.take_damage(sawn);
-            if sawn {
-                ShotgunDamage::SawedShot(killed)
-            } else {
-                ShotgunDamage::RegularShot(killed)
-            }
-        } else {
-            ShotgunDamage::Blank
-        }
-    }
-}
-
-fn add_item_to_pool_checked<F>(
-    pool: &mut Vec<Item>,
-    item: Item,
-    current_table_item_counts: &IndexMap<Item, usize>,
-    player_item_counts: &mut IndexMap<Item, usize>,
-    additional_check: F,
-) where
-    F: FnOnce() -> bool,
-{
-    let player_item_limit = player_item_limit(item);
-    let current_count = player_item_counts.get(&item);
-    if player_item_limit <= *current_count.unwrap() {
-        return;
-    }
-
-    let global_item_limit = global_item_limit(item);
-    let global_count = current_table_item_counts.get(&item);
-    if global_item_limit <= *global_count.unwrap() {
-        return;
+    pub fn remove_item(&mut self, index: usize) -> Item {
+        self.item_slots.remove(index);
+        self.items.remove(index)
     }
 
-    if !additional_check() {
-        return;
+    /// Damage comes from the round's modifiers, sawn only changes how a live shot is reported
+    pub fn shoot(&mut self, shell: Shell, sawn: bool, damage: u8) -> ShotgunDamage {
+        let shell_type = shell.shell_type();
+        match shell_type {
+            ShellType::Live => {
+                let killed = self.player.take_damage(damage);
+                if sawn {
+                    ShotgunDamage::SawedShot(killed)
+                } else {
+                    ShotgunDamage::RegularShot(killed)
+                }
+            }
+            ShellType::Slug => ShotgunDamage::Slug(self.player.take_damage(damage)),
+            ShellType::Incendiary => {
+                self.player.ignite(damage);
+                ShotgunDamage::Incendiary
+            }
+            ShellType::Dud => ShotgunDamage::Jammed,
+            ShellType::Blank => ShotgunDamage::Blank,
+        }
     }
-
-    pool.push(item);
 }
@@ -0,0 +1,329 @@
+mod client;
+mod framing;
+
+use std::{
+    io::{self, BufReader},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+use indexmap::IndexMap;
+use rand::Rng;
+
+use crate::{
+    action::Action,
+    decision::ActionOutcome,
+    game_session::GameSession,
+    player_number::PlayerNumber,
+    protocol::{
+        ActionNotification, ClientMessage, Event, GameId, ProtocolError, RedactedItemResult,
+        RejectionReason, ServerMessage, TableView, TurnOutcome,
+    },
+    seat::ForfeitedItems,
+};
+
+pub use client::GameClient;
+
+use framing::{read_message, write_message};
+
+/// Hosts GameSessions and lets clients connected over TCP play them
+#[derive(Debug)]
+pub struct GameServer<TRng> {
+    local_addr: SocketAddr,
+    games: Arc<Mutex<HostedGames<TRng>>>,
+    shutting_down: Arc<AtomicBool>,
+    accept_thread: JoinHandle<()>,
+    connections: Arc<Mutex<Vec<ConnectionThread>>>,
+}
+
+/// Kept so shutdown can close the stream and wait for the thread
+#[derive(Debug)]
+struct ConnectionThread {
+    stream: TcpStream,
+    thread: JoinHandle<()>,
+}
+
+#[derive(Debug)]
+struct HostedGames<TRng> {
+    games: IndexMap<GameId, HostedGame<TRng>>,
+    next_game_id: GameId,
+}
+
+#[derive(Debug)]
+struct HostedGame<TRng> {
+    session: GameSession<TRng>,
+    connections: IndexMap<PlayerNumber, TcpStream>,
+}
+
+impl<TRng> GameServer<TRng>
+where
+    TRng: Rng + Send + 'static,
+{
+    pub fn start<TAddr>(addr: TAddr) -> io::Result<Self>
+    where
+        TAddr: ToSocketAddrs,
+    {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+
+        let games = Arc::new(Mutex::new(HostedGames {
+            games: IndexMap::new(),
+            next_game_id: 1,
+        }));
+        let shutting_down = Arc::new(AtomicBool::new(false));
+        let connections = Arc::new(Mutex::new(Vec::<ConnectionThread>::new()));
+
+        let accept_games = games.clone();
+        let accept_shutting_down = shutting_down.clone();
+        let accept_connections = connections.clone();
+        let accept_thread = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if accept_shutting_down.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let Ok(shutdown_stream) = stream.try_clone() else {
+                    continue;
+                };
+
+                let games = accept_games.clone();
+                let mut connections = accept_connections.lock().unwrap();
+                connections.retain(|connection| !connection.thread.is_finished());
+                connections.push(ConnectionThread {
+                    stream: shutdown_stream,
+                    thread: thread::spawn(move || run_connection(stream, games)),
+                });
+            }
+        });
+
+        Ok(GameServer {
+            local_addr,
+            games,
+            shutting_down,
+            accept_thread,
+            connections,
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    pub fn host_game(&self, session: GameSession<TRng>) -> GameId {
+        let mut games = self.games.lock().unwrap();
+        let game_id = games.next_game_id;
+        games.next_game_id += 1;
+        games.games.insert(
+            game_id,
+            HostedGame {
+                session,
+                connections: IndexMap::new(),
+            },
+        );
+
+        game_id
+    }
+
+    pub fn with_session<F, TRet>(&self, game_id: GameId, func: F) -> Option<TRet>
+    where
+        F: FnOnce(&GameSession<TRng>) -> TRet,
+    {
+        let games = self.games.lock().unwrap();
+        games.games.get(&game_id).map(|game| func(&game.session))
+    }
+
+    /// Stops accepting connections, disconnects every client and waits for their threads to exit
+    pub fn shutdown(self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+
+        // wake up the accept loop
+        let _ = TcpStream::connect(self.local_addr);
+        let _ = self.accept_thread.join();
+
+        let connections = std::mem::take(&mut *self.connections.lock().unwrap());
+        for connection in &connections {
+            let _ = connection.stream.shutdown(Shutdown::Both);
+        }
+        for connection in connections {
+            let _ = connection.thread.join();
+        }
+    }
+}
+
+impl<TRng> HostedGame<TRng>
+where
+    TRng: Rng,
+{
+    fn join(&mut self, player: PlayerNumber, stream: TcpStream) -> Result<(), RejectionReason> {
+        match self.session.players().get(player) {
+            Ok(game_player) => {
+                if game_player.forfeited() {
+                    return Err(RejectionReason::Forfeited);
+                }
+            }
+            Err(_) => return Err(RejectionReason::NoPlayer),
+        }
+
+        if self.connections.contains_key(&player) {
+            return Err(RejectionReason::SeatTaken);
+        }
+
+        self.connections.insert(player, stream);
+        Ok(())
+    }
+
+    fn take_action(
+        &mut self,
+        player: PlayerNumber,
+        action: Action,
+    ) -> Result<ActionOutcome, RejectionReason> {
+        let round = match self.session.round() {
+            Some(round) => round,
+            None => return Err(RejectionReason::GameOver),
+        };
+
+        if round.next_player() != player {
+            return Err(RejectionReason::NotYourTurn);
+        }
+
+        self.session.submit(action).map_err(RejectionReason::from)
+    }
+
+    fn broadcast_update(&mut self, outcome: ActionOutcome) {
+        let table = TableView::new(&self.session);
+        let turn_outcome = TurnOutcome::from_action_outcome(&outcome);
+        for (recipient, stream) in &mut self.connections {
+            let notification = ActionNotification {
+                actor: outcome.actor,
+                action: outcome.action,
+                item_result: outcome.item_result.clone().map(|item_result| {
+                    RedactedItemResult::new(item_result, *recipient == outcome.actor)
+                }),
+                turn_outcome: turn_outcome.clone(),
+                table: table.clone(),
+            };
+
+            // failed writes are cleaned up by the recipient's connection thread
+            let _ = write_message(
+                stream,
+                &ServerMessage::Event(Event::ActionTaken(notification)),
+            );
+
+            let shot_cut = outcome
+                .shot_result
+                .iter()
+                .filter(|shot| shot.wires_cut)
+                .map(|shot| shot.target_player);
+            let burn_cut = outcome
+                .burns
+                .iter()
+                .filter(|burn| burn.wires_cut)
+                .map(|burn| burn.player);
+            for player in shot_cut.chain(burn_cut) {
+                let _ = write_message(stream, &ServerMessage::Event(Event::WiresCut { player }));
+            }
+        }
+    }
+
+    fn disconnect(&mut self, player: PlayerNumber) {
+        self.connections.shift_remove(&player);
+
+        if self.session.round().is_none() {
+            return;
+        }
+
+        if self.session.forfeit(player, ForfeitedItems::Keep).is_err() {
+            return;
+        }
+
+        let table = TableView::new(&self.session);
+        for stream in self.connections.values_mut() {
+            let _ = write_message(
+                stream,
+                &ServerMessage::Event(Event::Forfeited {
+                    player,
+                    table: table.clone(),
+                }),
+            );
+        }
+    }
+}
+
+fn run_connection<TRng>(mut stream: TcpStream, games: Arc<Mutex<HostedGames<TRng>>>)
+where
+    TRng: Rng,
+{
+    let mut reader = match stream.try_clone() {
+        Ok(read_stream) => BufReader::new(read_stream),
+        Err(_) => return,
+    };
+
+    let mut joined = None;
+    loop {
+        let message = match read_message::<_, ClientMessage>(&mut reader) {
+            Ok(Ok(message)) => message,
+            Ok(Err(error)) => {
+                let rejection = match error {
+                    ProtocolError::UnsupportedVersion(_) => RejectionReason::UnsupportedVersion,
+                    ProtocolError::Json(_) | ProtocolError::Binary(_) => RejectionReason::Malformed,
+                };
+
+                let _games = games.lock().unwrap();
+                let _ = write_message(&mut stream, &ServerMessage::Rejected(rejection));
+                continue;
+            }
+            Err(_) => break,
+        };
+
+        // all writes happen under the lock so broadcasts can't interleave
+        let mut games = games.lock().unwrap();
+        let result = match message {
+            ClientMessage::Join { game_id, player } => match joined {
+                Some(_) => Err(RejectionReason::AlreadyJoined),
+                None => match games.games.get_mut(&game_id) {
+                    Some(game) => match stream.try_clone() {
+                        Ok(write_stream) => game.join(player, write_stream).map(|_| {
+                            joined = Some((game_id, player));
+                            let _ = write_message(
+                                &mut stream,
+                                &ServerMessage::Joined {
+                                    game_id,
+                                    player,
+                                    table: TableView::new(&game.session),
+                                },
+                            );
+                        }),
+                        Err(_) => break,
+                    },
+                    None => Err(RejectionReason::NoGame),
+                },
+            },
+            ClientMessage::Act(action) => match joined {
+                Some((game_id, player)) => {
+                    let game = games.games.get_mut(&game_id).unwrap();
+                    game.take_action(player, action)
+                        .map(|outcome| game.broadcast_update(outcome))
+                }
+                None => Err(RejectionReason::NotJoined),
+            },
+        };
+
+        if let Err(rejection) = result {
+            let _ = write_message(&mut stream, &ServerMessage::Rejected(rejection));
+        }
+    }
+
+    if let Some((game_id, player)) = joined {
+        let mut games = games.lock().unwrap();
+        if let Some(game) = games.games.get_mut(&game_id) {
+            game.disconnect(player);
+        }
+    }
+}
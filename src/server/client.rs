@@ -0,0 +1,48 @@
+use std::{
+    io::{self, BufReader},
+    net::{TcpStream, ToSocketAddrs},
+};
+
+use crate::{
+    action::Action,
+    player_number::PlayerNumber,
+    protocol::{ClientMessage, GameId, ServerMessage},
+};
+
+use super::framing::{read_message, write_message};
+
+/// Blocking client for a GameServer
+#[derive(Debug)]
+pub struct GameClient {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl GameClient {
+    pub fn connect<TAddr>(addr: TAddr) -> io::Result<Self>
+    where
+        TAddr: ToSocketAddrs,
+    {
+        let writer = TcpStream::connect(addr)?;
+        let reader = BufReader::new(writer.try_clone()?);
+        Ok(GameClient { reader, writer })
+    }
+
+    pub fn send(&mut self, message: &ClientMessage) -> io::Result<()> {
+        write_message(&mut self.writer, message)
+    }
+
+    pub fn recv(&mut self) -> io::Result<ServerMessage> {
+        read_message(&mut self.reader)?
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub fn join(&mut self, game_id: GameId, player: PlayerNumber) -> io::Result<ServerMessage> {
+        self.send(&ClientMessage::Join { game_id, player })?;
+        self.recv()
+    }
+
+    pub fn act(&mut self, action: Action) -> io::Result<()> {
+        self.send(&ClientMessage::Act(action))
+    }
+}
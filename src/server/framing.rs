@@ -0,0 +1,36 @@
+use std::io::{self, BufRead, Write};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::protocol::{decode_json, encode_json, ProtocolError};
+
+/// Messages are sent as JSON encoded envelopes, one per line
+pub(crate) fn write_message<TWrite, TMessage>(
+    writer: &mut TWrite,
+    message: &TMessage,
+) -> io::Result<()>
+where
+    TWrite: Write,
+    TMessage: Serialize,
+{
+    let mut line = encode_json(message).map_err(io::Error::other)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes())?;
+    writer.flush()
+}
+
+/// The outer result is the state of the connection, the inner one the validity of the line read
+pub(crate) fn read_message<TRead, TMessage>(
+    reader: &mut TRead,
+) -> io::Result<Result<TMessage, ProtocolError>>
+where
+    TRead: BufRead,
+    TMessage: DeserializeOwned,
+{
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    Ok(decode_json(&line))
+}
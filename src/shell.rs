@@ -1,36 +1,129 @@
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Damage dealt by a slug, sawing the shotgun doesn't change it
+pub const SLUG_DAMAGE: u8 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShellType {
     Live,
     Blank,
+    /// Fixed higher damage
+    Slug,
+    /// Sets the target alight, the damage lands when the turn next reaches them, even a turn they sit out
+    Incendiary,
+    /// Jams and ends the turn. Looks like a live shell until it is fired
+    Dud,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShotgunDamage {
     Blank,
     RegularShot(bool),
     SawedShot(bool),
+    Slug(bool),
+    /// The target is burning, see RoundPlayer::burn
+    Incendiary,
+    Jammed,
+}
+
+/// How many of a loadout's live shells are swapped for each special type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpecialShells {
+    pub slug: usize,
+    pub incendiary: usize,
+    pub dud: usize,
 }
 
 #[derive(Debug, Clone)]
 pub struct Shell(ShellType);
 
+/// Special types in the order they are loaded
+pub static SPECIAL_SHELL_TYPES: [ShellType; 3] =
+    [ShellType::Slug, ShellType::Incendiary, ShellType::Dud];
+
+impl ShellType {
+    /// Damage dealt to the target, for Incendiary it is dealt later
+    pub fn damage(self, sawn: bool) -> u8 {
+        match self {
+            ShellType::Live | ShellType::Incendiary => {
+                if sawn {
+                    2
+                } else {
+                    1
+                }
+            }
+            ShellType::Slug => SLUG_DAMAGE,
+            ShellType::Blank | ShellType::Dud => 0,
+        }
+    }
+
+    /// What players see when the shell is revealed or ejected
+    pub fn visible_type(self) -> ShellType {
+        match self {
+            ShellType::Dud => ShellType::Live,
+            shell_type => shell_type,
+        }
+    }
+
+    /// Blanks become live, everything else becomes blank
+    pub fn inverted(self) -> ShellType {
+        match self {
+            ShellType::Blank => ShellType::Live,
+            ShellType::Live | ShellType::Slug | ShellType::Incendiary | ShellType::Dud => {
+                ShellType::Blank
+            }
+        }
+    }
+}
+
+impl SpecialShells {
+    /// Live, Blank and anything else not special are always 0
+    pub fn get(&self, shell_type: ShellType) -> usize {
+        match shell_type {
+            ShellType::Slug => self.slug,
+            ShellType::Incendiary => self.incendiary,
+            ShellType::Dud => self.dud,
+            ShellType::Live | ShellType::Blank => 0,
+        }
+    }
+
+    pub(crate) fn add(&mut self, shell_type: ShellType) {
+        match shell_type {
+            ShellType::Slug => self.slug += 1,
+            ShellType::Incendiary => self.incendiary += 1,
+            ShellType::Dud => self.dud += 1,
+            ShellType::Live | ShellType::Blank => {}
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.slug + self.incendiary + self.dud
+    }
+}
+
 impl Shell {
     pub fn new(shell_type: ShellType) -> Self {
         Shell(shell_type)
     }
 
     pub fn invert(&mut self) {
-        match self.0 {
-            ShellType::Live => self.0 = ShellType::Blank,
-            ShellType::Blank => self.0 = ShellType::Live,
-        }
+        self.0 = self.0.inverted();
     }
 
+    /// True if the shell goes off
     pub fn fire(self) -> bool {
-        self.0 == ShellType::Live
+        matches!(
+            self.0,
+            ShellType::Live | ShellType::Slug | ShellType::Incendiary
+        )
     }
 
     pub fn shell_type(&self) -> ShellType {
         self.0
     }
+
+    pub fn visible_type(&self) -> ShellType {
+        self.0.visible_type()
+    }
 }
@@ -0,0 +1,201 @@
+use crate::{
+    action::Action,
+    decision::ActionOutcome,
+    item::UnaryItem,
+    loadout::{Loadout, MAX_SHELLS},
+    player_number::PlayerNumber,
+    round::ShotResult,
+    shell::{ShellType, ShotgunDamage, SpecialShells},
+    turn::ItemUseResult,
+};
+
+const MAX_LOADOUT_SHELLS: usize = MAX_SHELLS as usize;
+
+/// Tracks what each player knows about the shells of the current loadout from publicly observable actions
+#[derive(Debug, Clone)]
+pub struct ShellKnowledge {
+    initial_live_rounds: usize,
+    initial_blank_rounds: usize,
+    spent_live_rounds: usize,
+    spent_blank_rounds: usize,
+    initial_special_rounds: SpecialShells,
+    /// Racked duds look live and are counted as spent live rounds
+    spent_special_rounds: SpecialShells,
+    inversions: usize,
+    /// Indexed by seat, then by absolute position in the loadout
+    known_shells: [[Option<ShellType>; MAX_LOADOUT_SHELLS]; 4],
+}
+
+impl ShellKnowledge {
+    pub fn new(loadout: &Loadout) -> Self {
+        ShellKnowledge {
+            initial_live_rounds: loadout.initial_live_rounds,
+            initial_blank_rounds: loadout.initial_blank_rounds,
+            spent_live_rounds: 0,
+            spent_blank_rounds: 0,
+            initial_special_rounds: loadout.special_rounds,
+            spent_special_rounds: Default::default(),
+            inversions: 0,
+            known_shells: [[None; MAX_LOADOUT_SHELLS]; 4],
+        }
+    }
+
+    pub fn reset(&mut self, loadout: &Loadout) {
+        *self = ShellKnowledge::new(loadout);
+    }
+
+    pub fn initial_live_rounds(&self) -> usize {
+        self.initial_live_rounds
+    }
+
+    pub fn initial_blank_rounds(&self) -> usize {
+        self.initial_blank_rounds
+    }
+
+    pub fn spent_live_rounds(&self) -> usize {
+        self.spent_live_rounds
+    }
+
+    pub fn spent_blank_rounds(&self) -> usize {
+        self.spent_blank_rounds
+    }
+
+    pub fn initial_special_rounds(&self) -> SpecialShells {
+        self.initial_special_rounds
+    }
+
+    pub fn spent_special_rounds(&self) -> SpecialShells {
+        self.spent_special_rounds
+    }
+
+    /// Most shells of the type that can be left, ignoring inversions. Duds and live shells can't be told apart until fired
+    pub fn remaining_rounds(&self, shell_type: ShellType) -> usize {
+        let initial_duds = self.initial_special_rounds.dud;
+        let spent_duds = self.spent_special_rounds.dud;
+        let live_looking_left = (self.initial_live_rounds + initial_duds)
+            .saturating_sub(self.spent_live_rounds + spent_duds);
+
+        match shell_type {
+            ShellType::Live => self.initial_live_rounds.min(live_looking_left),
+            ShellType::Dud => initial_duds
+                .saturating_sub(spent_duds)
+                .min(live_looking_left),
+            ShellType::Blank => self
+                .initial_blank_rounds
+                .saturating_sub(self.spent_blank_rounds),
+            ShellType::Slug | ShellType::Incendiary => self
+                .initial_special_rounds
+                .get(shell_type)
+                .saturating_sub(self.spent_special_rounds.get(shell_type)),
+        }
+    }
+
+    /// Number of Inverter uses this loadout, each one makes the remaining live/blank count uncertain for players who don't know the shell
+    pub fn inversions(&self) -> usize {
+        self.inversions
+    }
+
+    fn spent_shells(&self) -> usize {
+        self.spent_live_rounds + self.spent_blank_rounds + self.spent_special_rounds.total()
+    }
+
+    /// The type of the shell relative_index shells away from the chamber, if the player knows it
+    pub fn known_shell(&self, player: PlayerNumber, relative_index: usize) -> Option<ShellType> {
+        let absolute_index = self.spent_shells() + relative_index;
+        if absolute_index >= MAX_LOADOUT_SHELLS {
+            return None;
+        }
+
+        self.known_shells[player.seat_index()][absolute_index]
+    }
+
+    /// Updates the knowledge after a submitted action. The loadout must be the current one of the session afterwards
+    pub fn record_outcome(&mut self, outcome: &ActionOutcome, loadout: Option<&Loadout>) {
+        if outcome.new_loadout() {
+            if let Some(loadout) = loadout {
+                self.reset(loadout);
+            }
+
+            return;
+        }
+
+        self.record_action(outcome.actor, &outcome.action, outcome.item_result.as_ref());
+        if let Some(shot_result) = &outcome.shot_result {
+            self.record_shot(shot_result);
+        }
+    }
+
+    pub fn record_action(
+        &mut self,
+        actor: PlayerNumber,
+        action: &Action,
+        item_result: Option<&ItemUseResult>,
+    ) {
+        let unary_item = match action {
+            Action::UseUnaryItem(unary_item) | Action::UseAdreneline(_, unary_item) => *unary_item,
+            Action::Shoot(_)
+            | Action::UseJammer(_)
+            | Action::UseAdrenelineThenJammer(_, _)
+            | Action::UseHandcuffs(_)
+            | Action::UseAdrenelineThenHandcuffs(_, _) => return,
+        };
+
+        if let Some(item_result) = item_result {
+            self.record_item_use(actor, unary_item, item_result);
+        }
+    }
+
+    pub fn record_item_use(
+        &mut self,
+        actor: PlayerNumber,
+        unary_item: UnaryItem,
+        item_result: &ItemUseResult,
+    ) {
+        let spent_shells = self.spent_shells();
+        match item_result {
+            ItemUseResult::LearnedShell(learned_shell) => {
+                let absolute_index = spent_shells + learned_shell.relative_index;
+                if absolute_index < MAX_LOADOUT_SHELLS {
+                    self.known_shells[actor.seat_index()][absolute_index] =
+                        Some(learned_shell.shell_type);
+                }
+            }
+            ItemUseResult::ShotgunRacked(rack_result) => self.spend(rack_result.ejected_shell_type),
+            ItemUseResult::Default
+            | ItemUseResult::StunnedPlayer(_)
+            | ItemUseResult::Healed(_)
+            | ItemUseResult::HandcuffedPlayer(_) => {
+                if unary_item == UnaryItem::Inverter && spent_shells < MAX_LOADOUT_SHELLS {
+                    // anyone who knew the shell knows what it is now
+                    self.inversions += 1;
+                    for known_shells in &mut self.known_shells {
+                        known_shells[spent_shells] =
+                            known_shells[spent_shells].map(ShellType::inverted);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn record_shot(&mut self, shot_result: &ShotResult) {
+        match shot_result.damage {
+            ShotgunDamage::Blank => self.spend(ShellType::Blank),
+            ShotgunDamage::RegularShot(_) | ShotgunDamage::SawedShot(_) => {
+                self.spend(ShellType::Live)
+            }
+            ShotgunDamage::Slug(_) => self.spend(ShellType::Slug),
+            ShotgunDamage::Incendiary => self.spend(ShellType::Incendiary),
+            ShotgunDamage::Jammed => self.spend(ShellType::Dud),
+        }
+    }
+
+    fn spend(&mut self, shell_type: ShellType) {
+        match shell_type {
+            ShellType::Live => self.spent_live_rounds += 1,
+            ShellType::Blank => self.spent_blank_rounds += 1,
+            ShellType::Slug | ShellType::Incendiary | ShellType::Dud => {
+                self.spent_special_rounds.add(shell_type)
+            }
+        }
+    }
+}
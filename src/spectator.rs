@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+
+use rand::Rng;
+
+use crate::{
+    game_session::GameSession,
+    loadout::{DealtItem, Loadout},
+    modifier::GameModifiers,
+    player_number::PlayerNumber,
+    round_number::RoundNumber,
+    seat::SeatView,
+    shell::ShellType,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpectatorPolicy {
+    /// Full information, including the order of the loaded shells
+    Caster,
+    /// Only what every player at the table knows
+    Public,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpectatedSeat {
+    pub view: SeatView,
+    pub health: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpectatorFrame {
+    /// GameSession::turns_taken() when the frame was captured
+    pub turn_index: usize,
+    pub round_number: Option<RoundNumber>,
+    pub next_player: Option<PlayerNumber>,
+    pub shell_count: usize,
+    pub loadout: Option<Loadout>,
+    pub modifiers: GameModifiers,
+    pub seats: Vec<SpectatedSeat>,
+    /// Remaining shells in firing order, only present for casters
+    pub shells: Option<Vec<ShellType>>,
+    /// Items drawn for the current loadout and who got them, only present for casters
+    pub dealt_items: Option<Vec<DealtItem>>,
+}
+
+/// Captures frames of a GameSession and releases them to spectators a number of turns later
+#[derive(Debug, Clone)]
+pub struct SpectatorFeed {
+    policy: SpectatorPolicy,
+    delay_turns: usize,
+    pending_frames: VecDeque<SpectatorFrame>,
+    released_frame: Option<SpectatorFrame>,
+}
+
+impl SpectatorFrame {
+    pub fn new<TRng>(session: &GameSession<TRng>, policy: SpectatorPolicy) -> Self
+    where
+        TRng: Rng,
+    {
+        let turn_index = session.turns_taken();
+        match session.round() {
+            Some(round) => SpectatorFrame {
+                turn_index,
+                round_number: Some(round.number()),
+                next_player: Some(round.next_player()),
+                shell_count: round.shells().len(),
+                loadout: Some(round.loadout().clone()),
+                modifiers: round.game_modifiers().clone(),
+                seats: round
+                    .seats()
+                    .iter()
+                    .map(|seat| SpectatedSeat {
+                        view: seat.create_view(round.game_modifiers()),
+                        health: seat.player().map(|player| player.health()),
+                    })
+                    .collect(),
+                shells: match policy {
+                    SpectatorPolicy::Caster => Some(
+                        round
+                            .shells()
+                            .iter()
+                            .map(|shell| shell.shell_type())
+                            .collect(),
+                    ),
+                    SpectatorPolicy::Public => None,
+                },
+                dealt_items: match policy {
+                    SpectatorPolicy::Caster => Some(round.dealt_items().clone()),
+                    SpectatorPolicy::Public => None,
+                },
+            },
+            None => SpectatorFrame {
+                turn_index,
+                round_number: None,
+                next_player: None,
+                shell_count: 0,
+                loadout: None,
+                modifiers: Default::default(),
+                seats: Vec::new(),
+                shells: None,
+                dealt_items: None,
+            },
+        }
+    }
+}
+
+impl SpectatorFeed {
+    pub fn new(policy: SpectatorPolicy, delay_turns: usize) -> Self {
+        SpectatorFeed {
+            policy,
+            delay_turns,
+            pending_frames: VecDeque::new(),
+            released_frame: None,
+        }
+    }
+
+    pub fn policy(&self) -> SpectatorPolicy {
+        self.policy
+    }
+
+    pub fn delay_turns(&self) -> usize {
+        self.delay_turns
+    }
+
+    /// Captures the current state of the session. Should be called after every change to it
+    pub fn observe<TRng>(&mut self, session: &GameSession<TRng>) -> Vec<SpectatorFrame>
+    where
+        TRng: Rng,
+    {
+        self.pending_frames
+            .push_back(SpectatorFrame::new(session, self.policy));
+
+        let current_turn = session.turns_taken();
+        let mut released_frames = Vec::new();
+        while let Some(frame) = self.pending_frames.front() {
+            if frame.turn_index + self.delay_turns > current_turn {
+                break;
+            }
+
+            released_frames.push(self.pending_frames.pop_front().unwrap());
+        }
+
+        if let Some(frame) = released_frames.last() {
+            self.released_frame = Some(frame.clone());
+        }
+
+        released_frames
+    }
+
+    /// Releases every captured frame, for once the game is over
+    pub fn flush(&mut self) -> Vec<SpectatorFrame> {
+        let released_frames: Vec<SpectatorFrame> = self.pending_frames.drain(..).collect();
+        if let Some(frame) = released_frames.last() {
+            self.released_frame = Some(frame.clone());
+        }
+
+        released_frames
+    }
+
+    /// The most recent frame spectators are allowed to see
+    pub fn latest(&self) -> Option<&SpectatorFrame> {
+        self.released_frame.as_ref()
+    }
+}
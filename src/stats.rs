@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+
+use crate::{
+    action::Action,
+    history::{HistoryEntry, HistoryEvent, MatchHistory},
+    item::{Item, NotAdreneline},
+    player_number::PlayerNumber,
+    round_number::RoundNumber,
+    shell::{ShellType, ShotgunDamage},
+    turn::ItemUseResult,
+};
+
+/// Slugs count as regular damage
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DamageStats {
+    pub regular: u64,
+    pub sawn: u64,
+    pub burn: u64,
+}
+
+/// Slugs and incendiaries count as live
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShotStats {
+    pub live: u64,
+    pub blank: u64,
+    pub dud: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerStats {
+    pub shots_at_self: ShotStats,
+    pub shots_at_others: ShotStats,
+    /// Only damage to other players
+    pub damage_dealt: DamageStats,
+    pub damage_taken: DamageStats,
+    pub items_drawn: HashMap<Item, u64>,
+    /// Includes Adreneline and the items stolen with it
+    pub items_used: HashMap<Item, u64>,
+    /// Items taken with Adreneline
+    pub items_stolen: HashMap<Item, u64>,
+    /// Items taken with Adreneline, by the player they were taken from
+    pub stolen_from: HashMap<PlayerNumber, u64>,
+    pub stuns_applied: u64,
+    pub stuns_received: u64,
+    /// Burn deaths count for whoever fired the incendiary shell
+    pub kills: u64,
+    pub deaths: u64,
+    /// Forfeits count as dying first if nobody died before
+    pub first_deaths: u64,
+    pub rounds_won: u64,
+    /// Summed over every round won, divide by rounds_won for the average
+    pub health_at_win: u64,
+}
+
+/// Accumulates PlayerStats from match histories, per round number and per match
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsCollector {
+    players: [PlayerStats; 4],
+    rounds: [[PlayerStats; 4]; 3],
+    matches: u64,
+    observed_entries: usize,
+    /// Who set each seat on fire this round
+    igniters: [Option<PlayerNumber>; 4],
+}
+
+impl DamageStats {
+    pub fn total(&self) -> u64 {
+        self.regular + self.sawn + self.burn
+    }
+
+    fn merge(&mut self, other: &DamageStats) {
+        self.regular += other.regular;
+        self.sawn += other.sawn;
+        self.burn += other.burn;
+    }
+}
+
+impl ShotStats {
+    pub fn total(&self) -> u64 {
+        self.live + self.blank + self.dud
+    }
+
+    fn merge(&mut self, other: &ShotStats) {
+        self.live += other.live;
+        self.blank += other.blank;
+        self.dud += other.dud;
+    }
+}
+
+impl PlayerStats {
+    pub fn shots(&self) -> u64 {
+        self.shots_at_self.total() + self.shots_at_others.total()
+    }
+
+    pub fn merge(&mut self, other: &PlayerStats) {
+        self.shots_at_self.merge(&other.shots_at_self);
+        self.shots_at_others.merge(&other.shots_at_others);
+        self.damage_dealt.merge(&other.damage_dealt);
+        self.damage_taken.merge(&other.damage_taken);
+        merge_counts(&mut self.items_drawn, &other.items_drawn);
+        merge_counts(&mut self.items_used, &other.items_used);
+        merge_counts(&mut self.items_stolen, &other.items_stolen);
+        merge_counts(&mut self.stolen_from, &other.stolen_from);
+        self.stuns_applied += other.stuns_applied;
+        self.stuns_received += other.stuns_received;
+        self.kills += other.kills;
+        self.deaths += other.deaths;
+        self.first_deaths += other.first_deaths;
+        self.rounds_won += other.rounds_won;
+        self.health_at_win += other.health_at_win;
+    }
+}
+
+impl StatsCollector {
+    /// Totals over every observed match
+    pub fn player(&self, player_number: PlayerNumber) -> &PlayerStats {
+        &self.players[player_number.seat_index()]
+    }
+
+    /// Totals over every observed round with the number
+    pub fn round(&self, round_number: RoundNumber, player_number: PlayerNumber) -> &PlayerStats {
+        &self.rounds[round_index(round_number)][player_number.seat_index()]
+    }
+
+    /// Matches ended with end_match
+    pub fn matches(&self) -> u64 {
+        self.matches
+    }
+
+    /// Consumes the entries added since the last call. Can be called after every action or once the match is over
+    pub fn observe(&mut self, history: &MatchHistory) {
+        let entries = history.entries();
+        for entry in &entries[self.observed_entries.min(entries.len())..] {
+            self.observe_entry(entry);
+        }
+        self.observed_entries = entries.len();
+    }
+
+    /// The next history observed belongs to a new match
+    pub fn end_match(&mut self) {
+        self.matches += 1;
+        self.observed_entries = 0;
+        self.igniters = [None; 4];
+    }
+
+    /// Adds the stats of another collector, such as one from a different simulator thread
+    pub fn merge(&mut self, other: &StatsCollector) {
+        for (stats, other_stats) in self.players.iter_mut().zip(&other.players) {
+            stats.merge(other_stats);
+        }
+        for (round, other_round) in self.rounds.iter_mut().zip(&other.rounds) {
+            for (stats, other_stats) in round.iter_mut().zip(other_round) {
+                stats.merge(other_stats);
+            }
+        }
+        self.matches += other.matches;
+    }
+
+    fn observe_entry(&mut self, entry: &HistoryEntry) {
+        let round_index = round_index(entry.round_number);
+        let mut update = |player_number: PlayerNumber, update: &dyn Fn(&mut PlayerStats)| {
+            update(&mut self.players[player_number.seat_index()]);
+            update(&mut self.rounds[round_index][player_number.seat_index()]);
+        };
+
+        match &entry.event {
+            HistoryEvent::Loadout { dealt_items, .. } => {
+                for dealt_item in dealt_items {
+                    update(dealt_item.player_number, &|stats| {
+                        *stats.items_drawn.entry(dealt_item.item).or_default() += 1
+                    });
+                }
+            }
+            HistoryEvent::ItemUsed {
+                player,
+                action,
+                result,
+            } => {
+                let (item, stolen) = used_items(*action);
+                update(*player, &|stats| {
+                    *stats.items_used.entry(item).or_default() += 1;
+                    if let Some((victim, stolen_item)) = stolen {
+                        *stats.items_used.entry(stolen_item).or_default() += 1;
+                        *stats.items_stolen.entry(stolen_item).or_default() += 1;
+                        *stats.stolen_from.entry(victim).or_default() += 1;
+                    }
+                });
+
+                if let ItemUseResult::StunnedPlayer(target) = result {
+                    update(*player, &|stats| stats.stuns_applied += 1);
+                    update(*target, &|stats| stats.stuns_received += 1);
+                }
+            }
+            HistoryEvent::Shot {
+                shooter,
+                shell_type,
+                result,
+            } => {
+                let target = result.target_player;
+                update(*shooter, &|stats| {
+                    let shots = if target == *shooter {
+                        &mut stats.shots_at_self
+                    } else {
+                        &mut stats.shots_at_others
+                    };
+                    match shell_type {
+                        ShellType::Blank => shots.blank += 1,
+                        ShellType::Dud => shots.dud += 1,
+                        ShellType::Live | ShellType::Slug | ShellType::Incendiary => {
+                            shots.live += 1
+                        }
+                    }
+                });
+
+                let damage = u64::from(result.health_lost);
+                let (sawn, killed) = match result.damage {
+                    ShotgunDamage::RegularShot(killed) | ShotgunDamage::Slug(killed) => {
+                        (false, killed)
+                    }
+                    ShotgunDamage::SawedShot(killed) => (true, killed),
+                    ShotgunDamage::Incendiary => {
+                        self.igniters[target.seat_index()] = Some(*shooter);
+                        (false, false)
+                    }
+                    ShotgunDamage::Blank | ShotgunDamage::Jammed => (false, false),
+                };
+                let add_damage = |damage_stats: &mut DamageStats| {
+                    if sawn {
+                        damage_stats.sawn += damage;
+                    } else {
+                        damage_stats.regular += damage;
+                    }
+                };
+                update(target, &|stats| add_damage(&mut stats.damage_taken));
+                if target != *shooter {
+                    update(*shooter, &|stats| add_damage(&mut stats.damage_dealt));
+                }
+
+                if killed {
+                    update(target, &|stats| stats.deaths += 1);
+                    if target != *shooter {
+                        update(*shooter, &|stats| stats.kills += 1);
+                    }
+                }
+            }
+            HistoryEvent::Burned(burn) => {
+                let player = burn.player;
+                let damage = u64::from(burn.damage);
+                update(player, &|stats| stats.damage_taken.burn += damage);
+                let igniter =
+                    self.igniters[player.seat_index()].filter(|igniter| *igniter != player);
+                if let Some(igniter) = igniter {
+                    update(igniter, &|stats| stats.damage_dealt.burn += damage);
+                }
+
+                if burn.killed {
+                    update(player, &|stats| stats.deaths += 1);
+                    if let Some(igniter) = igniter {
+                        update(igniter, &|stats| stats.kills += 1);
+                    }
+                }
+            }
+            HistoryEvent::Forfeited { .. } => {}
+            HistoryEvent::RoundEnded {
+                winner,
+                first_dead_player,
+                health,
+            } => {
+                let health = u64::try_from(*health).unwrap_or(0);
+                update(*winner, &|stats| {
+                    stats.rounds_won += 1;
+                    stats.health_at_win += health;
+                });
+                update(*first_dead_player, &|stats| stats.first_deaths += 1);
+                self.igniters = [None; 4];
+            }
+        }
+    }
+}
+
+fn round_index(round_number: RoundNumber) -> usize {
+    round_number as usize - 1
+}
+
+/// The item the action used, and the item stolen for it along with who it was taken from
+fn used_items(action: Action) -> (Item, Option<(PlayerNumber, Item)>) {
+    match action {
+        Action::UseUnaryItem(unary_item) => (
+            Item::NotAdreneline(NotAdreneline::UnaryItem(unary_item)),
+            None,
+        ),
+        Action::UseAdreneline(victim, unary_item) => (
+            Item::Adreneline,
+            Some((
+                victim,
+                Item::NotAdreneline(NotAdreneline::UnaryItem(unary_item)),
+            )),
+        ),
+        Action::UseJammer(_) => (Item::NotAdreneline(NotAdreneline::Jammer), None),
+        Action::UseAdrenelineThenJammer(victim, _) => (
+            Item::Adreneline,
+            Some((victim, Item::NotAdreneline(NotAdreneline::Jammer))),
+        ),
+        Action::UseHandcuffs(_) => (Item::NotAdreneline(NotAdreneline::Handcuffs), None),
+        Action::UseAdrenelineThenHandcuffs(victim, _) => (
+            Item::Adreneline,
+            Some((victim, Item::NotAdreneline(NotAdreneline::Handcuffs))),
+        ),
+        Action::Shoot(_) => unreachable!("Shots aren't recorded as item uses"),
+    }
+}
+
+fn merge_counts<K>(counts: &mut HashMap<K, u64>, other: &HashMap<K, u64>)
+where
+    K: Copy + Eq + std::hash::Hash,
+{
+    for (key, count) in other {
+        *counts.entry(*key).or_default() += count;
+    }
+}
@@ -1,21 +1,31 @@
 use std::{collections::VecDeque, ops::IndexMut};
 
-use rand::Rng;
 use thiserror::Error;
 
 use crate::{
+    action::Action,
+    chance::ChanceProvider,
     item::{Item, NotAdreneline, UnaryItem},
+    item_registry::{ItemContext, ItemRegistry},
+    modifier::GameModifiers,
     player_number::PlayerNumber,
-    round_player::{RoundPlayer, StunState},
+    round_player::{HandcuffState, RoundPlayer, StunState},
+    round_rules::RoundRules,
     seat::{OccupiedSeat, SeatView},
     shell::{Shell, ShellType},
-    LOG_RNG,
 };
 
 #[derive(Debug)]
 struct TurnOwnedData<'turn, TRng> {
     shells: &'turn mut VecDeque<Shell>,
     modifiers: GameModifiers,
+    rules: RoundRules,
+    item_registry: &'turn ItemRegistry,
+    /// Applied to the round's players when the action ends
+    handcuffed_players: Vec<PlayerNumber>,
+    stunned_players: Vec<PlayerNumber>,
+    /// Items taken off other tables with adreneline, removed from their seats when the action ends
+    stolen_items: Vec<(PlayerNumber, Item)>,
     occupied_seat: OccupiedSeat<'turn>,
     rng: &'turn mut TRng,
 }
@@ -23,6 +33,8 @@ struct TurnOwnedData<'turn, TRng> {
 #[derive(Debug)]
 struct InnerTurn<'turn, TRng> {
     other_seats: Vec<SeatView>,
+    /// Successful item uses so far this turn, in order
+    item_uses: Vec<(Action, ItemUseResult)>,
     owned_data: TurnOwnedData<'turn, TRng>,
 }
 
@@ -37,21 +49,23 @@ pub enum TerminalAction {
     Shot(PlayerNumber),
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct GameModifiers {
-    pub shotgun_sawn: bool,
-    pub turn_order_inverted: bool,
-}
-
 #[derive(Debug, Clone)]
 pub struct TakenTurn {
     pub action: TerminalAction,
+    /// What the player did to end the turn
+    pub player_action: Action,
     pub modifiers: GameModifiers,
+    pub handcuffed_players: Vec<PlayerNumber>,
+    pub stunned_players: Vec<PlayerNumber>,
+    pub stolen_items: Vec<(PlayerNumber, Item)>,
+    /// Every successful item use of the turn in order, including a terminal one
+    pub item_uses: Vec<(Action, ItemUseResult)>,
 }
 
 #[derive(Debug)]
 pub struct ContinuedTurn<'turn, TRng> {
     inner_turn: InnerTurn<'turn, TRng>,
+    player_action: Action,
     item_result: Result<ItemUseResult, InvalidItemUseError>,
 }
 
@@ -62,26 +76,40 @@ pub enum TakenAction<'turn, TRng> {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LearnedShell {
     pub relative_index: usize,
     pub shell_type: ShellType,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShotgunRackResult {
     pub empty: bool,
     pub ejected_shell_type: ShellType,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HealResult {
+    pub requested: u8,
+    /// Less than requested when capped by the round's healing rule
+    pub healed: u8,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ItemUseResult {
     Default,
     ShotgunRacked(ShotgunRackResult),
     LearnedShell(LearnedShell),
     StunnedPlayer(PlayerNumber),
+    Healed(HealResult),
+    HandcuffedPlayer(PlayerNumber),
 }
 
-#[derive(Debug, Clone, Copy, Error)]
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InvalidItemUseError {
     #[error("The item is not present in the player's inventory")]
     NoItem,
@@ -93,6 +121,14 @@ pub enum InvalidItemUseError {
     DoubleStun,
     #[error("Player targeted by stun is dead, non-existent, or self")]
     InvalidStunTarget,
+    #[error("Player's wires are cut and they can't heal")]
+    WiresCut,
+    #[error("Player is already handcuffed or the broken cuffs haven't come off yet")]
+    DoubleHandcuff,
+    #[error("Player targeted by handcuffs is dead, non-existent, or self")]
+    InvalidHandcuffTarget,
+    #[error("The item's script failed or ran out of instructions")]
+    ScriptFailed,
 }
 
 impl<'turn, TRng> ContinuedTurn<'turn, TRng> {
@@ -100,6 +136,10 @@ impl<'turn, TRng> ContinuedTurn<'turn, TRng> {
         &self.item_result
     }
 
+    pub fn player_action(&self) -> Action {
+        self.player_action
+    }
+
     pub fn next_action(self) -> Turn<'turn, TRng> {
         Turn {
             inner_turn: self.inner_turn,
@@ -109,6 +149,26 @@ impl<'turn, TRng> ContinuedTurn<'turn, TRng> {
     pub fn modifiers(&self) -> &GameModifiers {
         &self.inner_turn.owned_data.modifiers
     }
+
+    /// Players handcuffed so far this turn
+    pub fn handcuffed_players(&self) -> &Vec<PlayerNumber> {
+        &self.inner_turn.owned_data.handcuffed_players
+    }
+
+    /// Successful item uses so far this turn in order, including this one
+    pub fn item_uses(&self) -> &Vec<(Action, ItemUseResult)> {
+        &self.inner_turn.item_uses
+    }
+
+    /// Players stunned so far this turn
+    pub fn stunned_players(&self) -> &Vec<PlayerNumber> {
+        &self.inner_turn.owned_data.stunned_players
+    }
+
+    /// Items stolen so far this turn and who they were stolen from
+    pub fn stolen_items(&self) -> &Vec<(PlayerNumber, Item)> {
+        &self.inner_turn.owned_data.stolen_items
+    }
 }
 
 impl ItemUseResult {
@@ -117,14 +177,16 @@ impl ItemUseResult {
             ItemUseResult::ShotgunRacked(rack_result) => rack_result.empty,
             ItemUseResult::Default
             | ItemUseResult::LearnedShell(_)
-            | ItemUseResult::StunnedPlayer(_) => false,
+            | ItemUseResult::StunnedPlayer(_)
+            | ItemUseResult::Healed(_)
+            | ItemUseResult::HandcuffedPlayer(_) => false,
         }
     }
 }
 
 impl<'turn, TRng> Turn<'turn, TRng>
 where
-    TRng: Rng,
+    TRng: ChanceProvider,
 {
     pub fn new(
         occupied_seat: OccupiedSeat<'turn>,
@@ -132,6 +194,8 @@ where
         shells: &'turn mut VecDeque<Shell>,
         rng: &'turn mut TRng,
         modifiers: GameModifiers,
+        rules: RoundRules,
+        item_registry: &'turn ItemRegistry,
     ) -> Turn<'turn, TRng> {
         Turn {
             inner_turn: InnerTurn {
@@ -139,9 +203,15 @@ where
                     occupied_seat,
                     shells,
                     modifiers,
+                    rules,
+                    item_registry,
+                    handcuffed_players: Vec::new(),
+                    stunned_players: Vec::new(),
+                    stolen_items: Vec::new(),
                     rng,
                 },
                 other_seats,
+                item_uses: Vec::new(),
             },
         }
     }
@@ -169,16 +239,21 @@ where
     pub fn shoot(self, target: PlayerNumber) -> TakenAction<'turn, TRng> {
         TakenAction::Terminal(TakenTurn {
             action: TerminalAction::Shot(target),
+            player_action: Action::Shoot(target),
             modifiers: self.inner_turn.owned_data.modifiers,
+            handcuffed_players: self.inner_turn.owned_data.handcuffed_players,
+            stunned_players: self.inner_turn.owned_data.stunned_players,
+            stolen_items: self.inner_turn.owned_data.stolen_items,
+            item_uses: self.inner_turn.item_uses,
         })
     }
 
     pub fn use_unary_item(mut self, unary_item: UnaryItem) -> TakenAction<'turn, TRng>
     where
-        TRng: Rng,
+        TRng: ChanceProvider,
     {
         let result = self.inner_turn.use_unary_item(unary_item);
-        self.convert_to_taken_action(result)
+        self.convert_to_taken_action(Action::UseUnaryItem(unary_item), result)
     }
 
     pub fn use_adreneline(
@@ -187,15 +262,15 @@ where
         target_item: UnaryItem,
     ) -> TakenAction<'turn, TRng>
     where
-        TRng: Rng,
+        TRng: ChanceProvider,
     {
         let result = self.inner_turn.use_adreneline(target_player, target_item);
-        self.convert_to_taken_action(result)
+        self.convert_to_taken_action(Action::UseAdreneline(target_player, target_item), result)
     }
 
     pub fn use_jammer(mut self, target_player: PlayerNumber) -> TakenAction<'turn, TRng> {
         let result = self.inner_turn.use_jammer(target_player);
-        self.convert_to_taken_action(result)
+        self.convert_to_taken_action(Action::UseJammer(target_player), result)
     }
 
     pub fn use_adreneline_then_jammer(
@@ -206,18 +281,50 @@ where
         let result = self
             .inner_turn
             .use_adreneline_then_jammer(theive_from, jam_target);
-        self.convert_to_taken_action(result)
+        self.convert_to_taken_action(
+            Action::UseAdrenelineThenJammer(theive_from, jam_target),
+            result,
+        )
+    }
+
+    pub fn use_handcuffs(mut self, target_player: PlayerNumber) -> TakenAction<'turn, TRng> {
+        let result = self.inner_turn.use_handcuffs(target_player);
+        self.convert_to_taken_action(Action::UseHandcuffs(target_player), result)
+    }
+
+    pub fn use_adreneline_then_handcuffs(
+        mut self,
+        theive_from: PlayerNumber,
+        cuff_target: PlayerNumber,
+    ) -> TakenAction<'turn, TRng> {
+        let result = self
+            .inner_turn
+            .use_adreneline_then_handcuffs(theive_from, cuff_target);
+        self.convert_to_taken_action(
+            Action::UseAdrenelineThenHandcuffs(theive_from, cuff_target),
+            result,
+        )
     }
 
     fn convert_to_taken_action(
-        self,
+        mut self,
+        player_action: Action,
         mut item_result: Result<ItemUseResult, InvalidItemUseError>,
     ) -> TakenAction<'turn, TRng> {
         if let Ok(item_use_result) = item_result {
+            self.inner_turn
+                .item_uses
+                .push((player_action, item_use_result.clone()));
+
             if item_use_result.is_terminal() {
                 return TakenAction::Terminal(TakenTurn {
                     action: TerminalAction::Item(item_use_result),
+                    player_action,
                     modifiers: self.inner_turn.owned_data.modifiers,
+                    handcuffed_players: self.inner_turn.owned_data.handcuffed_players,
+                    stunned_players: self.inner_turn.owned_data.stunned_players,
+                    stolen_items: self.inner_turn.owned_data.stolen_items,
+                    item_uses: self.inner_turn.item_uses,
                 });
             }
 
@@ -226,6 +333,7 @@ where
 
         TakenAction::Continued(ContinuedTurn {
             item_result,
+            player_action,
             inner_turn: self.inner_turn,
         })
     }
@@ -233,7 +341,7 @@ where
 
 impl<'turn, TRng> InnerTurn<'turn, TRng>
 where
-    TRng: Rng,
+    TRng: ChanceProvider,
 {
     fn use_unary_item(
         &mut self,
@@ -248,12 +356,12 @@ where
         target_item: UnaryItem,
     ) -> Result<ItemUseResult, InvalidItemUseError>
     where
-        TRng: Rng,
+        TRng: ChanceProvider,
     {
         self.with_adreneline(
             target_player,
             NotAdreneline::UnaryItem(target_item),
-            |seat, owned_data| owned_data.use_unary_item(Some(&mut seat.items), target_item),
+            |seat, owned_data| owned_data.use_unary_item(Some(&seat.items), target_item),
         )
     }
 
@@ -262,7 +370,8 @@ where
         target_player: PlayerNumber,
     ) -> Result<ItemUseResult, InvalidItemUseError> {
         self.check_can_jam(target_player)?;
-        self.with_item(Item::NotAdreneline(NotAdreneline::Jammer), |_| {
+        self.with_item(Item::NotAdreneline(NotAdreneline::Jammer), |inner_self| {
+            inner_self.stun(target_player);
             Ok(ItemUseResult::StunnedPlayer(target_player))
         })
     }
@@ -273,9 +382,64 @@ where
         jam_target: PlayerNumber,
     ) -> Result<ItemUseResult, InvalidItemUseError> {
         self.check_can_jam(jam_target)?;
-        self.with_adreneline(theive_from, NotAdreneline::Jammer, |_, _| {
+        let result = self.with_adreneline(theive_from, NotAdreneline::Jammer, |_, _| {
             Ok(ItemUseResult::StunnedPlayer(jam_target))
-        })
+        });
+
+        if result.is_ok() {
+            self.stun(jam_target);
+        }
+
+        result
+    }
+
+    fn use_handcuffs(
+        &mut self,
+        target_player: PlayerNumber,
+    ) -> Result<ItemUseResult, InvalidItemUseError> {
+        self.check_can_handcuff(target_player)?;
+        self.with_item(
+            Item::NotAdreneline(NotAdreneline::Handcuffs),
+            |inner_self| {
+                inner_self.handcuff(target_player);
+                Ok(ItemUseResult::HandcuffedPlayer(target_player))
+            },
+        )
+    }
+
+    fn use_adreneline_then_handcuffs(
+        &mut self,
+        theive_from: PlayerNumber,
+        cuff_target: PlayerNumber,
+    ) -> Result<ItemUseResult, InvalidItemUseError> {
+        self.check_can_handcuff(cuff_target)?;
+        let result = self.with_adreneline(theive_from, NotAdreneline::Handcuffs, |_, _| {
+            Ok(ItemUseResult::HandcuffedPlayer(cuff_target))
+        });
+
+        if result.is_ok() {
+            self.handcuff(cuff_target);
+        }
+
+        result
+    }
+
+    /// Marks the view so the same player can't be cuffed twice in one turn
+    fn handcuff(&mut self, target_player: PlayerNumber) {
+        if let Some(view) = get_opposing_seat(&mut self.other_seats, target_player) {
+            view.handcuff_state = Some(HandcuffState::Cuffed);
+        }
+
+        self.owned_data.handcuffed_players.push(target_player);
+    }
+
+    /// Marks the view so the same player can't be stunned twice in one turn
+    fn stun(&mut self, target_player: PlayerNumber) {
+        if let Some(view) = get_opposing_seat(&mut self.other_seats, target_player) {
+            view.stun_state = Some(StunState::Stunned);
+        }
+
+        self.owned_data.stunned_players.push(target_player);
     }
 
     fn with_item<F>(&mut self, item: Item, func: F) -> Result<ItemUseResult, InvalidItemUseError>
@@ -291,7 +455,7 @@ where
         let result = func(self);
 
         if result.is_ok() {
-            self.owned_data.occupied_seat.items.remove(index_to_remove);
+            self.owned_data.occupied_seat.remove_item(index_to_remove);
         }
 
         result
@@ -312,18 +476,45 @@ where
         self.with_item(Item::Adreneline, |inner_self| {
             match get_opposing_seat(&mut inner_self.other_seats, target_player) {
                 Some(seat) => {
-                    check_item_in_inventory(
+                    let stolen_index = check_item_in_inventory(
                         &seat.items,
                         Item::NotAdreneline(target_item),
                         InvalidItemUseError::BadAdrenelineTarget,
                     )?;
-                    func(seat, &mut inner_self.owned_data)
+                    let result = func(seat, &mut inner_self.owned_data);
+
+                    if result.is_ok() {
+                        seat.items.remove(stolen_index);
+                        seat.item_slots.remove(stolen_index);
+                        inner_self
+                            .owned_data
+                            .stolen_items
+                            .push((target_player, Item::NotAdreneline(target_item)));
+                    }
+
+                    result
                 }
                 None => Err(InvalidItemUseError::BadAdrenelineTarget),
             }
         })
     }
 
+    fn check_can_handcuff(
+        &mut self,
+        target_player: PlayerNumber,
+    ) -> Result<(), InvalidItemUseError> {
+        match get_opposing_seat(&mut self.other_seats, target_player) {
+            Some(view) => match view.handcuff_state {
+                Some(HandcuffState::Free) => Ok(()),
+                Some(HandcuffState::Cuffed | HandcuffState::Broken) => {
+                    Err(InvalidItemUseError::DoubleHandcuff)
+                }
+                None => Err(InvalidItemUseError::InvalidHandcuffTarget),
+            },
+            None => Err(InvalidItemUseError::InvalidHandcuffTarget),
+        }
+    }
+
     fn check_can_jam(&mut self, target_player: PlayerNumber) -> Result<(), InvalidItemUseError> {
         match get_opposing_seat(&mut self.other_seats, target_player) {
             Some(view) => match view.stun_state {
@@ -342,62 +533,38 @@ where
 
 impl<'turn, TRng> TurnOwnedData<'turn, TRng>
 where
-    TRng: Rng,
+    TRng: ChanceProvider,
 {
     fn use_unary_item(
         &mut self,
-        other_items: Option<&mut Vec<Item>>,
+        other_items: Option<&[Item]>,
         unary_item: UnaryItem,
     ) -> Result<ItemUseResult, InvalidItemUseError> {
-        let items = match other_items {
-            Some(other_items) => other_items,
-            None => &mut self.occupied_seat.items,
-        };
-
         let index_to_remove = check_item_in_inventory(
-            items,
+            other_items.unwrap_or(self.occupied_seat.items),
             Item::NotAdreneline(NotAdreneline::UnaryItem(unary_item)),
             InvalidItemUseError::NoItem,
         )?;
 
-        let mut use_result = None;
-        match unary_item {
-            UnaryItem::Remote => {
-                self.modifiers.turn_order_inverted = !self.modifiers.turn_order_inverted
-            }
-            UnaryItem::Phone => {
-                if self.shells.len() > 2 {
-                    let relative_index = self.rng.gen_range(2, self.shells.len());
-
-                    if LOG_RNG {
-                        println!("Phone revealed shell {}", relative_index);
-                    }
-
-                    use_result = learn_shell(self.shells, relative_index)
-                }
-            }
-            UnaryItem::Inverter => self.shells[0].invert(),
-            UnaryItem::MagnifyingGlass => use_result = learn_shell(self.shells, 0),
-            UnaryItem::Cigarettes => self.occupied_seat.player.gain_health(1),
-            UnaryItem::Handsaw => {
-                if self.modifiers.shotgun_sawn {
-                    return Err(InvalidItemUseError::DoubleSaw);
-                }
-                self.modifiers.shotgun_sawn = true;
-            }
-            UnaryItem::Beer => {
-                let ejected_shell = self.shells.pop_front().unwrap();
-
-                use_result = Some(ItemUseResult::ShotgunRacked(ShotgunRackResult {
-                    empty: self.shells.is_empty(),
-                    ejected_shell_type: ejected_shell.shell_type(),
-                }));
-            }
+        let item_use_result = self
+            .item_registry
+            .behavior(Item::NotAdreneline(NotAdreneline::UnaryItem(unary_item)))
+            .ok_or(InvalidItemUseError::NoItem)?
+            .use_item(ItemContext {
+                shells: self.shells,
+                modifiers: &mut self.modifiers,
+                player: self.occupied_seat.player,
+                items: self.occupied_seat.items,
+                rules: &self.rules,
+                chance: self.rng,
+            })?;
+
+        // stolen items are taken off the other table by with_adreneline
+        if other_items.is_none() {
+            self.occupied_seat.remove_item(index_to_remove);
         }
 
-        items.remove(index_to_remove);
-
-        Ok(use_result.unwrap_or(ItemUseResult::Default))
+        Ok(item_use_result)
     }
 }
 
@@ -414,13 +581,6 @@ fn get_opposing_seat(
     }
 }
 
-fn learn_shell(shells: &VecDeque<Shell>, relative_index: usize) -> Option<ItemUseResult> {
-    Some(ItemUseResult::LearnedShell(LearnedShell {
-        relative_index,
-        shell_type: shells[relative_index].shell_type(),
-    }))
-}
-
 fn check_item_in_inventory(
     items: &[Item],
     target_item: Item,
@@ -0,0 +1,132 @@
+use crate::{
+    compact_round::{CompactRound, MAX_SEATS},
+    item::TOTAL_ITEMS,
+    loadout::MAX_SHELLS,
+    player_number::PlayerNumber,
+    round_number::RoundNumber,
+};
+
+const MAX_ITEM_COUNT: usize = 8;
+const SEAT_STATES: usize = 256;
+const HANDCUFF_STATES: usize = 3;
+
+const LIVE_SHELL_KEYS: usize = 0;
+const SHELL_COUNT_KEYS: usize = LIVE_SHELL_KEYS + MAX_SHELLS as usize;
+const SEAT_KEYS: usize = SHELL_COUNT_KEYS + MAX_SHELLS as usize + 1;
+const ITEM_KEYS: usize = SEAT_KEYS + MAX_SEATS * SEAT_STATES;
+const SHOTGUN_SAWN_KEY: usize = ITEM_KEYS + MAX_SEATS * TOTAL_ITEMS * (MAX_ITEM_COUNT + 1);
+const TURN_ORDER_INVERTED_KEY: usize = SHOTGUN_SAWN_KEY + 1;
+const ACTIVE_SEAT_KEYS: usize = TURN_ORDER_INVERTED_KEY + 1;
+const ROUND_NUMBER_KEYS: usize = ACTIVE_SEAT_KEYS + MAX_SEATS;
+const HANDCUFF_KEYS: usize = ROUND_NUMBER_KEYS + 3;
+const TOTAL_KEYS: usize = HANDCUFF_KEYS + MAX_SEATS * HANDCUFF_STATES;
+
+// generated at compile time from a fixed seed so hashes are the same across runs and builds
+static KEYS: [u64; TOTAL_KEYS] = generate_keys(0x6275636B73686F74);
+
+/// Fixed size, always replace table of values for previously seen states
+#[derive(Debug, Clone)]
+pub struct TranspositionTable<TValue> {
+    entries: Vec<Option<(CompactRound, TValue)>>,
+    mask: u64,
+}
+
+impl<TValue> TranspositionTable<TValue> {
+    /// Table with 2^bits entries
+    pub fn new(bits: u32) -> Self {
+        let size = 1 << bits;
+        let mut entries = Vec::with_capacity(size);
+        entries.resize_with(size, || None);
+        TranspositionTable {
+            entries,
+            mask: size as u64 - 1,
+        }
+    }
+
+    /// Compares the full state, so colliding hashes never return another state's value
+    pub fn get(&self, state: &CompactRound) -> Option<&TValue> {
+        match &self.entries[self.index(state)] {
+            Some((stored_state, value)) if stored_state == state => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Overwrites whatever was stored in the state's slot
+    pub fn insert(&mut self, state: CompactRound, value: TValue) {
+        let index = self.index(&state);
+        self.entries[index] = Some((state, value));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.iter_mut().for_each(|entry| *entry = None);
+    }
+
+    fn index(&self, state: &CompactRound) -> usize {
+        (state.zobrist_hash() & self.mask) as usize
+    }
+}
+
+/// Key for a live shell at the position counted from the first shell of the loadout
+pub(crate) fn live_shell_key(position: usize) -> u64 {
+    KEYS[LIVE_SHELL_KEYS + position]
+}
+
+pub(crate) fn shell_count_key(shell_count: usize) -> u64 {
+    KEYS[SHELL_COUNT_KEYS + shell_count]
+}
+
+pub(crate) fn seat_key(seat_index: usize, packed_seat: u8) -> u64 {
+    KEYS[SEAT_KEYS + seat_index * SEAT_STATES + packed_seat as usize]
+}
+
+/// Zero for no items, so empty tables don't contribute
+pub(crate) fn item_key(seat_index: usize, item_index: usize, count: u8) -> u64 {
+    if count == 0 {
+        return 0;
+    }
+
+    let count = (count as usize).min(MAX_ITEM_COUNT);
+    KEYS[ITEM_KEYS + (seat_index * TOTAL_ITEMS + item_index) * (MAX_ITEM_COUNT + 1) + count]
+}
+
+pub(crate) fn shotgun_sawn_key() -> u64 {
+    KEYS[SHOTGUN_SAWN_KEY]
+}
+
+pub(crate) fn turn_order_inverted_key() -> u64 {
+    KEYS[TURN_ORDER_INVERTED_KEY]
+}
+
+pub(crate) fn active_seat_key(player_number: PlayerNumber) -> u64 {
+    KEYS[ACTIVE_SEAT_KEYS + player_number.seat_index()]
+}
+
+pub(crate) fn round_number_key(round_number: RoundNumber) -> u64 {
+    KEYS[ROUND_NUMBER_KEYS + round_number as usize - 1]
+}
+
+/// Zero for free players, so seats without cuffs don't contribute
+pub(crate) fn handcuff_key(seat_index: usize, handcuff_state: u8) -> u64 {
+    if handcuff_state == 0 {
+        return 0;
+    }
+
+    KEYS[HANDCUFF_KEYS + seat_index * HANDCUFF_STATES + handcuff_state as usize]
+}
+
+const fn generate_keys(seed: u64) -> [u64; TOTAL_KEYS] {
+    let mut keys = [0; TOTAL_KEYS];
+    let mut state = seed;
+    let mut index = 0;
+    while index < TOTAL_KEYS {
+        // splitmix64
+        state = state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        keys[index] = z ^ (z >> 31);
+        index += 1;
+    }
+
+    keys
+}
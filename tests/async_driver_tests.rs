@@ -0,0 +1,150 @@
+use std::{
+    future::{pending, ready, Future},
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake},
+    thread::{self, Thread},
+    time::Duration,
+};
+
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    async_driver::{AsyncAgent, AsyncDriver, BoxFuture, Cancellation, DriveOutcome, Timer},
+    decision::{ActionOutcome, PendingDecision, SubmitError},
+    game_session::GameSession,
+    item::UnaryItem,
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<TFuture: Future>(future: TFuture) -> TFuture::Output {
+    let mut future = pin!(future);
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct SelfShooter {
+    observed: usize,
+    rejections: usize,
+    cancel_after: Option<(usize, Cancellation)>,
+}
+
+impl AsyncAgent for SelfShooter {
+    fn decide(&mut self, decision: PendingDecision) -> BoxFuture<'_, Option<Action>> {
+        if let Some((remaining, cancellation)) = &mut self.cancel_after {
+            if *remaining == 0 {
+                cancellation.cancel();
+                return Box::pin(pending());
+            }
+
+            *remaining -= 1;
+        }
+
+        Box::pin(ready(Some(Action::Shoot(decision.player))))
+    }
+
+    fn observe(&mut self, _outcome: &ActionOutcome) {
+        self.observed += 1;
+    }
+
+    fn rejected(&mut self, _error: SubmitError) {
+        self.rejections += 1;
+    }
+}
+
+struct Stalling;
+
+impl AsyncAgent for Stalling {
+    fn decide(&mut self, _decision: PendingDecision) -> BoxFuture<'_, Option<Action>> {
+        Box::pin(pending())
+    }
+}
+
+struct Broken;
+
+impl AsyncAgent for Broken {
+    fn decide(&mut self, _decision: PendingDecision) -> BoxFuture<'_, Option<Action>> {
+        // nobody can have more than eight items, so this eventually runs out
+        Box::pin(async { Some(Action::UseUnaryItem(UnaryItem::Remote)) })
+    }
+}
+
+/// Times out every decision that doesn't resolve on its first poll
+struct ImmediateTimer;
+
+impl Timer for ImmediateTimer {
+    fn sleep(&mut self, _duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(ready(()))
+    }
+}
+
+#[test]
+fn agents_play_a_full_game() {
+    let mut session: GameSession<StdRng> =
+        GameSession::new(MultiplayerCount::Three, StdRng::seed_from_u64(42));
+    let mut driver = AsyncDriver::new((0..3).map(|_| SelfShooter::default()).collect());
+
+    assert!(block_on(driver.run(&mut session)).unwrap() == DriveOutcome::Finished);
+    assert!(session.round().is_none());
+
+    let observed = driver.agent(PlayerNumber::One).unwrap().observed;
+    assert!(observed == session.turns_taken());
+    assert!(driver.agent(PlayerNumber::Two).unwrap().observed == observed);
+}
+
+#[test]
+fn stalling_and_broken_agents_forfeit() {
+    let mut session: GameSession<StdRng> =
+        GameSession::new(MultiplayerCount::Three, StdRng::seed_from_u64(42));
+    let agents: Vec<Box<dyn AsyncAgent>> = vec![
+        Box::new(SelfShooter::default()),
+        Box::new(Stalling),
+        Box::new(Broken),
+    ];
+    let mut driver = AsyncDriver::new(agents).with_timeout(Duration::from_secs(30), ImmediateTimer);
+
+    assert!(block_on(driver.run(&mut session)).unwrap() == DriveOutcome::Finished);
+
+    let players = session.players();
+    assert!(!players.get(PlayerNumber::One).unwrap().forfeited());
+    assert!(players.get(PlayerNumber::Two).unwrap().forfeited());
+    assert!(players.get(PlayerNumber::Three).unwrap().forfeited());
+}
+
+#[test]
+fn cancellation_stops_the_driver() {
+    let mut session: GameSession<StdRng> =
+        GameSession::new(MultiplayerCount::Two, StdRng::seed_from_u64(42));
+    let cancellation = Cancellation::new();
+    let agents = vec![
+        SelfShooter {
+            cancel_after: Some((0, cancellation.clone())),
+            ..Default::default()
+        },
+        SelfShooter {
+            cancel_after: Some((0, cancellation.clone())),
+            ..Default::default()
+        },
+    ];
+    let mut driver = AsyncDriver::new(agents).with_cancellation(cancellation);
+
+    assert!(block_on(driver.run(&mut session)).unwrap() == DriveOutcome::Cancelled);
+    assert!(session.round().is_some());
+    assert!(session.turns_taken() == 0);
+}
@@ -0,0 +1,181 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    bot_protocol::{
+        host::{BotConnection, BotError, BotHost},
+        BotReply, EngineCommand, ParseLineError, VisibleState,
+    },
+    game_session::GameSession,
+    item::UnaryItem,
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    shell_knowledge::ShellKnowledge,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn lines_round_trip() {
+    let session: GameSession<StdRng> =
+        GameSession::new(MultiplayerCount::Four, StdRng::seed_from_u64(42));
+    let round = session.round().unwrap();
+    let knowledge = ShellKnowledge::new(round.loadout());
+
+    let commands = [
+        EngineCommand::Hello,
+        EngineCommand::NewGame {
+            player_count: 4,
+            player: PlayerNumber::Three,
+        },
+        EngineCommand::State(VisibleState::new(round, &knowledge, round.next_player())),
+        EngineCommand::Go,
+        EngineCommand::Info {
+            actor: PlayerNumber::Two,
+            action: Action::UseAdrenelineThenJammer(PlayerNumber::One, PlayerNumber::Four),
+        },
+        EngineCommand::Illegal("Shotgun is already sawn".to_string()),
+        EngineCommand::Quit,
+    ];
+    for command in commands {
+        let line = command.to_string();
+        assert!(!line.contains('\n'));
+        assert!(line.parse::<EngineCommand>().unwrap() == command);
+    }
+
+    let replies = [
+        BotReply::Id {
+            name: "Dealer 2000".to_string(),
+        },
+        BotReply::Ready,
+        BotReply::Act(Action::Shoot(PlayerNumber::One)),
+        BotReply::Act(Action::UseUnaryItem(UnaryItem::MagnifyingGlass)),
+        BotReply::Act(Action::UseAdreneline(PlayerNumber::Two, UnaryItem::Beer)),
+        BotReply::Act(Action::UseJammer(PlayerNumber::Four)),
+    ];
+    for reply in replies {
+        assert!(reply.to_string().parse::<BotReply>().unwrap() == reply);
+    }
+
+    assert!("action shoot 5".parse::<BotReply>().is_err());
+    assert!("action use jammer".parse::<BotReply>().is_err());
+    assert!(matches!("".parse::<BotReply>(), Err(ParseLineError::Empty)));
+    assert!(matches!(
+        "bestmove e2e4".parse::<BotReply>(),
+        Err(ParseLineError::UnknownCommand(_))
+    ));
+}
+
+#[test]
+fn hosts_need_a_bot_per_seat() {
+    let mut session: GameSession<StdRng> =
+        GameSession::new(MultiplayerCount::Three, StdRng::seed_from_u64(42));
+
+    let connections = (0..2)
+        .map(|_| BotConnection::new(Vec::new(), "".as_bytes()))
+        .collect();
+    let error = BotHost::new(connections).play(&mut session).unwrap_err();
+
+    assert!(matches!(
+        error.downcast_ref::<BotError>(),
+        Some(BotError::SeatCount { bots: 2, seats: 3 })
+    ));
+    assert!(session.round().is_some());
+}
+
+#[cfg(unix)]
+mod subprocess {
+    use std::{process::Command, time::Duration};
+
+    use super::*;
+
+    const SELF_SHOOTER: &str = r#"
+        while read command rest; do
+            case "$command" in
+                bsr) echo "id name self shooter"; echo bsrok ;;
+                newgame) set -- $rest; me=$2 ;;
+                go) echo "action shoot $me" ;;
+                quit) exit 0 ;;
+            esac
+        done
+    "#;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn bot(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+        command
+    }
+
+    #[test]
+    fn bots_play_a_full_game() {
+        let mut session: GameSession<StdRng> =
+            GameSession::new(MultiplayerCount::Three, StdRng::seed_from_u64(42));
+
+        let mut host = BotHost::spawn((0..3).map(|_| bot(SELF_SHOOTER)), TIMEOUT).unwrap();
+        host.play(&mut session).unwrap();
+
+        assert!(session.round().is_none());
+        assert!(host.bot(PlayerNumber::One).unwrap().name() == Some("self shooter"));
+        let total_wins: usize = session
+            .players()
+            .as_vec()
+            .iter()
+            .map(|player| player.wins().len())
+            .sum();
+        assert!(total_wins == 3);
+    }
+
+    #[test]
+    fn misbehaving_bots_forfeit() {
+        let mut session: GameSession<StdRng> =
+            GameSession::new(MultiplayerCount::Three, StdRng::seed_from_u64(42));
+
+        let mut host = BotHost::spawn(
+            [
+                bot(SELF_SHOOTER),
+                bot("exit 0"),
+                bot("while read line; do echo 'action use beer'; done"),
+            ],
+            TIMEOUT,
+        )
+        .unwrap();
+        host.play(&mut session).unwrap();
+
+        assert!(session.round().is_none());
+        assert!(host.bot(PlayerNumber::Two).is_none());
+        assert!(host.bot(PlayerNumber::Three).is_none());
+
+        let players = session.players();
+        assert!(players.get(PlayerNumber::Two).unwrap().forfeited());
+        assert!(players.get(PlayerNumber::Three).unwrap().forfeited());
+        assert!(!players.get(PlayerNumber::One).unwrap().forfeited());
+    }
+
+    #[test]
+    fn silent_bots_forfeit() {
+        let mut session: GameSession<StdRng> =
+            GameSession::new(MultiplayerCount::Three, StdRng::seed_from_u64(42));
+
+        // answers the handshake, then never acts
+        let silent = r#"
+            while read command rest; do
+                case "$command" in
+                    bsr) echo bsrok ;;
+                esac
+            done
+        "#;
+        let mut host = BotHost::spawn(
+            [bot(SELF_SHOOTER), bot(silent), bot(SELF_SHOOTER)],
+            Duration::from_millis(200),
+        )
+        .unwrap();
+        host.play(&mut session).unwrap();
+
+        assert!(session.round().is_none());
+        assert!(host.bot(PlayerNumber::Two).is_none());
+        assert!(session
+            .players()
+            .get(PlayerNumber::Two)
+            .unwrap()
+            .forfeited());
+    }
+}
@@ -0,0 +1,78 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    chance::{ChanceEvent, ChanceProvider, EnumeratingChance},
+    compact_round::CompactRound,
+    game_session::GameSession,
+    godot_rng::GodotRandom,
+    item::{Item, NotAdreneline, UnaryItem},
+    loadout::Loadout,
+    multiplayer_count::MultiplayerCount,
+    turn::ItemUseResult,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn assert_total_probability(outcomes: &[(impl Sized, f64)]) {
+    let total: f64 = outcomes.iter().map(|(_, probability)| probability).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn default_provider_draws_like_godot() {
+    let mut chance_rng = StdRng::seed_from_u64(4);
+    let mut godot_rng = chance_rng.clone();
+    for _ in 0..100 {
+        assert!(
+            chance_rng.choose(ChanceEvent::PhoneShell, 2, 7)
+                == godot_rng.randi_range(2, 6) as usize
+        );
+        assert!(chance_rng.bernoulli(ChanceEvent::ShellType, 0.5) == (godot_rng.randf() < 0.5));
+    }
+}
+
+#[test]
+fn loadouts_enumerate() {
+    let outcomes =
+        EnumeratingChance::enumerate(|chance| Loadout::new(MultiplayerCount::Two, chance));
+
+    // 9 sequences, 3 item counts
+    assert!(outcomes.len() == 27);
+    assert_total_probability(&outcomes);
+    assert!(outcomes
+        .iter()
+        .all(|(_, probability)| (probability - 1.0 / 27.0).abs() < 1e-9));
+}
+
+#[test]
+fn phone_enumerates_every_shell() {
+    let phone = UnaryItem::Phone;
+    let compact = (0..)
+        .map(|seed| {
+            let session = GameSession::new(MultiplayerCount::Two, StdRng::seed_from_u64(seed));
+            CompactRound::from_round(session.round().unwrap()).unwrap()
+        })
+        .find(|compact| {
+            compact.shell_count() > 3
+                && compact.item_count(
+                    compact.next_player(),
+                    Item::NotAdreneline(NotAdreneline::UnaryItem(phone)),
+                ) > 0
+        })
+        .unwrap();
+
+    let outcomes = EnumeratingChance::enumerate(|chance| {
+        let mut compact = compact;
+        compact.apply(Action::UseUnaryItem(phone), chance).unwrap()
+    });
+
+    assert!(outcomes.len() == compact.shell_count() - 2);
+    assert_total_probability(&outcomes);
+    for (index, (outcome, _)) in outcomes.iter().enumerate() {
+        match &outcome.item_result {
+            Some(ItemUseResult::LearnedShell(learned_shell)) => {
+                assert!(learned_shell.relative_index == index + 2);
+                assert!(Some(learned_shell.shell_type) == compact.shell(index + 2));
+            }
+            _ => panic!("Phone should reveal a shell"),
+        }
+    }
+}
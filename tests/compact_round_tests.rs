@@ -0,0 +1,218 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    compact_round::CompactRound,
+    decision::{ActionOutcome, TurnEnd},
+    game_session::{GameSession, GameSessionOptions},
+    item::{Item, NotAdreneline, ALL_UNARY_ITEMS},
+    item_registry::{ItemBehavior, ItemContext, ItemLimits, ItemRegistry},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    rng_streams::RngStreams,
+    round_rules::{Healing, RoundRules},
+    seat::ForfeitedItems,
+    shell::SpecialShells,
+    turn::{InvalidItemUseError, ItemUseResult},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const PLAYERS: [PlayerNumber; 4] = [
+    PlayerNumber::One,
+    PlayerNumber::Two,
+    PlayerNumber::Three,
+    PlayerNumber::Four,
+];
+
+/// Mostly uses held items so they don't pile up, with some shots and illegal actions mixed in
+fn random_action(compact: &CompactRound, rng: &mut StdRng) -> Action {
+    let player = PLAYERS[rng.gen_range(0, 4)];
+    let unary_item = ALL_UNARY_ITEMS[rng.gen_range(0, ALL_UNARY_ITEMS.len())];
+    let held_items: Vec<Item> = compact.items(compact.next_player()).collect();
+    match rng.gen_range(0, 12) {
+        0..=5 if !held_items.is_empty() => match held_items[rng.gen_range(0, held_items.len())] {
+            Item::NotAdreneline(NotAdreneline::UnaryItem(unary_item)) => {
+                Action::UseUnaryItem(unary_item)
+            }
+            Item::NotAdreneline(NotAdreneline::Jammer) => Action::UseJammer(player),
+            Item::NotAdreneline(NotAdreneline::Handcuffs) => Action::UseHandcuffs(player),
+            Item::Adreneline => Action::UseAdreneline(player, unary_item),
+        },
+        0..=7 => Action::Shoot(player),
+        8 => Action::UseUnaryItem(unary_item),
+        9 => Action::UseJammer(player),
+        10 => Action::UseAdrenelineThenJammer(player, PLAYERS[rng.gen_range(0, 4)]),
+        _ => Action::UseAdrenelineThenHandcuffs(player, PLAYERS[rng.gen_range(0, 4)]),
+    }
+}
+
+fn snapshot(session: &GameSession<StdRng>) -> (CompactRound, RngStreams<StdRng>) {
+    let round = session.round().unwrap();
+    (
+        CompactRound::from_round(round).unwrap(),
+        round.rng().clone(),
+    )
+}
+
+fn play_both(mut session: GameSession<StdRng>, policy_seed: u64) {
+    let mut policy_rng = StdRng::seed_from_u64(policy_seed);
+    let (mut compact, mut rng) = snapshot(&session);
+
+    while session.round().is_some() {
+        let action = random_action(&compact, &mut policy_rng);
+        let compact_outcome = compact.apply(action, &mut rng);
+        // compact rounds don't know about the match
+        let outcome = session.submit(action).map(|outcome| ActionOutcome {
+            match_result: None,
+            ..outcome
+        });
+        assert!(compact_outcome == outcome);
+
+        if let Ok(outcome) = outcome {
+            if let Some(TurnEnd::RoundEnds { winner }) = outcome.turn_end {
+                assert!(compact.winner() == Some(winner));
+                if session.round().is_none() {
+                    return;
+                }
+
+                // keeps drawing from the compact side's streams, so any draw out of order shows up later
+                compact = compact.next_round(session.players(), &mut rng);
+                assert!(snapshot(&session).0 == compact);
+                continue;
+            }
+        }
+
+        assert!(CompactRound::from_round(session.round().unwrap()).unwrap() == compact);
+    }
+}
+
+#[test]
+fn compact_round_plays_like_round() {
+    for seed in 0..50 {
+        for multiplayer_count in [
+            MultiplayerCount::Two,
+            MultiplayerCount::Three,
+            MultiplayerCount::Four,
+        ] {
+            play_both(
+                GameSession::new(multiplayer_count, StdRng::seed_from_u64(seed)),
+                seed,
+            );
+            play_both(
+                GameSession::with_options(
+                    multiplayer_count,
+                    GameSessionOptions::with_stream_seed(seed),
+                ),
+                seed + 1000,
+            );
+
+            let healing = [Healing::Capped, Healing::Overheal(2), Healing::Disabled];
+            let mut options = GameSessionOptions::with_stream_seed(seed);
+            options.rules = RoundRules::default()
+                .with_item_capacity(2)
+                .with_healing(healing[seed as usize % healing.len()])
+                .with_sudden_death(2)
+                .with_handcuffs();
+            play_both(
+                GameSession::with_options(multiplayer_count, options),
+                seed + 2000,
+            );
+
+            // the forfeited seat stays empty in later rounds
+            let mut session = GameSession::new(multiplayer_count, StdRng::seed_from_u64(seed));
+            session
+                .forfeit(PlayerNumber::One, ForfeitedItems::Keep)
+                .unwrap();
+            if session.round().is_some() {
+                play_both(session, seed + 3000);
+            }
+        }
+    }
+}
+
+#[test]
+fn compact_round_deals_like_round() {
+    for seed in 0..50 {
+        let mut options = GameSessionOptions::<StdRng>::with_stream_seed(seed);
+        options.rules = RoundRules::default().with_handcuffs();
+        let session = GameSession::with_options(MultiplayerCount::Four, options.clone());
+
+        let mut rng = options.rng_streams;
+        let compact = CompactRound::new(session.players(), options.rules, &mut rng).unwrap();
+        assert!(compact == snapshot(&session).0);
+    }
+}
+
+/// Does nothing, only there to make the registry custom
+#[derive(Debug)]
+struct Trinket;
+
+impl ItemBehavior for Trinket {
+    fn name(&self) -> &str {
+        "Trinket"
+    }
+
+    fn limits(&self) -> ItemLimits {
+        ItemLimits {
+            player: 1,
+            global: 1,
+        }
+    }
+
+    fn use_item(&self, _context: ItemContext) -> Result<ItemUseResult, InvalidItemUseError> {
+        Ok(ItemUseResult::Default)
+    }
+}
+
+#[test]
+fn rounds_compact_rounds_cant_hold_are_none() {
+    let mut options = GameSessionOptions::<StdRng>::with_stream_seed(3);
+    options.rules = RoundRules::default().with_special_shells(SpecialShells {
+        slug: 1,
+        incendiary: 0,
+        dud: 0,
+    });
+    let session = GameSession::with_options(MultiplayerCount::Three, options.clone());
+    assert!(CompactRound::from_round(session.round().unwrap()).is_none());
+
+    let mut rng = options.rng_streams;
+    assert!(CompactRound::new(session.players(), options.rules, &mut rng).is_none());
+
+    let mut options = GameSessionOptions::<StdRng>::with_stream_seed(3);
+    options.item_registry = ItemRegistry::built_in();
+    options.item_registry.register(Trinket);
+    let session = GameSession::with_options(MultiplayerCount::Three, options);
+    assert!(CompactRound::from_round(session.round().unwrap()).is_none());
+
+    // health past what a seat packs
+    let mut options = GameSessionOptions::<StdRng>::with_stream_seed(3);
+    options.rules = RoundRules::default().with_healing(Healing::Overheal(20));
+    let session = GameSession::with_options(MultiplayerCount::Three, options.clone());
+    assert!(CompactRound::from_round(session.round().unwrap()).is_none());
+
+    let mut rng = options.rng_streams;
+    assert!(CompactRound::new(session.players(), options.rules, &mut rng).is_none());
+
+    let mut options = GameSessionOptions::<StdRng>::with_stream_seed(3);
+    options.rules = RoundRules::default().with_healing(Healing::Overheal(2));
+    let session = GameSession::with_options(MultiplayerCount::Three, options);
+    assert!(CompactRound::from_round(session.round().unwrap()).is_some());
+}
+
+#[test]
+fn compact_round_converts_back() {
+    let session = GameSession::new(MultiplayerCount::Four, StdRng::seed_from_u64(12));
+    let (compact, rng) = snapshot(&session);
+
+    let round = compact.into_round(rng);
+    assert!(CompactRound::from_round(&round).unwrap() == compact);
+    assert!(round.next_player() == session.round().unwrap().next_player());
+    assert!(round.shells().len() == compact.shell_count());
+
+    for seat in round.seats() {
+        let player_number = seat.player_number();
+        assert!(seat.items().len() == compact.items(player_number).count());
+        assert!(
+            seat.player().map(|player| player.health())
+                == Some(compact.health(player_number) as i32)
+        );
+    }
+}
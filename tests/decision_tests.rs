@@ -0,0 +1,85 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    decision::{SubmitError, TurnEnd},
+    game_session::GameSession,
+    item::{Item, NotAdreneline, UnaryItem, ALL_UNARY_ITEMS},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    turn::InvalidItemUseError,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn submit_plays_a_full_game() {
+    let mut session: GameSession<StdRng> =
+        GameSession::new(MultiplayerCount::Four, StdRng::seed_from_u64(42));
+
+    let mut rounds_ended = 0;
+    while let Some(decision) = session.pending_decision() {
+        assert!(decision.other_seats.len() == 3);
+        assert!(decision.round_player.player_number() == decision.player);
+
+        // use the first owned item that is allowed, otherwise shoot self
+        let owned_item = ALL_UNARY_ITEMS.iter().find(|unary_item| {
+            (**unary_item != UnaryItem::Handsaw || !decision.modifiers.shotgun_sawn())
+                && decision
+                    .items
+                    .contains(&Item::NotAdreneline(NotAdreneline::UnaryItem(**unary_item)))
+        });
+        let action = match owned_item {
+            Some(unary_item) => Action::UseUnaryItem(*unary_item),
+            None => Action::Shoot(decision.player),
+        };
+
+        let outcome = session.submit(action).unwrap();
+        assert!(outcome.actor == decision.player);
+        assert!(outcome.action == action);
+        match action {
+            Action::Shoot(_) => {
+                assert!(outcome.item_result.is_none());
+                assert!(outcome.shot_result.is_some());
+                assert!(outcome.turn_end.is_some());
+            }
+            _ => assert!(outcome.item_result.is_some()),
+        }
+
+        if let Some(TurnEnd::RoundEnds { .. }) = outcome.turn_end {
+            rounds_ended += 1;
+        }
+    }
+
+    assert!(rounds_ended == 3);
+    assert!(matches!(
+        session.submit(Action::Shoot(PlayerNumber::One)),
+        Err(SubmitError::NoRound)
+    ));
+}
+
+#[test]
+fn rejected_submissions_change_nothing() {
+    let mut session: GameSession<StdRng> =
+        GameSession::new(MultiplayerCount::Two, StdRng::seed_from_u64(42));
+
+    let decision = session.pending_decision().unwrap();
+    let missing_item = ALL_UNARY_ITEMS
+        .iter()
+        .find(|unary_item| {
+            !decision
+                .items
+                .contains(&Item::NotAdreneline(NotAdreneline::UnaryItem(**unary_item)))
+        })
+        .unwrap();
+
+    assert!(matches!(
+        session.submit(Action::UseUnaryItem(*missing_item)),
+        Err(SubmitError::InvalidItemUse(InvalidItemUseError::NoItem))
+    ));
+
+    let round = session.round().unwrap();
+    let shell_count = round.shells().len();
+    let after = session.pending_decision().unwrap();
+    assert!(after.player == decision.player);
+    assert!(after.items == decision.items);
+    assert!(after.shell_count == shell_count);
+    assert!(session.turns_taken() == 0);
+}
@@ -0,0 +1,93 @@
+use buckshot_roulette_gameplay_engine::{
+    game_session::GameSession, multiplayer_count::MultiplayerCount, player_number::PlayerNumber,
+    round::RoundContinuation, round_number::RoundNumber, seat::ForfeitedItems,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn two_player_forfeit_ends_game() {
+    let rng: StdRng = StdRng::seed_from_u64(42);
+
+    let mut session: GameSession<StdRng> = GameSession::new(MultiplayerCount::Two, rng);
+    session
+        .forfeit(PlayerNumber::Two, ForfeitedItems::Discard)
+        .unwrap();
+
+    assert!(session.round().is_none());
+
+    let players = session.players().as_vec();
+    assert!(players[0].wins().contains(&RoundNumber::One));
+    assert!(players[1].forfeited());
+    assert!(session
+        .forfeit(PlayerNumber::One, ForfeitedItems::Discard)
+        .is_err());
+}
+
+#[test]
+fn forfeit_active_player_passes_turn() {
+    let rng: StdRng = StdRng::seed_from_u64(42);
+
+    let mut session: GameSession<StdRng> = GameSession::new(MultiplayerCount::Three, rng);
+    assert!(session.round().unwrap().next_player() == PlayerNumber::One);
+
+    session
+        .forfeit(PlayerNumber::One, ForfeitedItems::Keep)
+        .unwrap();
+
+    let round = session.round().unwrap();
+    assert!(round.next_player() == PlayerNumber::Two);
+    assert!(round.living_players().count() == 2);
+    assert!(round.first_dead_player() == Some(PlayerNumber::One));
+    assert!(round.seats()[0].player().is_none());
+    assert!(!round.seats()[0].items().is_empty());
+
+    assert!(session
+        .forfeit(PlayerNumber::One, ForfeitedItems::Keep)
+        .is_err());
+}
+
+#[test]
+fn forfeited_player_skipped_in_following_rounds() {
+    let rng: StdRng = StdRng::seed_from_u64(42);
+
+    let mut session: GameSession<StdRng> = GameSession::new(MultiplayerCount::Three, rng);
+    session
+        .forfeit(PlayerNumber::Two, ForfeitedItems::Discard)
+        .unwrap();
+
+    {
+        let round = session.round().unwrap();
+        assert!(round.seats()[1].player().is_none());
+        assert!(round.seats()[1].items().is_empty());
+        assert!(round.next_player() == PlayerNumber::One);
+    }
+
+    let mut won_round = false;
+    for _ in 0..100 {
+        session
+            .with_turn(
+                |turn| turn.shoot(PlayerNumber::Three),
+                |summary| match &summary.round_continuation {
+                    RoundContinuation::RoundContinues(_) => {}
+                    RoundContinuation::RoundEnds(finished_round) => {
+                        assert!(finished_round.winner() == PlayerNumber::One);
+                        won_round = true;
+                    }
+                },
+            )
+            .unwrap();
+
+        if won_round {
+            break;
+        }
+    }
+
+    assert!(won_round);
+
+    // player two died first, but can't start the round
+    let round = session.round().unwrap();
+    assert!(round.number() == RoundNumber::Two);
+    assert!(round.next_player() == PlayerNumber::Three);
+    assert!(round.living_players().count() == 2);
+    assert!(round.seats()[1].player().is_none());
+}
@@ -0,0 +1,108 @@
+use buckshot_roulette_gameplay_engine::{
+    game_session::GameSession,
+    godot_rng::{GodotRandom, GodotRng},
+    loadout::Loadout,
+    multiplayer_count::MultiplayerCount,
+    shell::{ShellType, SpecialShells},
+};
+use rand::{RngCore, SeedableRng};
+
+#[test]
+fn matches_pcg32_reference_output() {
+    // pcg32-demo from the reference implementation, pcg32_srandom_r(&rng, 42u, 54u)
+    let mut rng = GodotRng::with_sequence(42, 54);
+    for expected in [
+        0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e,
+    ] {
+        assert!(rng.next_u32() == expected);
+    }
+}
+
+#[test]
+fn ranges_are_inclusive() {
+    let mut rng = GodotRng::new(12345);
+    let mut seen = [false; 3];
+    for _ in 0..1000 {
+        let value = rng.randi_range(3, 5);
+        assert!((3..=5).contains(&value));
+        seen[(value - 3) as usize] = true;
+
+        // reversed bounds behave the same
+        assert!((3..=5).contains(&rng.randi_range(5, 3)));
+        assert!(rng.randi_range(0, 3) <= 3);
+
+        let float = rng.randf();
+        assert!((0.0..=1.0).contains(&float));
+    }
+
+    assert!(seen.iter().all(|seen| *seen));
+    assert!(rng.randi_range(7, 7) == 7);
+}
+
+#[test]
+fn seeds_are_used_as_is() {
+    assert!(GodotRng::seed_from_u64(99) == GodotRng::new(99));
+    assert!(GodotRng::new(99).seed() == 99);
+
+    let mut rng = GodotRng::new(99);
+    let state = rng.state();
+    let first = rng.next_u32();
+    rng.set_state(state);
+    assert!(rng.next_u32() == first);
+}
+
+#[test]
+fn sessions_reproduce_from_seed() {
+    let first: GameSession<GodotRng> =
+        GameSession::new(MultiplayerCount::Four, GodotRng::new(2024));
+    let second: GameSession<GodotRng> =
+        GameSession::new(MultiplayerCount::Four, GodotRng::new(2024));
+
+    let first_round = first.round().unwrap();
+    let second_round = second.round().unwrap();
+    assert!(first_round.loadout() == second_round.loadout());
+    assert!(first_round.max_health() == second_round.max_health());
+    assert!(first_round
+        .shells()
+        .iter()
+        .zip(second_round.shells())
+        .all(|(first_shell, second_shell)| first_shell.shell_type() == second_shell.shell_type()));
+}
+
+#[test]
+fn sessions_match_the_pinned_seed_output() {
+    // recorded from this engine, not from a game capture, so it catches drift in the draw order but not a
+    // draw order that was wrong to begin with
+    let session: GameSession<GodotRng> =
+        GameSession::new(MultiplayerCount::Four, GodotRng::new(2024));
+    let round = session.round().unwrap();
+
+    assert!(
+        *round.loadout()
+            == Loadout {
+                initial_blank_rounds: 4,
+                initial_live_rounds: 3,
+                new_items: 3,
+                undealt_items: [0; 4],
+                special_rounds: SpecialShells::default(),
+            }
+    );
+    assert!(round.max_health() == 5);
+    let shells: Vec<ShellType> = round
+        .shells()
+        .iter()
+        .map(|shell| shell.shell_type())
+        .collect();
+    assert!(
+        shells
+            == [
+                ShellType::Live,
+                ShellType::Blank,
+                ShellType::Blank,
+                ShellType::Live,
+                ShellType::Live,
+                ShellType::Blank,
+                ShellType::Blank,
+            ]
+    );
+}
@@ -0,0 +1,118 @@
+use buckshot_roulette_gameplay_engine::{
+    gym::{
+        action_mask, decode_action, encode_action, ActionMask, GymEnv, GymError, Observation,
+        Opponent, RandomOpponent, ACTION_COUNT,
+    },
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+#[test]
+fn random_agent_plays_full_games() {
+    for seed in 0..20 {
+        let mut env: GymEnv<StdRng, _> = GymEnv::new(
+            MultiplayerCount::Four,
+            PlayerNumber::Two,
+            RandomOpponent::new(StdRng::seed_from_u64(seed + 1000)),
+        );
+        let mut agent_rng = StdRng::seed_from_u64(seed + 2000);
+
+        env.reset(seed).unwrap();
+        let mut total_reward = 0.0;
+        let mut done = env.round().is_none();
+        while !done {
+            let mask = env.action_mask();
+            let legal_actions: Vec<usize> = (0..ACTION_COUNT).filter(|id| mask[*id]).collect();
+            assert!(!legal_actions.is_empty());
+
+            let action_id = legal_actions[agent_rng.gen_range(0, legal_actions.len())];
+            let step_result = env.step(action_id).unwrap();
+            total_reward += step_result.reward;
+            done = step_result.done;
+        }
+
+        // three rounds are always played
+        assert!(total_reward.abs() <= 3.0);
+        assert!(matches!(env.step(0), Err(GymError::EpisodeOver)));
+        assert!(env.action_mask().iter().all(|legal| !legal));
+    }
+}
+
+#[test]
+fn illegal_actions_are_rejected() {
+    let mut env: GymEnv<StdRng, _> = GymEnv::new(
+        MultiplayerCount::Two,
+        PlayerNumber::One,
+        RandomOpponent::new(StdRng::seed_from_u64(1)),
+    );
+
+    assert!(matches!(env.step(0), Err(GymError::NotReset)));
+
+    env.reset(42).unwrap();
+    let mask = env.action_mask();
+
+    // only two seats exist, so the last two shoot actions are never legal
+    assert!(!mask[2] && !mask[3]);
+    assert!(matches!(env.step(3), Err(GymError::IllegalAction(3))));
+    assert!(matches!(
+        env.step(ACTION_COUNT),
+        Err(GymError::IllegalAction(_))
+    ));
+}
+
+#[test]
+fn actions_encode_and_decode() {
+    let mut env: GymEnv<StdRng, _> = GymEnv::new(
+        MultiplayerCount::Three,
+        PlayerNumber::Three,
+        RandomOpponent::new(StdRng::seed_from_u64(1)),
+    );
+    env.reset(7).unwrap();
+
+    let round = env.round().unwrap();
+    let player = round.next_player();
+    let mask = action_mask(round);
+    for action_id in (0..ACTION_COUNT).filter(|id| mask[*id]) {
+        let action = decode_action(round, player, action_id).unwrap();
+        assert!(encode_action(round, player, &action) == action_id);
+    }
+}
+
+#[test]
+fn resets_are_deterministic() {
+    let mut env: GymEnv<StdRng, _> = GymEnv::new(
+        MultiplayerCount::Three,
+        PlayerNumber::One,
+        RandomOpponent::new(StdRng::seed_from_u64(1)),
+    );
+
+    let first_observation = env.reset(99).unwrap();
+    let second_observation = env.reset(99).unwrap();
+    assert!(first_observation == second_observation);
+}
+
+struct IllegalOpponent;
+
+impl Opponent for IllegalOpponent {
+    fn choose_action(
+        &mut self,
+        _player: PlayerNumber,
+        _observation: &Observation,
+        _action_mask: &ActionMask,
+    ) -> usize {
+        ACTION_COUNT
+    }
+}
+
+#[test]
+fn illegal_opponent_actions_fail_the_reset() {
+    // player one always takes the first turn
+    let mut env: GymEnv<StdRng, _> =
+        GymEnv::new(MultiplayerCount::Two, PlayerNumber::Two, IllegalOpponent);
+
+    assert!(matches!(
+        env.reset(3),
+        Err(GymError::IllegalAction(ACTION_COUNT))
+    ));
+}
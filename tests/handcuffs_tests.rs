@@ -0,0 +1,193 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    decision::{SubmitError, TurnEnd},
+    game_session::{GameSession, GameSessionOptions},
+    item::{Item, NotAdreneline, UnaryItem},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    round::Round,
+    round_player::HandcuffState,
+    round_rules::RoundRules,
+    turn::{InvalidItemUseError, ItemUseResult},
+};
+use rand::rngs::StdRng;
+
+const HANDCUFFS: Item = Item::NotAdreneline(NotAdreneline::Handcuffs);
+const REMOTE: Item = Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Remote));
+
+fn session(
+    multiplayer_count: MultiplayerCount,
+    rules: RoundRules,
+    seed: u64,
+) -> GameSession<StdRng> {
+    let mut options = GameSessionOptions::with_stream_seed(seed);
+    options.rules = rules;
+    GameSession::with_options(multiplayer_count, options)
+}
+
+fn handcuff_state(round: &Round<StdRng>, player_number: PlayerNumber) -> HandcuffState {
+    round.seats()[player_number.seat_index()]
+        .player()
+        .unwrap()
+        .handcuff_state()
+}
+
+fn count(round: &Round<StdRng>, player_number: PlayerNumber, item: Item) -> usize {
+    round.seats()[player_number.seat_index()]
+        .items()
+        .iter()
+        .filter(|held| **held == item)
+        .count()
+}
+
+/// The player who would go after the actor without any skips
+fn next_in_order(round: &Round<StdRng>) -> PlayerNumber {
+    let seat_count = round.seats().len();
+    let step = if round.game_modifiers().turn_order_inverted() {
+        seat_count - 1
+    } else {
+        1
+    };
+
+    let mut seat_index = round.next_player().seat_index();
+    loop {
+        seat_index = (seat_index + step) % seat_count;
+        if round.seats()[seat_index].player().is_some() {
+            return round.seats()[seat_index].player_number();
+        }
+    }
+}
+
+fn shoot_someone_else(session: &mut GameSession<StdRng>, excluded: PlayerNumber) -> TurnEnd {
+    let round = session.round().unwrap();
+    let actor = round.next_player();
+    let target = round
+        .living_players()
+        .map(|seat| seat.player_number())
+        .find(|player_number| *player_number != actor && *player_number != excluded)
+        .unwrap_or(excluded);
+    session
+        .submit(Action::Shoot(target))
+        .unwrap()
+        .turn_end
+        .unwrap()
+}
+
+#[test]
+fn handcuffs_are_not_dealt_by_default() {
+    for seed in 0..20 {
+        let mut session = session(MultiplayerCount::Four, RoundRules::default(), seed);
+        while let Some(round) = session.round() {
+            for seat in round.seats() {
+                assert!(!seat.items().contains(&HANDCUFFS));
+            }
+
+            let actor = round.next_player();
+            shoot_someone_else(&mut session, actor);
+        }
+    }
+}
+
+#[test]
+fn handcuffs_skip_one_turn() {
+    let mut cuffed = 0;
+    for seed in 0..50 {
+        for multiplayer_count in [MultiplayerCount::Two, MultiplayerCount::Four] {
+            let mut session = session(
+                multiplayer_count,
+                RoundRules::default().with_handcuffs(),
+                seed,
+            );
+
+            while let Some(round) = session.round() {
+                let actor = round.next_player();
+                let items = round.seats()[actor.seat_index()].items();
+                if !items.contains(&HANDCUFFS) {
+                    shoot_someone_else(&mut session, actor);
+                    continue;
+                }
+
+                // flip the order sometimes so both directions are covered
+                if items.contains(&REMOTE) {
+                    session
+                        .submit(Action::UseUnaryItem(UnaryItem::Remote))
+                        .unwrap();
+                }
+
+                let round = session.round().unwrap();
+                let target = next_in_order(round);
+                if handcuff_state(round, target) != HandcuffState::Free {
+                    shoot_someone_else(&mut session, actor);
+                    continue;
+                }
+
+                let outcome = session.submit(Action::UseHandcuffs(target)).unwrap();
+                assert!(outcome.item_result == Some(ItemUseResult::HandcuffedPlayer(target)));
+                assert!(handcuff_state(session.round().unwrap(), target) == HandcuffState::Cuffed);
+
+                if !matches!(
+                    shoot_someone_else(&mut session, target),
+                    TurnEnd::LoadoutContinues | TurnEnd::LoadoutEnds { .. }
+                ) {
+                    continue;
+                }
+
+                let round = session.round().unwrap();
+                if round.seats()[target.seat_index()].player().is_none() {
+                    continue;
+                }
+
+                assert!(round.next_player() != target);
+                assert!(handcuff_state(round, target) == HandcuffState::Broken);
+                cuffed += 1;
+
+                // can't be cuffed again until the broken cuffs come off
+                let result = session.submit(Action::UseHandcuffs(target));
+                assert!(matches!(
+                    result,
+                    Err(SubmitError::InvalidItemUse(
+                        InvalidItemUseError::DoubleHandcuff | InvalidItemUseError::NoItem
+                    ))
+                ));
+            }
+        }
+    }
+
+    assert!(cuffed > 0);
+}
+
+#[test]
+fn handcuffs_can_be_stolen() {
+    let mut stolen = 0;
+    for seed in 0..50 {
+        let mut session = session(
+            MultiplayerCount::Two,
+            RoundRules::default().with_handcuffs(),
+            seed,
+        );
+
+        while let Some(round) = session.round() {
+            let actor = round.next_player();
+            let victim = next_in_order(round);
+            let can_steal = round.seats()[actor.seat_index()]
+                .items()
+                .contains(&Item::Adreneline)
+                && round.seats()[victim.seat_index()]
+                    .items()
+                    .contains(&HANDCUFFS);
+            if can_steal && handcuff_state(round, victim) == HandcuffState::Free {
+                let held = count(round, victim, HANDCUFFS);
+                let outcome = session
+                    .submit(Action::UseAdrenelineThenHandcuffs(victim, victim))
+                    .unwrap();
+                assert!(outcome.item_result == Some(ItemUseResult::HandcuffedPlayer(victim)));
+                assert!(count(session.round().unwrap(), victim, HANDCUFFS) == held - 1);
+                stolen += 1;
+            }
+
+            shoot_someone_else(&mut session, actor);
+        }
+    }
+
+    assert!(stolen > 0);
+}
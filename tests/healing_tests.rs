@@ -0,0 +1,99 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    game_session::{GameSession, GameSessionOptions},
+    item::{Item, NotAdreneline, UnaryItem},
+    multiplayer_count::MultiplayerCount,
+    round_rules::{Healing, RoundRules},
+    turn::{HealResult, ItemUseResult},
+};
+use rand::rngs::StdRng;
+
+const CIGARETTES: Item = Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Cigarettes));
+
+/// Smokes whenever possible, otherwise shoots the next living player. Returns every heal with the health before it and the max health
+fn smoke_through_game(healing: Healing, seed: u64) -> Vec<(i32, i32, HealResult)> {
+    let mut options = GameSessionOptions::with_stream_seed(seed);
+    options.rules = RoundRules::default().with_healing(healing);
+    let mut session: GameSession<StdRng> =
+        GameSession::with_options(MultiplayerCount::Two, options);
+
+    let mut heals = Vec::new();
+    while let Some(round) = session.round() {
+        let actor = round.next_player();
+        let seat = &round.seats()[actor.seat_index()];
+        let player = seat.player().unwrap();
+        let health = player.health();
+        let max_health = player.max_health();
+
+        if seat.items().contains(&CIGARETTES) {
+            let outcome = session
+                .submit(Action::UseUnaryItem(UnaryItem::Cigarettes))
+                .unwrap();
+            match outcome.item_result {
+                Some(ItemUseResult::Healed(heal_result)) => {
+                    let round = session.round().unwrap();
+                    let player = round.seats()[actor.seat_index()].player().unwrap();
+                    assert!(player.health() == health + i32::from(heal_result.healed));
+                    heals.push((health, max_health, heal_result));
+                }
+                _ => panic!("Cigarettes should heal"),
+            }
+            continue;
+        }
+
+        let target = round
+            .living_players()
+            .find(|seat| seat.player_number() != actor)
+            .unwrap()
+            .player_number();
+        session.submit(Action::Shoot(target)).unwrap();
+    }
+
+    heals
+}
+
+#[test]
+fn heals_are_capped_at_max_health() {
+    let mut capped = false;
+    for seed in 0..20 {
+        for (health, max_health, heal_result) in smoke_through_game(Healing::Capped, seed) {
+            assert!(heal_result.requested == 1);
+            assert!(heal_result.healed == u8::from(health < max_health));
+            capped |= heal_result.healed == 0;
+        }
+    }
+
+    assert!(capped);
+}
+
+#[test]
+fn overheal_goes_past_max_health() {
+    let mut overhealed = false;
+    for seed in 0..20 {
+        for (health, max_health, heal_result) in smoke_through_game(Healing::Overheal(2), seed) {
+            assert!(heal_result.healed == u8::from(health < max_health + 2));
+            overhealed |= health >= max_health && heal_result.healed == 1;
+        }
+    }
+
+    assert!(overhealed);
+}
+
+#[test]
+fn disabled_healing_still_uses_the_item() {
+    let mut smoked = false;
+    for seed in 0..20 {
+        for (_, _, heal_result) in smoke_through_game(Healing::Disabled, seed) {
+            assert!(
+                heal_result
+                    == HealResult {
+                        requested: 1,
+                        healed: 0
+                    }
+            );
+            smoked = true;
+        }
+    }
+
+    assert!(smoked);
+}
@@ -0,0 +1,249 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    decision::ActionOutcome,
+    game_session::{GameSession, GameSessionOptions},
+    history::HistoryEvent,
+    item::{Item, NotAdreneline, UnaryItem, ALL_UNARY_ITEMS},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    shell::{ShellType, ShotgunDamage},
+    turn::TakenAction,
+};
+use rand::rngs::StdRng;
+
+/// Uses every held item that works, then shoots the next living player. Returns every outcome
+fn play(seed: u64) -> (GameSession<StdRng>, Vec<ActionOutcome>) {
+    let mut session: GameSession<StdRng> = GameSession::with_options(
+        MultiplayerCount::Three,
+        GameSessionOptions::with_stream_seed(seed),
+    );
+
+    let mut outcomes = Vec::new();
+    while let Some(decision) = session.pending_decision() {
+        let item_outcome = ALL_UNARY_ITEMS
+            .iter()
+            .filter(|unary_item| {
+                decision
+                    .items
+                    .contains(&Item::NotAdreneline(NotAdreneline::UnaryItem(**unary_item)))
+            })
+            .find_map(|unary_item| session.submit(Action::UseUnaryItem(*unary_item)).ok());
+
+        let outcome = match item_outcome {
+            Some(outcome) => outcome,
+            None => {
+                let target = decision
+                    .other_seats
+                    .iter()
+                    .find(|seat| seat.stun_state.is_some())
+                    .unwrap()
+                    .player_number;
+                session.submit(Action::Shoot(target)).unwrap()
+            }
+        };
+        outcomes.push(outcome);
+    }
+
+    (session, outcomes)
+}
+
+#[test]
+fn history_records_every_action_in_order() {
+    for seed in 0..10 {
+        let (session, outcomes) = play(seed);
+        let history = session.history();
+
+        let recorded: Vec<(PlayerNumber, Action)> = history
+            .entries()
+            .iter()
+            .filter_map(|entry| match &entry.event {
+                HistoryEvent::ItemUsed { player, action, .. } => Some((*player, *action)),
+                HistoryEvent::Shot {
+                    shooter, result, ..
+                } => Some((*shooter, Action::Shoot(result.target_player))),
+                HistoryEvent::Loadout { .. }
+                | HistoryEvent::Burned(_)
+                | HistoryEvent::Forfeited { .. }
+                | HistoryEvent::RoundEnded { .. } => None,
+            })
+            .collect();
+        let submitted: Vec<(PlayerNumber, Action)> = outcomes
+            .iter()
+            .map(|outcome| (outcome.actor, outcome.action))
+            .collect();
+        assert!(recorded == submitted);
+
+        for (entry, outcome) in history
+            .entries()
+            .iter()
+            .filter(|entry| {
+                !matches!(
+                    entry.event,
+                    HistoryEvent::Loadout { .. } | HistoryEvent::RoundEnded { .. }
+                )
+            })
+            .zip(&outcomes)
+        {
+            match &entry.event {
+                HistoryEvent::ItemUsed { result, .. } => {
+                    assert!(Some(result) == outcome.item_result.as_ref())
+                }
+                HistoryEvent::Shot {
+                    shell_type, result, ..
+                } => {
+                    assert!(Some(result) == outcome.shot_result.as_ref());
+                    assert!(
+                        (*shell_type == ShellType::Blank)
+                            == (result.damage == ShotgunDamage::Blank)
+                    );
+                }
+                HistoryEvent::Loadout { .. }
+                | HistoryEvent::Burned(_)
+                | HistoryEvent::Forfeited { .. }
+                | HistoryEvent::RoundEnded { .. } => unreachable!(),
+            }
+        }
+
+        let new_loadouts = outcomes
+            .iter()
+            .filter(|outcome| outcome.new_loadout())
+            .count();
+        // the last round's end doesn't load the shotgun again
+        assert!(history.loadouts().count() == new_loadouts);
+
+        assert!(history
+            .entries()
+            .windows(2)
+            .all(|pair| pair[0].turn_index <= pair[1].turn_index
+                && pair[0].loadout_index <= pair[1].loadout_index));
+    }
+}
+
+#[test]
+fn history_can_be_queried_by_player_and_loadout() {
+    let (session, outcomes) = play(3);
+    let history = session.history();
+
+    for player_number in [PlayerNumber::One, PlayerNumber::Two, PlayerNumber::Three] {
+        let acted = history
+            .by_player(player_number)
+            .filter(|entry| match &entry.event {
+                HistoryEvent::ItemUsed { player, .. } => *player == player_number,
+                HistoryEvent::Shot { shooter, .. } => *shooter == player_number,
+                HistoryEvent::Loadout { .. }
+                | HistoryEvent::Burned(_)
+                | HistoryEvent::Forfeited { .. }
+                | HistoryEvent::RoundEnded { .. } => false,
+            })
+            .count();
+        let submitted = outcomes
+            .iter()
+            .filter(|outcome| outcome.actor == player_number)
+            .count();
+        assert!(acted == submitted);
+        assert!(history
+            .by_player(player_number)
+            .all(|entry| entry.event.involves(player_number)));
+    }
+
+    for (loadout_index, (loadout, dealt_items)) in history.loadouts().enumerate() {
+        let mut entries = history.by_loadout(loadout_index);
+        match &entries.next().unwrap().event {
+            HistoryEvent::Loadout {
+                loadout: first_loadout,
+                dealt_items: first_dealt_items,
+            } => {
+                assert!(first_loadout == loadout);
+                assert!(first_dealt_items == dealt_items);
+            }
+            _ => panic!("A loadout should start with its composition"),
+        }
+
+        // shells fired in a loadout never exceed what was loaded
+        let shots = entries
+            .filter(|entry| matches!(entry.event, HistoryEvent::Shot { .. }))
+            .count();
+        assert!(shots <= loadout.initial_live_rounds + loadout.initial_blank_rounds);
+    }
+}
+
+#[test]
+fn history_records_items_chained_in_one_turn() {
+    const CHAINABLE: [UnaryItem; 3] = [
+        UnaryItem::MagnifyingGlass,
+        UnaryItem::Inverter,
+        UnaryItem::Phone,
+    ];
+
+    let mut chained = 0;
+    for seed in 0..20 {
+        let mut session: GameSession<StdRng> = GameSession::with_options(
+            MultiplayerCount::Three,
+            GameSessionOptions::with_stream_seed(seed),
+        );
+
+        while let Some(decision) = session.pending_decision() {
+            let target = decision
+                .other_seats
+                .iter()
+                .find(|seat| seat.stun_state.is_some())
+                .unwrap()
+                .player_number;
+            let held: Vec<UnaryItem> = CHAINABLE
+                .into_iter()
+                .filter(|unary_item| {
+                    decision
+                        .items
+                        .contains(&Item::NotAdreneline(NotAdreneline::UnaryItem(*unary_item)))
+                })
+                .collect();
+            if held.len() < 2 {
+                session.submit(Action::Shoot(target)).unwrap();
+                continue;
+            }
+
+            let recorded = session.history().entries().len();
+            session
+                .with_turn(
+                    |turn| {
+                        let TakenAction::Continued(first) = turn.use_unary_item(held[0]) else {
+                            panic!("Chainable items don't end the turn");
+                        };
+                        let TakenAction::Continued(second) =
+                            first.next_action().use_unary_item(held[1])
+                        else {
+                            panic!("Chainable items don't end the turn");
+                        };
+                        second.next_action().shoot(target)
+                    },
+                    |_| (),
+                )
+                .unwrap();
+
+            let actions: Vec<(PlayerNumber, Action)> = session.history().entries()[recorded..]
+                .iter()
+                .filter_map(|entry| match &entry.event {
+                    HistoryEvent::ItemUsed { player, action, .. } => Some((*player, *action)),
+                    HistoryEvent::Shot {
+                        shooter, result, ..
+                    } => Some((*shooter, Action::Shoot(result.target_player))),
+                    HistoryEvent::Loadout { .. }
+                    | HistoryEvent::Burned(_)
+                    | HistoryEvent::Forfeited { .. }
+                    | HistoryEvent::RoundEnded { .. } => None,
+                })
+                .collect();
+            assert!(
+                actions
+                    == vec![
+                        (decision.player, Action::UseUnaryItem(held[0])),
+                        (decision.player, Action::UseUnaryItem(held[1])),
+                        (decision.player, Action::Shoot(target)),
+                    ]
+            );
+            chained += 1;
+        }
+    }
+
+    assert!(chained > 0);
+}
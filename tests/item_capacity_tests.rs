@@ -0,0 +1,184 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    compact_round::CompactRound,
+    decision::{ActionOutcome, TurnEnd},
+    game_session::{GameSession, GameSessionOptions},
+    item::{Item, NotAdreneline, UnaryItem},
+    multiplayer_count::MultiplayerCount,
+    round_rules::RoundRules,
+    seat::MAX_ITEMS,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn session_with_capacity(seed: u64, item_capacity: usize) -> GameSession<StdRng> {
+    let mut options = GameSessionOptions::with_stream_seed(seed);
+    options.rules = RoundRules::default().with_item_capacity(item_capacity);
+    GameSession::with_options(MultiplayerCount::Four, options)
+}
+
+/// Shoots the next player until a loadout ends
+fn play_out_loadout(session: &mut GameSession<StdRng>) {
+    loop {
+        let round = session.round().unwrap();
+        let target = round
+            .living_players()
+            .find(|seat| seat.player_number() != round.next_player())
+            .unwrap()
+            .player_number();
+
+        let outcome = session.submit(Action::Shoot(target)).unwrap();
+        if !matches!(outcome.turn_end, Some(TurnEnd::LoadoutContinues)) {
+            return;
+        }
+    }
+}
+
+#[test]
+fn full_seats_are_skipped() {
+    for seed in 0..20 {
+        let mut session = session_with_capacity(seed, 2);
+        for _ in 0..4 {
+            let Some(round) = session.round() else {
+                break;
+            };
+
+            for seat in round.seats() {
+                assert!(seat.items().len() <= 2);
+            }
+
+            let undealt_items: usize = round.loadout().undealt_items.iter().sum();
+            let expected_items = round.loadout().new_items * round.living_players().count();
+            assert!(round.dealt_items().len() + undealt_items == expected_items);
+
+            play_out_loadout(&mut session);
+        }
+    }
+}
+
+#[test]
+fn exhausted_item_pools_are_undealt() {
+    let mut exhausted = 0;
+    for seed in 0..20 {
+        let mut session = session_with_capacity(seed, 64);
+        let round = session.round().unwrap();
+        let mut compact = CompactRound::from_round(round).unwrap();
+        let mut rng = round.rng().clone();
+
+        // Beer makes loadouts go by with fewer shots, the other items pile up until they are at their limits
+        let mut new_loadout = true;
+        while let Some(round) = session.round() {
+            if new_loadout {
+                let undealt_items: usize = round.loadout().undealt_items.iter().sum();
+                let expected_items = round.loadout().new_items * round.living_players().count();
+                assert!(round.dealt_items().len() + undealt_items == expected_items);
+                if undealt_items > 0 {
+                    exhausted += 1;
+                }
+            }
+
+            let beer = Action::UseUnaryItem(UnaryItem::Beer);
+            let action = match compact.check(beer) {
+                Ok(()) => beer,
+                Err(_) => Action::Shoot(
+                    round
+                        .living_players()
+                        .find(|seat| seat.player_number() != round.next_player())
+                        .unwrap()
+                        .player_number(),
+                ),
+            };
+            let outcome = session.submit(action).unwrap();
+            // compact rounds don't know about the match
+            let compact_outcome = ActionOutcome {
+                match_result: outcome.match_result.clone(),
+                ..compact.apply(action, &mut rng).unwrap()
+            };
+            assert!(compact_outcome == outcome);
+
+            new_loadout = matches!(
+                outcome.turn_end,
+                Some(TurnEnd::LoadoutEnds { .. } | TurnEnd::RoundEnds { .. })
+            );
+            match session.round() {
+                Some(round) if matches!(outcome.turn_end, Some(TurnEnd::RoundEnds { .. })) => {
+                    compact = CompactRound::from_round(round).unwrap();
+                    rng = round.rng().clone();
+                }
+                Some(round) => assert!(CompactRound::from_round(round).unwrap() == compact),
+                None => {}
+            }
+        }
+    }
+
+    assert!(exhausted > 0);
+}
+
+#[test]
+fn default_capacity_fills_the_table() {
+    let session = GameSession::new(MultiplayerCount::Two, StdRng::seed_from_u64(0));
+    let round = session.round().unwrap();
+    assert!(*round.rules() == RoundRules::default());
+    assert!(round.rules().item_capacities == [MAX_ITEMS; 4]);
+    assert!(round.loadout().undealt_items == [0; 4]);
+}
+
+#[test]
+fn items_take_the_first_free_slot() {
+    let mut refilled = 0;
+    for seed in 0..20 {
+        let mut session = session_with_capacity(seed, MAX_ITEMS);
+        let round = session.round().unwrap();
+        for seat in round.seats() {
+            let slots: Vec<usize> = (0..seat.items().len()).collect();
+            assert!(*seat.item_slots() == slots);
+        }
+
+        for dealt_item in round.dealt_items() {
+            let seat = &round.seats()[dealt_item.player_number.seat_index()];
+            let index = seat
+                .item_slots()
+                .iter()
+                .position(|slot| *slot == dealt_item.slot)
+                .unwrap();
+            assert!(seat.items()[index] == dealt_item.item);
+        }
+
+        // Beer and Cigarettes keep the turn, so the first slot can be freed without shooting
+        let player_number = round.next_player();
+        let first_item = round.seats()[player_number.seat_index()].items()[0];
+        let unary_item = match first_item {
+            Item::NotAdreneline(NotAdreneline::UnaryItem(
+                unary_item @ (UnaryItem::Beer | UnaryItem::Cigarettes),
+            )) => unary_item,
+            _ => continue,
+        };
+        if round.shells().len() < 2 {
+            continue;
+        }
+
+        let outcome = session.submit(Action::UseUnaryItem(unary_item)).unwrap();
+        if outcome.turn_end.is_some() {
+            continue;
+        }
+
+        let round = session.round().unwrap();
+        assert!(!round.seats()[player_number.seat_index()]
+            .item_slots()
+            .contains(&0));
+
+        play_out_loadout(&mut session);
+        let Some(round) = session.round() else {
+            continue;
+        };
+        if let Some(dealt_item) = round
+            .dealt_items()
+            .iter()
+            .find(|dealt_item| dealt_item.player_number == player_number)
+        {
+            assert!(dealt_item.slot == 0);
+            refilled += 1;
+        }
+    }
+
+    assert!(refilled > 0);
+}
@@ -0,0 +1,188 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    decision::SubmitError,
+    game_session::{GameSession, GameSessionOptions},
+    item::{CustomItemId, Item, NotAdreneline, UnaryItem},
+    item_registry::{ItemBehavior, ItemContext, ItemLimits, ItemRegistry},
+    multiplayer_count::MultiplayerCount,
+    turn::{HealResult, InvalidItemUseError, ItemUseResult, LearnedShell},
+};
+use rand::rngs::StdRng;
+
+/// Heals two
+#[derive(Debug)]
+struct Bandage;
+
+/// Reveals the last shell
+#[derive(Debug)]
+struct Scope;
+
+impl ItemBehavior for Bandage {
+    fn name(&self) -> &str {
+        "Bandage"
+    }
+
+    fn limits(&self) -> ItemLimits {
+        ItemLimits {
+            player: 1,
+            global: 2,
+        }
+    }
+
+    fn use_item(&self, context: ItemContext) -> Result<ItemUseResult, InvalidItemUseError> {
+        let healed = context.player.gain_health(2, context.rules.healing);
+        Ok(ItemUseResult::Healed(HealResult {
+            requested: 2,
+            healed,
+        }))
+    }
+}
+
+impl ItemBehavior for Scope {
+    fn name(&self) -> &str {
+        "Scope"
+    }
+
+    fn limits(&self) -> ItemLimits {
+        ItemLimits {
+            player: 1,
+            global: 1,
+        }
+    }
+
+    fn use_item(&self, context: ItemContext) -> Result<ItemUseResult, InvalidItemUseError> {
+        let relative_index = context.shells.len() - 1;
+        Ok(ItemUseResult::LearnedShell(LearnedShell {
+            relative_index,
+            shell_type: context.shells[relative_index].shell_type(),
+        }))
+    }
+}
+
+fn custom(id: CustomItemId) -> Item {
+    Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Custom(id)))
+}
+
+fn session(item_registry: ItemRegistry, seed: u64) -> GameSession<StdRng> {
+    let mut options = GameSessionOptions::with_stream_seed(seed);
+    options.item_registry = item_registry;
+    GameSession::with_options(MultiplayerCount::Four, options)
+}
+
+#[test]
+fn custom_items_are_dealt_within_limits_and_used() {
+    let mut item_registry = ItemRegistry::built_in();
+    let bandage = item_registry.register(Bandage);
+    let scope = item_registry.register(Scope);
+    assert!(!item_registry.is_built_in());
+    assert!(item_registry.behavior(custom(scope)).unwrap().name() == "Scope");
+
+    let mut bandages_used = 0;
+    let mut scopes_used = 0;
+    let mut stolen = 0;
+    for seed in 0..30 {
+        let mut session = session(item_registry.clone(), seed);
+        while let Some(round) = session.round() {
+            for (item, limits) in [(bandage, Bandage.limits()), (scope, Scope.limits())] {
+                let counts = round.seats().iter().map(|seat| {
+                    seat.items()
+                        .iter()
+                        .filter(|held| **held == custom(item))
+                        .count()
+                });
+                assert!(counts.clone().all(|count| count <= limits.player));
+                assert!(counts.sum::<usize>() <= limits.global);
+            }
+
+            let actor = round.next_player();
+            let seat = &round.seats()[actor.seat_index()];
+            let health = seat.player().unwrap().health();
+            let shell_count = round.shells().len();
+
+            let victim = round
+                .seats()
+                .iter()
+                .find(|other| {
+                    other.player_number() != actor
+                        && other.player().is_some()
+                        && other.items().contains(&custom(bandage))
+                })
+                .map(|other| other.player_number());
+
+            let action = if seat.items().contains(&custom(scope)) {
+                Action::UseUnaryItem(UnaryItem::Custom(scope))
+            } else if seat.items().contains(&custom(bandage)) {
+                Action::UseUnaryItem(UnaryItem::Custom(bandage))
+            } else if let (true, Some(victim)) = (seat.items().contains(&Item::Adreneline), victim)
+            {
+                stolen += 1;
+                Action::UseAdreneline(victim, UnaryItem::Custom(bandage))
+            } else {
+                let target = round
+                    .living_players()
+                    .find(|other| other.player_number() != actor)
+                    .unwrap()
+                    .player_number();
+                session.submit(Action::Shoot(target)).unwrap();
+                continue;
+            };
+
+            let outcome = session.submit(action).unwrap();
+            let round = session.round().unwrap();
+            match outcome.item_result {
+                Some(ItemUseResult::Healed(heal_result)) => {
+                    bandages_used += 1;
+                    assert!(heal_result.requested == 2);
+                    let player = round.seats()[actor.seat_index()].player().unwrap();
+                    assert!(player.health() == health + i32::from(heal_result.healed));
+                }
+                Some(ItemUseResult::LearnedShell(learned_shell)) => {
+                    scopes_used += 1;
+                    assert!(learned_shell.relative_index == shell_count - 1);
+                    assert!(
+                        round.shells()[learned_shell.relative_index].shell_type()
+                            == learned_shell.shell_type
+                    );
+                }
+                _ => panic!("Unexpected result for {:?}", action),
+            }
+        }
+    }
+
+    assert!(bandages_used > 0);
+    assert!(scopes_used > 0);
+    assert!(stolen > 0);
+}
+
+#[test]
+fn unregistered_items_cannot_be_used() {
+    let mut session = session(ItemRegistry::built_in(), 0);
+    let result = session.submit(Action::UseUnaryItem(UnaryItem::Custom(CustomItemId(0))));
+    assert!(result == Err(SubmitError::InvalidItemUse(InvalidItemUseError::NoItem)));
+}
+
+#[test]
+fn built_in_registry_keeps_the_game_order() {
+    let item_registry = ItemRegistry::default();
+    assert!(item_registry.is_built_in());
+
+    let names: Vec<&str> = item_registry
+        .items()
+        .map(|item| item_registry.behavior(item).unwrap().name())
+        .collect();
+    assert!(
+        names
+            == [
+                "Remote",
+                "Phone",
+                "Inverter",
+                "Magnifying Glass",
+                "Cigarettes",
+                "Handsaw",
+                "Beer",
+                "Adreneline",
+                "Jammer",
+                "Handcuffs"
+            ]
+    );
+}
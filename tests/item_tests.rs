@@ -39,6 +39,8 @@ where
         session
             .with_turn(
                 |turn| {
+                    // predates the manual_contains lint
+                    #[allow(clippy::manual_contains)]
                     let has_item = turn.items().iter().any(|item| *item == target_item);
 
                     let current_player = turn.player().player_number();
@@ -93,7 +95,10 @@ fn test_phone() {
                                 assert!(shell_count > 2);
                                 assert!(learned_shell.relative_index > 1)
                             }
-                            ItemUseResult::ShotgunRacked(_) | ItemUseResult::StunnedPlayer(_) => {
+                            ItemUseResult::ShotgunRacked(_)
+                            | ItemUseResult::StunnedPlayer(_)
+                            | ItemUseResult::Healed(_)
+                            | ItemUseResult::HandcuffedPlayer(_) => {
                                 panic!("Should be impossible with phone")
                             }
                         },
@@ -127,7 +132,9 @@ fn test_beer() {
                             }
                             ItemUseResult::Default
                             | ItemUseResult::LearnedShell(_)
-                            | ItemUseResult::StunnedPlayer(_) => {
+                            | ItemUseResult::StunnedPlayer(_)
+                            | ItemUseResult::Healed(_)
+                            | ItemUseResult::HandcuffedPlayer(_) => {
                                 panic!("Shouldn't be possible with beer")
                             }
                         },
@@ -155,21 +162,28 @@ fn test_cigs() {
             let item_use = turn.use_unary_item(UnaryItem::Cigarettes);
             match item_use {
                 TakenAction::Continued(continued_turn) => {
-                    match continued_turn.item_result() {
+                    let healed = match continued_turn.item_result() {
                         Ok(use_result) => match use_result {
-                            ItemUseResult::Default => {}
-                            ItemUseResult::LearnedShell(_)
+                            ItemUseResult::Healed(heal_result) => {
+                                assert!(heal_result.requested == 1);
+                                heal_result.healed
+                            }
+                            ItemUseResult::Default
+                            | ItemUseResult::LearnedShell(_)
                             | ItemUseResult::StunnedPlayer(_)
-                            | ItemUseResult::ShotgunRacked(_) => {
-                                panic!("Shouldn't be possible with beer")
+                            | ItemUseResult::ShotgunRacked(_)
+                            | ItemUseResult::HandcuffedPlayer(_) => {
+                                panic!("Shouldn't be possible with cigs")
                             }
                         },
                         Err(_) => panic!("Cigs should never have a bad use result"),
-                    }
+                    };
 
                     let next_action = continued_turn.next_action();
 
-                    assert!(prior_health <= next_action.player().health());
+                    let player = next_action.player();
+                    assert!(prior_health + i32::from(healed) == player.health());
+                    assert!(player.health() <= player.max_health());
                     if prior_health < next_action.player().health() {
                         healed_once = true;
                     }
@@ -0,0 +1,172 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    decision::{SubmitError, TurnEnd},
+    game_session::{GameSession, GameSessionOptions},
+    item::{Item, NotAdreneline, UnaryItem},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    round::Round,
+    round_player::StunState,
+    turn::{InvalidItemUseError, ItemUseResult},
+};
+use rand::rngs::StdRng;
+
+const JAMMER: Item = Item::NotAdreneline(NotAdreneline::Jammer);
+
+fn stun_state(round: &Round<StdRng>, player_number: PlayerNumber) -> Option<StunState> {
+    round.seats()[player_number.seat_index()]
+        .player()
+        .map(|player| player.stun_state())
+}
+
+fn count(round: &Round<StdRng>, player_number: PlayerNumber, item: Item) -> usize {
+    round.seats()[player_number.seat_index()]
+        .items()
+        .iter()
+        .filter(|held| **held == item)
+        .count()
+}
+
+/// The next living player after the actor who isn't stunned yet
+fn unstunned_target(round: &Round<StdRng>) -> Option<PlayerNumber> {
+    let actor = round.next_player();
+    round
+        .living_players()
+        .map(|seat| seat.player_number())
+        .find(|player_number| {
+            *player_number != actor
+                && stun_state(round, *player_number) == Some(StunState::Unstunned)
+        })
+}
+
+fn shoot_someone_else(session: &mut GameSession<StdRng>) -> TurnEnd {
+    let round = session.round().unwrap();
+    let actor = round.next_player();
+    let target = round
+        .living_players()
+        .map(|seat| seat.player_number())
+        .find(|player_number| *player_number != actor)
+        .unwrap();
+    session
+        .submit(Action::Shoot(target))
+        .unwrap()
+        .turn_end
+        .unwrap()
+}
+
+#[test]
+fn jammers_skip_the_targets_turn() {
+    let mut jammed = 0;
+    for seed in 0..50 {
+        let mut session = GameSession::with_options(
+            MultiplayerCount::Four,
+            GameSessionOptions::with_stream_seed(seed),
+        );
+
+        while let Some(round) = session.round() {
+            let actor = round.next_player();
+            let target = unstunned_target(round);
+            let Some(target) = target.filter(|_| count(round, actor, JAMMER) > 0) else {
+                shoot_someone_else(&mut session);
+                continue;
+            };
+
+            let outcome = session.submit(Action::UseJammer(target)).unwrap();
+            assert!(outcome.item_result == Some(ItemUseResult::StunnedPlayer(target)));
+            assert!(stun_state(session.round().unwrap(), target) == Some(StunState::Stunned));
+
+            // can't be jammed twice
+            let result = session.submit(Action::UseJammer(target));
+            assert!(matches!(
+                result,
+                Err(SubmitError::InvalidItemUse(
+                    InvalidItemUseError::DoubleStun | InvalidItemUseError::NoItem
+                ))
+            ));
+
+            // the target sits out every turn until the loadout ends or they recover
+            loop {
+                let round = session.round().unwrap();
+                if round.next_player() == target {
+                    assert!(stun_state(round, target) == Some(StunState::Unstunned));
+                    break;
+                }
+                if stun_state(round, target) != Some(StunState::Stunned) {
+                    jammed += 1;
+                    break;
+                }
+                if !matches!(shoot_someone_else(&mut session), TurnEnd::LoadoutContinues) {
+                    break;
+                }
+            }
+        }
+    }
+
+    assert!(jammed > 0);
+}
+
+#[test]
+fn adreneline_takes_the_stolen_item() {
+    let mut stolen = 0;
+    for seed in 0..50 {
+        let mut session = GameSession::with_options(
+            MultiplayerCount::Four,
+            GameSessionOptions::with_stream_seed(seed),
+        );
+
+        while let Some(round) = session.round() {
+            let actor = round.next_player();
+            if count(round, actor, Item::Adreneline) > 0 {
+                let victim = round
+                    .living_players()
+                    .map(|seat| seat.player_number())
+                    .find(|player_number| {
+                        *player_number != actor && count(round, *player_number, JAMMER) > 0
+                    });
+                if let (Some(victim), Some(target)) = (victim, unstunned_target(round)) {
+                    let held = count(round, victim, JAMMER);
+                    let outcome = session
+                        .submit(Action::UseAdrenelineThenJammer(victim, target))
+                        .unwrap();
+                    assert!(outcome.item_result == Some(ItemUseResult::StunnedPlayer(target)));
+
+                    let round = session.round().unwrap();
+                    assert!(count(round, victim, JAMMER) == held - 1);
+                    assert!(stun_state(round, target) == Some(StunState::Stunned));
+                    stolen += 1;
+                }
+            }
+
+            let round = session.round().unwrap();
+            let beer = Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Beer));
+            let victim = round
+                .living_players()
+                .map(|seat| seat.player_number())
+                .find(|player_number| {
+                    *player_number != round.next_player() && count(round, *player_number, beer) > 0
+                });
+            if let (Some(victim), true) = (
+                victim,
+                count(round, round.next_player(), Item::Adreneline) > 0,
+            ) {
+                let held = count(round, victim, beer);
+                if session
+                    .submit(Action::UseAdreneline(victim, UnaryItem::Beer))
+                    .unwrap()
+                    .turn_end
+                    .is_none()
+                {
+                    assert!(count(session.round().unwrap(), victim, beer) == held - 1);
+                    stolen += 1;
+                    continue;
+                }
+            }
+
+            if session.round().is_some() {
+                shoot_someone_else(&mut session);
+            }
+        }
+    }
+
+    assert!(stolen > 0);
+}
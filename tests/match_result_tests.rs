@@ -0,0 +1,158 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    game_players::GamePlayers,
+    game_session::{GameSession, GameSessionOptions},
+    match_result::{MatchRules, Tiebreak, WinCriterion},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    round_number::RoundNumber,
+    seat::ForfeitedItems,
+};
+use rand::rngs::StdRng;
+
+fn players_with_winners(
+    multiplayer_count: MultiplayerCount,
+    round_winners: &[PlayerNumber],
+) -> GamePlayers {
+    let mut players = GamePlayers::new(multiplayer_count);
+    for (round_winner, round_number) in
+        round_winners
+            .iter()
+            .zip([RoundNumber::One, RoundNumber::Two, RoundNumber::Three])
+    {
+        players.register_win(*round_winner, round_number).unwrap();
+    }
+    players
+}
+
+/// Shoots the next living player until the session is over
+fn play(match_rules: MatchRules, seed: u64) -> GameSession<StdRng> {
+    let mut options = GameSessionOptions::with_stream_seed(seed);
+    options.match_rules = match_rules;
+    let mut session = GameSession::with_options(MultiplayerCount::Three, options);
+
+    while let Some(decision) = session.pending_decision() {
+        assert!(session.match_result().is_none());
+        let target = decision
+            .other_seats
+            .iter()
+            .find(|seat| seat.stun_state.is_some())
+            .unwrap()
+            .player_number;
+        let outcome = session.submit(Action::Shoot(target)).unwrap();
+        assert!(outcome.match_result.as_ref() == session.match_result());
+    }
+
+    session
+}
+
+#[test]
+fn split_wins_go_to_the_tiebreak() {
+    let players = players_with_winners(
+        MultiplayerCount::Three,
+        &[PlayerNumber::One, PlayerNumber::Two, PlayerNumber::Three],
+    );
+
+    let rules = |criterion, tiebreak| MatchRules {
+        criterion,
+        tiebreak,
+        end_early: false,
+    };
+    let result = rules(WinCriterion::MostRounds, Tiebreak::LatestWin).result(&players);
+    assert!(result.winner == Some(PlayerNumber::Three));
+    assert!(result.rounds_won == [1, 1, 1]);
+    assert!(!result.ended_early);
+
+    let result = rules(WinCriterion::MostRounds, Tiebreak::EarliestWin).result(&players);
+    assert!(result.winner == Some(PlayerNumber::One));
+    let result = rules(WinCriterion::FirstToTwo, Tiebreak::Draw).result(&players);
+    assert!(result.winner.is_none());
+    let result = rules(WinCriterion::FinalRound, Tiebreak::Draw).result(&players);
+    assert!(result.winner == Some(PlayerNumber::Three));
+}
+
+#[test]
+fn criteria_decide_when_the_match_ends() {
+    let one_two = players_with_winners(
+        MultiplayerCount::Three,
+        &[PlayerNumber::Two, PlayerNumber::Two],
+    );
+    let first_to_two = MatchRules {
+        criterion: WinCriterion::FirstToTwo,
+        ..Default::default()
+    };
+    let final_round = MatchRules {
+        criterion: WinCriterion::FinalRound,
+        end_early: true,
+        ..Default::default()
+    };
+    let early = MatchRules {
+        end_early: true,
+        ..Default::default()
+    };
+
+    assert!(first_to_two.decided(&one_two));
+    assert!(early.decided(&one_two));
+    assert!(!MatchRules::default().decided(&one_two));
+    assert!(!final_round.decided(&one_two));
+
+    let result = first_to_two.result(&one_two);
+    assert!(result.winner == Some(PlayerNumber::Two));
+    assert!(result.rounds_won(PlayerNumber::Two) == 2);
+    assert!(result.ended_early);
+
+    let split = players_with_winners(
+        MultiplayerCount::Three,
+        &[PlayerNumber::One, PlayerNumber::Two],
+    );
+    assert!(!first_to_two.decided(&split));
+    assert!(!early.decided(&split));
+}
+
+#[test]
+fn sessions_report_the_result_when_finished() {
+    let first_to_two = MatchRules {
+        criterion: WinCriterion::FirstToTwo,
+        ..Default::default()
+    };
+
+    for seed in 0..20 {
+        let session = play(MatchRules::default(), seed);
+        let result = session.match_result().unwrap();
+        assert!(result.round_winners.len() == 3);
+        assert!(result.rounds_won.iter().sum::<usize>() == 3);
+        let most = *result.rounds_won.iter().max().unwrap();
+        assert!(result.rounds_won(result.winner.unwrap()) == most);
+        for player in session.players().as_vec() {
+            assert!(player.wins().len() == result.rounds_won(player.number()));
+        }
+
+        let session = play(first_to_two, seed);
+        let result = session.match_result().unwrap();
+        let winner = result.winner.unwrap();
+        if result.ended_early {
+            assert!(result.round_winners == [winner, winner]);
+        }
+        assert!(!session.history().entries().is_empty());
+    }
+}
+
+#[test]
+fn the_last_player_left_wins() {
+    let mut session: GameSession<StdRng> = GameSession::with_options(
+        MultiplayerCount::Three,
+        GameSessionOptions::with_stream_seed(0),
+    );
+    session
+        .forfeit(PlayerNumber::One, ForfeitedItems::Discard)
+        .unwrap();
+    session
+        .forfeit(PlayerNumber::Three, ForfeitedItems::Discard)
+        .unwrap();
+
+    assert!(session.round().is_none());
+    let result = session.match_result().unwrap();
+    assert!(result.winner == Some(PlayerNumber::Two));
+    assert!(result.round_winners == [PlayerNumber::Two]);
+    assert!(result.ended_early);
+}
@@ -0,0 +1,154 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    game_session::{GameSession, GameSessionOptions},
+    item::{Item, NotAdreneline, UnaryItem},
+    item_registry::{ItemBehavior, ItemContext, ItemLimits, ItemRegistry},
+    modifier::{
+        GameModifiers, Modifier, ModifierEffect, ModifierError, ModifierLifetime, ModifierScope,
+        Stacking,
+    },
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    shell::{ShellType, ShotgunDamage},
+    turn::{InvalidItemUseError, ItemUseResult},
+};
+use rand::rngs::StdRng;
+
+/// Every shot the user fires for two turns deals one more damage
+#[derive(Debug)]
+struct Powder;
+
+impl ItemBehavior for Powder {
+    fn name(&self) -> &str {
+        "Powder"
+    }
+
+    fn limits(&self) -> ItemLimits {
+        ItemLimits {
+            player: 1,
+            global: 2,
+        }
+    }
+
+    fn use_item(&self, context: ItemContext) -> Result<ItemUseResult, InvalidItemUseError> {
+        context
+            .modifiers
+            .add(powder(context.player.player_number()))
+            .unwrap();
+        Ok(ItemUseResult::Default)
+    }
+}
+
+fn powder(player_number: PlayerNumber) -> Modifier {
+    Modifier {
+        effect: ModifierEffect::ExtraDamage(1),
+        scope: ModifierScope::Player(player_number),
+        lifetime: ModifierLifetime::Turns(2),
+        stacking: Stacking::Stack,
+    }
+}
+
+#[test]
+fn stacking_follows_the_modifier() {
+    let mut modifiers = GameModifiers::default();
+    modifiers.add(Modifier::sawn_shotgun()).unwrap();
+    assert!(modifiers.add(Modifier::sawn_shotgun()) == Err(ModifierError::AlreadyActive));
+    assert!(modifiers.damage(ShellType::Live, PlayerNumber::One) == 2);
+    assert!(modifiers.damage(ShellType::Blank, PlayerNumber::One) == 0);
+
+    modifiers.add(powder(PlayerNumber::Two)).unwrap();
+    modifiers.add(powder(PlayerNumber::Two)).unwrap();
+    assert!(modifiers.damage(ShellType::Live, PlayerNumber::One) == 2);
+    assert!(modifiers.damage(ShellType::Live, PlayerNumber::Two) == 4);
+    assert!(modifiers.damage(ShellType::Slug, PlayerNumber::Two) == 5);
+    assert!(modifiers.for_player(PlayerNumber::Two).count() == 2);
+    assert!(modifiers.for_player(PlayerNumber::One).count() == 0);
+
+    modifiers.add(Modifier::inverted_turn_order()).unwrap();
+    assert!(modifiers.turn_order_inverted());
+    modifiers.add(Modifier::inverted_turn_order()).unwrap();
+    assert!(!modifiers.turn_order_inverted());
+
+    let mut reversal = Modifier::inverted_turn_order();
+    reversal.scope = ModifierScope::Player(PlayerNumber::Three);
+    modifiers.add(reversal).unwrap();
+    assert!(!modifiers.turn_order_inverted());
+    assert!(modifiers.turn_order_inverted_for(PlayerNumber::Three));
+    assert!(!modifiers.turn_order_inverted_for(PlayerNumber::One));
+
+    assert!(modifiers.remove(ModifierEffect::Sawn, ModifierScope::Table));
+    assert!(!modifiers.shotgun_sawn());
+    assert!(!modifiers.remove(ModifierEffect::Sawn, ModifierScope::Table));
+}
+
+#[test]
+fn item_modifiers_add_damage_and_expire() {
+    let mut item_registry = ItemRegistry::built_in();
+    let powder_id = item_registry.register(Powder);
+    let powder_item = Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Custom(powder_id)));
+
+    let mut boosted_hits = 0;
+    let mut lasted_a_turn = 0;
+    for seed in 0..30 {
+        let mut options = GameSessionOptions::with_stream_seed(seed);
+        options.item_registry = item_registry.clone();
+        let mut session: GameSession<StdRng> =
+            GameSession::with_options(MultiplayerCount::Two, options);
+
+        while let Some(round) = session.round() {
+            let actor = round.next_player();
+            let seat = &round.seats()[actor.seat_index()];
+            let active = round.game_modifiers().for_player(actor).count();
+            assert!(seat.create_view(round.game_modifiers()).modifiers.len() == active);
+
+            if active == 0 && seat.items().contains(&powder_item) {
+                session
+                    .submit(Action::UseUnaryItem(UnaryItem::Custom(powder_id)))
+                    .unwrap();
+                let round = session.round().unwrap();
+                assert!(round.game_modifiers().for_player(actor).count() == 1);
+                continue;
+            }
+
+            let target = round
+                .living_players()
+                .find(|other| other.player_number() != actor)
+                .unwrap()
+                .player_number();
+            let health = round.seats()[target.seat_index()]
+                .player()
+                .unwrap()
+                .health();
+            let round_number = round.number();
+
+            let outcome = session.submit(Action::Shoot(target)).unwrap();
+            let Some(round) = session
+                .round()
+                .filter(|round| round.number() == round_number)
+            else {
+                continue;
+            };
+
+            if let ShotgunDamage::RegularShot(false) = outcome.shot_result.unwrap().damage {
+                let damage = health
+                    - round.seats()[target.seat_index()]
+                        .player()
+                        .unwrap()
+                        .health();
+                assert!(damage == 1 + active as i32);
+                if active > 0 {
+                    boosted_hits += 1;
+                }
+            }
+
+            // two turns pass before the powder is gone
+            let remaining = round.game_modifiers().for_player(actor).count();
+            if active > 0 && remaining > 0 {
+                lasted_a_turn += 1;
+            }
+        }
+    }
+
+    assert!(boosted_hits > 0);
+    assert!(lasted_a_turn > 0);
+}
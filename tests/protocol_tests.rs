@@ -0,0 +1,166 @@
+#![cfg(feature = "protocol")]
+
+use std::fmt::Debug;
+
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    game_session::GameSession,
+    item::UnaryItem,
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    protocol::{
+        decode_binary, decode_json, encode_binary, encode_json, ActionNotification, ClientMessage,
+        ContinuationNotification, Event, ProtocolError, RedactedItemResult, RejectionReason,
+        ServerMessage, TableView, TurnOutcome, PROTOCOL_VERSION,
+    },
+    shell::ShellType,
+    turn::{InvalidItemUseError, ItemUseResult, LearnedShell},
+};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::{de::DeserializeOwned, Serialize};
+
+fn round_trip<TMessage>(message: TMessage)
+where
+    TMessage: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let json = encode_json(&message).unwrap();
+    let from_json: TMessage = decode_json(&json).unwrap();
+    assert_eq!(message, from_json);
+
+    let binary = encode_binary(&message).unwrap();
+    let from_binary: TMessage = decode_binary(&binary).unwrap();
+    assert_eq!(message, from_binary);
+
+    assert!(binary.len() < json.len());
+}
+
+#[test]
+fn client_messages_round_trip() {
+    round_trip(ClientMessage::Join {
+        game_id: 7,
+        player: PlayerNumber::Three,
+    });
+
+    for action in [
+        Action::Shoot(PlayerNumber::Two),
+        Action::UseUnaryItem(UnaryItem::MagnifyingGlass),
+        Action::UseAdreneline(PlayerNumber::Four, UnaryItem::Handsaw),
+        Action::UseJammer(PlayerNumber::One),
+        Action::UseAdrenelineThenJammer(PlayerNumber::Two, PlayerNumber::Three),
+    ] {
+        round_trip(ClientMessage::Act(action));
+    }
+}
+
+#[test]
+fn rejections_round_trip() {
+    for error in [
+        InvalidItemUseError::NoItem,
+        InvalidItemUseError::BadAdrenelineTarget,
+        InvalidItemUseError::DoubleSaw,
+        InvalidItemUseError::DoubleStun,
+        InvalidItemUseError::InvalidStunTarget,
+    ] {
+        let rejection = RejectionReason::from(error);
+        assert_eq!(rejection.to_string(), error.to_string());
+        round_trip(ServerMessage::Rejected(rejection));
+    }
+
+    round_trip(ServerMessage::Rejected(RejectionReason::NotYourTurn));
+}
+
+#[test]
+fn events_round_trip() {
+    let mut session: GameSession<StdRng> =
+        GameSession::new(MultiplayerCount::Four, StdRng::seed_from_u64(42));
+
+    let table = TableView::new(&session);
+    assert!(table.seats.len() == 4);
+    round_trip(ServerMessage::Joined {
+        game_id: 1,
+        player: PlayerNumber::One,
+        table,
+    });
+
+    for _ in 0..100 {
+        let actor = session.round().unwrap().next_player();
+        let turn_outcome = session
+            .with_turn(|turn| turn.shoot(actor), TurnOutcome::new)
+            .unwrap()
+            .unwrap();
+
+        let round_over = matches!(
+            turn_outcome.continuation,
+            ContinuationNotification::RoundEnds { .. }
+        );
+
+        round_trip(ServerMessage::Event(Event::ActionTaken(
+            ActionNotification {
+                actor,
+                action: Action::Shoot(actor),
+                item_result: None,
+                turn_outcome: Some(turn_outcome),
+                table: TableView::new(&session),
+            },
+        )));
+
+        if round_over {
+            break;
+        }
+    }
+
+    let learned_shell = ItemUseResult::LearnedShell(LearnedShell {
+        relative_index: 3,
+        shell_type: ShellType::Live,
+    });
+    assert!(RedactedItemResult::new(learned_shell.clone(), false) == RedactedItemResult::Hidden);
+
+    round_trip(ServerMessage::Event(Event::ActionTaken(
+        ActionNotification {
+            actor: PlayerNumber::Two,
+            action: Action::UseUnaryItem(UnaryItem::Phone),
+            item_result: Some(RedactedItemResult::new(learned_shell, true)),
+            turn_outcome: None,
+            table: TableView::new(&session),
+        },
+    )));
+
+    round_trip(ServerMessage::Event(Event::Forfeited {
+        player: PlayerNumber::Four,
+        table: TableView::new(&session),
+    }));
+
+    round_trip(ServerMessage::Event(Event::WiresCut {
+        player: PlayerNumber::Two,
+    }));
+}
+
+#[test]
+fn rejects_other_versions() {
+    let message = ClientMessage::Act(Action::Shoot(PlayerNumber::One));
+    let old_version = PROTOCOL_VERSION - 1;
+
+    let json = encode_json(&message).unwrap().replace(
+        &format!("\"version\":{}", PROTOCOL_VERSION),
+        &format!("\"version\":{}", old_version),
+    );
+    assert!(matches!(
+        decode_json::<ClientMessage>(&json),
+        Err(ProtocolError::UnsupportedVersion(version)) if version == old_version
+    ));
+
+    let mut binary = encode_binary(&message).unwrap();
+    binary[0] = old_version as u8;
+    assert!(matches!(
+        decode_binary::<ClientMessage>(&binary),
+        Err(ProtocolError::UnsupportedVersion(version)) if version == old_version
+    ));
+
+    assert!(matches!(
+        decode_json::<ClientMessage>(&format!(
+            "{{\"version\":{},\"message\":\"Nonsense\"}}",
+            PROTOCOL_VERSION
+        )),
+        Err(ProtocolError::Json(_))
+    ));
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "provably_fair")]
+
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    game_session::GameSession,
+    multiplayer_count::MultiplayerCount,
+    provably_fair::{verify, FairDealer, ObservedShell, VerificationError},
+    shell::{ShellType, ShotgunDamage},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn loadout_verifies_after_reveal() {
+    let mut session = GameSession::new(MultiplayerCount::Two, StdRng::seed_from_u64(3));
+    let mut dealer = FairDealer::new(StdRng::seed_from_u64(99));
+
+    let commitment = dealer.commit(&session).unwrap();
+    let observed_items = session.round().unwrap().dealt_items().clone();
+    let mut observed_shells = Vec::new();
+    loop {
+        let player = session.pending_decision().unwrap().player;
+        let outcome = session.submit(Action::Shoot(player)).unwrap();
+        let shot_result = outcome.shot_result.clone().unwrap();
+        observed_shells.push(ObservedShell {
+            shell_type: match shot_result.damage {
+                ShotgunDamage::Blank => ShellType::Blank,
+                _ => ShellType::Live,
+            },
+            inverted: false,
+        });
+
+        if outcome.new_loadout() {
+            break;
+        }
+    }
+
+    let reveal = dealer.reveal().unwrap();
+    assert!(verify(&commitment, &reveal, &observed_shells, &observed_items) == Ok(()));
+
+    let next_commitment = dealer.commit(&session);
+    if let Some(next_commitment) = next_commitment {
+        assert!(next_commitment.loadout_index == 1);
+        assert!(next_commitment.digest != commitment.digest);
+    }
+}
+
+#[test]
+fn tampering_is_detected() {
+    let session = GameSession::new(MultiplayerCount::Two, StdRng::seed_from_u64(5));
+    let mut dealer = FairDealer::new(StdRng::seed_from_u64(99));
+
+    let commitment = dealer.commit(&session).unwrap();
+    let reveal = dealer.reveal().unwrap();
+    let items = reveal.dealt_items.clone();
+
+    let first_shell = ObservedShell {
+        shell_type: reveal.shells[0],
+        inverted: false,
+    };
+    assert!(verify(&commitment, &reveal, &[first_shell], &items) == Ok(()));
+
+    let inverted_shell = ObservedShell {
+        inverted: true,
+        ..first_shell
+    };
+    assert!(
+        verify(&commitment, &reveal, &[inverted_shell], &items)
+            == Err(VerificationError::ShellMismatch(0))
+    );
+
+    let mut tampered = reveal.clone();
+    tampered.shells[0] = tampered.shells[0].inverted();
+    assert!(verify(&commitment, &tampered, &[], &items) == Err(VerificationError::DigestMismatch));
+
+    let mut resalted = reveal.clone();
+    resalted.salt[0] ^= 1;
+    assert!(verify(&commitment, &resalted, &[], &items) == Err(VerificationError::DigestMismatch));
+
+    let mut wrong_items = items.clone();
+    wrong_items.pop();
+    if !items.is_empty() {
+        assert!(
+            verify(&commitment, &reveal, &[], &wrong_items) == Err(VerificationError::ItemMismatch)
+        );
+    }
+}
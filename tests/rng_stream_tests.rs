@@ -0,0 +1,82 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    decision::TurnEnd,
+    game_session::{GameSession, GameSessionOptions},
+    item::{Item, NotAdreneline, UnaryItem},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    rng_streams::{RngStream, RngStreams},
+    shell::ShellType,
+};
+use rand::{rngs::StdRng, RngCore};
+
+fn shell_types(session: &GameSession<StdRng>) -> Vec<ShellType> {
+    session
+        .round()
+        .unwrap()
+        .shells()
+        .iter()
+        .map(|shell| shell.shell_type())
+        .collect()
+}
+
+/// Everyone shoots themselves until the loadout is over
+fn finish_loadout(session: &mut GameSession<StdRng>) {
+    loop {
+        let player = session.pending_decision().unwrap().player;
+        let outcome = session.submit(Action::Shoot(player)).unwrap();
+        if outcome.new_loadout() {
+            assert!(!matches!(outcome.turn_end, Some(TurnEnd::RoundEnds { .. })));
+            return;
+        }
+    }
+}
+
+#[test]
+fn streams_are_independent() {
+    let mut first: RngStreams<StdRng> = RngStreams::from_seed(7);
+    let mut second: RngStreams<StdRng> = RngStreams::from_seed(7);
+
+    first.get(RngStream::Phone).next_u32();
+    first.get(RngStream::Items(PlayerNumber::Two)).next_u32();
+
+    assert!(first.get(RngStream::Shells).next_u64() == second.get(RngStream::Shells).next_u64());
+    assert!(
+        first.get(RngStream::Items(PlayerNumber::One)).next_u64()
+            == second.get(RngStream::Items(PlayerNumber::One)).next_u64()
+    );
+    assert!(first.get(RngStream::Health).next_u64() != first.get(RngStream::Loadout).next_u64());
+}
+
+#[test]
+fn phone_use_does_not_shift_later_shells() {
+    let phone = Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Phone));
+    let seed = (0..1000)
+        .find(|seed| {
+            let session: GameSession<StdRng> = GameSession::with_options(
+                MultiplayerCount::Four,
+                GameSessionOptions::with_stream_seed(*seed),
+            );
+            let decision = session.pending_decision().unwrap();
+            decision.items.contains(&phone) && decision.shell_count > 2
+        })
+        .unwrap();
+
+    let mut with_phone: GameSession<StdRng> = GameSession::with_options(
+        MultiplayerCount::Four,
+        GameSessionOptions::with_stream_seed(seed),
+    );
+    let mut without_phone = with_phone.clone();
+
+    with_phone
+        .submit(Action::UseUnaryItem(UnaryItem::Phone))
+        .unwrap();
+
+    finish_loadout(&mut with_phone);
+    finish_loadout(&mut without_phone);
+
+    let with_phone_round = with_phone.round().unwrap();
+    let without_phone_round = without_phone.round().unwrap();
+    assert!(with_phone_round.loadout() == without_phone_round.loadout());
+    assert!(shell_types(&with_phone) == shell_types(&without_phone));
+}
@@ -0,0 +1,194 @@
+#![cfg(feature = "scripting")]
+
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    decision::{ActionOutcome, SubmitError},
+    game_session::{GameSession, GameSessionOptions},
+    item::{Item, NotAdreneline, UnaryItem},
+    item_registry::ItemRegistry,
+    multiplayer_count::MultiplayerCount,
+    round_rules::{Healing, RoundRules},
+    scripting::{ScriptEngine, ScriptError},
+    shell::ShellType,
+    turn::InvalidItemUseError,
+};
+use rand::rngs::StdRng;
+
+const BEER: Item = Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Beer));
+const MAGNIFYING_GLASS: Item =
+    Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::MagnifyingGlass));
+
+const BUDGET: u64 = 10_000;
+
+fn script_path(name: &str) -> String {
+    format!("{}/tests/scripts/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn scripted_registry() -> ItemRegistry {
+    let engine = ScriptEngine::new(BUDGET);
+    let mut item_registry = ItemRegistry::built_in();
+    assert!(item_registry.replace(BEER, engine.load_item(script_path("beer.rhai")).unwrap()));
+    assert!(item_registry.replace(
+        MAGNIFYING_GLASS,
+        engine
+            .load_item(script_path("magnifying_glass.rhai"))
+            .unwrap()
+    ));
+    item_registry
+}
+
+/// Drinks and looks whenever possible, otherwise shoots the next living player. Returns every outcome with the shells left after it
+fn play(item_registry: ItemRegistry, seed: u64) -> Vec<(ActionOutcome, Vec<ShellType>)> {
+    let mut options = GameSessionOptions::with_stream_seed(seed);
+    options.item_registry = item_registry;
+    let mut session: GameSession<StdRng> =
+        GameSession::with_options(MultiplayerCount::Three, options);
+
+    let mut outcomes = Vec::new();
+    while let Some(round) = session.round() {
+        let actor = round.next_player();
+        let items = round.seats()[actor.seat_index()].items();
+        let action = if items.contains(&MAGNIFYING_GLASS) {
+            Action::UseUnaryItem(UnaryItem::MagnifyingGlass)
+        } else if items.contains(&BEER) {
+            Action::UseUnaryItem(UnaryItem::Beer)
+        } else {
+            Action::Shoot(
+                round
+                    .living_players()
+                    .find(|seat| seat.player_number() != actor)
+                    .unwrap()
+                    .player_number(),
+            )
+        };
+
+        let outcome = session.submit(action).unwrap();
+        let shells = session
+            .round()
+            .map(|round| {
+                round
+                    .shells()
+                    .iter()
+                    .map(|shell| shell.shell_type())
+                    .collect()
+            })
+            .unwrap_or_default();
+        outcomes.push((outcome, shells));
+    }
+
+    outcomes
+}
+
+#[test]
+fn scripted_beer_and_magnifying_glass_match_native() {
+    let scripted_registry = scripted_registry();
+    assert!(!scripted_registry.is_built_in());
+    assert!(scripted_registry.behavior(BEER).unwrap().name() == "Beer");
+
+    for seed in 0..30 {
+        let native = play(ItemRegistry::built_in(), seed);
+        let scripted = play(scripted_registry.clone(), seed);
+        assert!(native
+            .iter()
+            .any(|(outcome, _)| outcome.item_result.is_some()));
+        assert!(native == scripted);
+    }
+}
+
+#[test]
+fn scripts_over_budget_fail_without_using_the_item() {
+    let engine = ScriptEngine::new(BUDGET);
+    let looping_beer = engine
+        .item_from_source(
+            r#"
+            const NAME = "Bottomless Beer";
+            const PLAYER_LIMIT = 8;
+            const GLOBAL_LIMIT = 32;
+
+            fn use_item(round) {
+                loop {
+                    round.shell_count();
+                }
+            }
+            "#,
+        )
+        .unwrap();
+    let mut item_registry = ItemRegistry::built_in();
+    item_registry.replace(BEER, looping_beer);
+
+    let mut checked = false;
+    for seed in 0..20 {
+        let mut options = GameSessionOptions::with_stream_seed(seed);
+        options.item_registry = item_registry.clone();
+        let mut session: GameSession<StdRng> =
+            GameSession::with_options(MultiplayerCount::Two, options);
+
+        let round = session.round().unwrap();
+        let actor = round.next_player();
+        let beers = |session: &GameSession<StdRng>| {
+            session.round().unwrap().seats()[actor.seat_index()]
+                .items()
+                .iter()
+                .filter(|item| **item == BEER)
+                .count()
+        };
+        let shell_count = round.shells().len();
+        let held = beers(&session);
+        if held == 0 {
+            continue;
+        }
+
+        let result = session.submit(Action::UseUnaryItem(UnaryItem::Beer));
+        assert!(
+            result
+                == Err(SubmitError::InvalidItemUse(
+                    InvalidItemUseError::ScriptFailed
+                ))
+        );
+        assert!(beers(&session) == held);
+        assert!(session.round().unwrap().shells().len() == shell_count);
+        checked = true;
+    }
+
+    assert!(checked);
+}
+
+#[test]
+fn item_scripts_need_their_constants_and_function() {
+    let engine = ScriptEngine::new(BUDGET);
+    assert!(matches!(
+        engine.item_from_source("const NAME = \"Nothing\";"),
+        Err(ScriptError::Missing("use_item(round)"))
+    ));
+    assert!(matches!(
+        engine.item_from_source("const NAME = \"Nothing\"; fn use_item(round) {}"),
+        Err(ScriptError::Missing("PLAYER_LIMIT"))
+    ));
+    assert!(matches!(
+        engine.item_from_source(
+            "const NAME = \"Nothing\"; const PLAYER_LIMIT = -1; const GLOBAL_LIMIT = 1; fn use_item(round) {}"
+        ),
+        Err(ScriptError::OutOfRange("PLAYER_LIMIT"))
+    ));
+}
+
+#[test]
+fn rule_scripts_change_the_rules() {
+    let engine = ScriptEngine::new(BUDGET);
+    let rules = engine
+        .load_rules(script_path("rules.rhai"), RoundRules::default())
+        .unwrap();
+    assert!(
+        rules
+            == RoundRules::default()
+                .with_item_capacity(4)
+                .with_sudden_death(2)
+                .with_handcuffs()
+                .with_healing(Healing::Overheal(1))
+    );
+
+    assert!(matches!(
+        engine.rules_from_source("rules.item_capacity = -1;", RoundRules::default()),
+        Err(ScriptError::Eval(_))
+    ));
+}
@@ -0,0 +1,192 @@
+#![cfg(feature = "server")]
+
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    game_session::GameSession,
+    item::{Item, NotAdreneline, UnaryItem},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    protocol::{Event, RejectionReason, ServerMessage, TableView},
+    server::{GameClient, GameServer},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn expect_update(client: &mut GameClient) -> TableView {
+    match client.recv().unwrap() {
+        ServerMessage::Event(Event::ActionTaken(notification)) => notification.table,
+        message => panic!("Expected update, got {:?}", message),
+    }
+}
+
+fn expect_rejection(client: &mut GameClient) -> RejectionReason {
+    match client.recv().unwrap() {
+        ServerMessage::Rejected(rejection) => rejection,
+        message => panic!("Expected rejection, got {:?}", message),
+    }
+}
+
+#[test]
+fn two_clients_play_full_game() {
+    let server: GameServer<StdRng> = GameServer::start("127.0.0.1:0").unwrap();
+    let game_id = server.host_game(GameSession::new(
+        MultiplayerCount::Two,
+        StdRng::seed_from_u64(42),
+    ));
+
+    let mut client_one = GameClient::connect(server.local_addr()).unwrap();
+    let mut client_two = GameClient::connect(server.local_addr()).unwrap();
+
+    let mut table = match client_one.join(game_id, PlayerNumber::One).unwrap() {
+        ServerMessage::Joined { table, .. } => table,
+        message => panic!("Expected join, got {:?}", message),
+    };
+    assert!(matches!(
+        client_two.join(game_id, PlayerNumber::Two).unwrap(),
+        ServerMessage::Joined { .. }
+    ));
+
+    for _ in 0..300 {
+        let next_player = match table.next_player {
+            Some(next_player) => next_player,
+            None => break,
+        };
+
+        let (actor, other, target) = match next_player {
+            PlayerNumber::One => (&mut client_one, &mut client_two, PlayerNumber::Two),
+            PlayerNumber::Two => (&mut client_two, &mut client_one, PlayerNumber::One),
+            PlayerNumber::Three | PlayerNumber::Four => panic!("Unexpected player"),
+        };
+
+        other.act(Action::Shoot(target)).unwrap();
+        assert!(matches!(
+            expect_rejection(other),
+            RejectionReason::NotYourTurn
+        ));
+
+        actor.act(Action::Shoot(target)).unwrap();
+        table = expect_update(actor);
+        expect_update(other);
+    }
+
+    assert!(table.round_number.is_none());
+    assert!(server
+        .with_session(game_id, |session| session.round().is_none())
+        .unwrap());
+
+    client_one.act(Action::Shoot(PlayerNumber::Two)).unwrap();
+    assert!(matches!(
+        expect_rejection(&mut client_one),
+        RejectionReason::GameOver
+    ));
+
+    server.shutdown();
+}
+
+#[test]
+fn rejects_invalid_requests() {
+    let server: GameServer<StdRng> = GameServer::start("127.0.0.1:0").unwrap();
+    let game_id = server.host_game(GameSession::new(
+        MultiplayerCount::Two,
+        StdRng::seed_from_u64(42),
+    ));
+
+    let mut client = GameClient::connect(server.local_addr()).unwrap();
+    client.act(Action::Shoot(PlayerNumber::Two)).unwrap();
+    assert!(matches!(
+        expect_rejection(&mut client),
+        RejectionReason::NotJoined
+    ));
+
+    assert!(matches!(
+        client.join(game_id + 1, PlayerNumber::One).unwrap(),
+        ServerMessage::Rejected(RejectionReason::NoGame)
+    ));
+    assert!(matches!(
+        client.join(game_id, PlayerNumber::Three).unwrap(),
+        ServerMessage::Rejected(RejectionReason::NoPlayer)
+    ));
+
+    let table = match client.join(game_id, PlayerNumber::One).unwrap() {
+        ServerMessage::Joined { table, .. } => table,
+        message => panic!("Expected join, got {:?}", message),
+    };
+
+    let mut intruder = GameClient::connect(server.local_addr()).unwrap();
+    assert!(matches!(
+        intruder.join(game_id, PlayerNumber::One).unwrap(),
+        ServerMessage::Rejected(RejectionReason::SeatTaken)
+    ));
+
+    client.act(Action::Shoot(PlayerNumber::Four)).unwrap();
+    assert!(matches!(
+        expect_rejection(&mut client),
+        RejectionReason::InvalidTarget
+    ));
+
+    let missing_item = [
+        UnaryItem::Remote,
+        UnaryItem::Phone,
+        UnaryItem::Inverter,
+        UnaryItem::MagnifyingGlass,
+        UnaryItem::Cigarettes,
+        UnaryItem::Handsaw,
+        UnaryItem::Beer,
+    ]
+    .into_iter()
+    .find(|unary_item| {
+        !table.seats[0]
+            .view
+            .items
+            .contains(&Item::NotAdreneline(NotAdreneline::UnaryItem(*unary_item)))
+    })
+    .unwrap();
+
+    client.act(Action::UseUnaryItem(missing_item)).unwrap();
+    assert!(matches!(
+        expect_rejection(&mut client),
+        RejectionReason::NoItem
+    ));
+
+    server.shutdown();
+}
+
+#[test]
+fn disconnect_forfeits() {
+    let server: GameServer<StdRng> = GameServer::start("127.0.0.1:0").unwrap();
+    let game_id = server.host_game(GameSession::new(
+        MultiplayerCount::Two,
+        StdRng::seed_from_u64(42),
+    ));
+
+    let mut client_one = GameClient::connect(server.local_addr()).unwrap();
+    client_one.join(game_id, PlayerNumber::One).unwrap();
+
+    {
+        let mut client_two = GameClient::connect(server.local_addr()).unwrap();
+        client_two.join(game_id, PlayerNumber::Two).unwrap();
+    }
+
+    match client_one.recv().unwrap() {
+        ServerMessage::Event(Event::Forfeited { player, table }) => {
+            assert!(player == PlayerNumber::Two);
+            assert!(table.round_number.is_none());
+        }
+        message => panic!("Expected forfeit, got {:?}", message),
+    }
+
+    server.shutdown();
+}
+
+#[test]
+fn shutdown_closes_clients_that_never_joined() {
+    let server: GameServer<StdRng> = GameServer::start("127.0.0.1:0").unwrap();
+
+    let mut client = GameClient::connect(server.local_addr()).unwrap();
+    client
+        .act(Action::UseUnaryItem(UnaryItem::MagnifyingGlass))
+        .unwrap();
+    assert!(expect_rejection(&mut client) == RejectionReason::NotJoined);
+
+    server.shutdown();
+    assert!(client.recv().is_err());
+}
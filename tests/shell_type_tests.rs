@@ -0,0 +1,320 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    game_session::{GameSession, GameSessionOptions},
+    item::{Item, NotAdreneline, UnaryItem},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    round_player::{HandcuffState, RoundPlayer, StunState},
+    round_rules::RoundRules,
+    shell::{ShellType, ShotgunDamage, SpecialShells, SLUG_DAMAGE},
+    shell_knowledge::ShellKnowledge,
+    turn::{ItemUseResult, LearnedShell},
+};
+use rand::rngs::StdRng;
+
+const JAMMER: Item = Item::NotAdreneline(NotAdreneline::Jammer);
+const HANDCUFFS: Item = Item::NotAdreneline(NotAdreneline::Handcuffs);
+const INVERTER: Item = Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Inverter));
+const MAGNIFYING_GLASS: Item =
+    Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::MagnifyingGlass));
+
+const PLAYERS: [PlayerNumber; 3] = [PlayerNumber::One, PlayerNumber::Two, PlayerNumber::Three];
+
+const ALL_SHELL_TYPES: [ShellType; 5] = [
+    ShellType::Live,
+    ShellType::Blank,
+    ShellType::Slug,
+    ShellType::Incendiary,
+    ShellType::Dud,
+];
+
+#[derive(Debug, Default)]
+struct Seen {
+    slugs: usize,
+    burns: usize,
+    burned_out: usize,
+    jams: usize,
+    duds_looked_at: usize,
+    specials_inverted: usize,
+}
+
+fn session(seed: u64) -> GameSession<StdRng> {
+    let mut options = GameSessionOptions::with_stream_seed(seed);
+    options.rules = RoundRules::default().with_special_shells(SpecialShells {
+        slug: 1,
+        incendiary: 1,
+        dud: 1,
+    });
+    GameSession::with_options(MultiplayerCount::Three, options)
+}
+
+fn count(session: &GameSession<StdRng>, shell_type: ShellType) -> usize {
+    session
+        .round()
+        .unwrap()
+        .shells()
+        .iter()
+        .filter(|shell| shell.shell_type() == shell_type)
+        .count()
+}
+
+fn health_and_burn(session: &GameSession<StdRng>, player_number: PlayerNumber) -> (i32, u8) {
+    session.round().unwrap().seats()[player_number.seat_index()]
+        .player()
+        .map(|player| (player.health(), player.burn()))
+        .unwrap_or((0, 0))
+}
+
+/// Looks at duds, inverts special shells and otherwise shoots, at itself when the dud is next
+fn play(seed: u64, seen: &mut Seen) {
+    let mut session = session(seed);
+    let mut knowledge = ShellKnowledge::new(session.round().unwrap().loadout());
+    let mut fresh_loadout = true;
+
+    while let Some(round) = session.round() {
+        if fresh_loadout {
+            let loadout = round.loadout();
+            assert!(count(&session, ShellType::Live) == loadout.initial_live_rounds);
+            assert!(count(&session, ShellType::Blank) == loadout.initial_blank_rounds);
+            for shell_type in [ShellType::Slug, ShellType::Incendiary, ShellType::Dud] {
+                assert!(count(&session, shell_type) == loadout.special_rounds.get(shell_type));
+            }
+        }
+
+        if knowledge.inversions() == 0 {
+            for shell_type in ALL_SHELL_TYPES {
+                assert!(count(&session, shell_type) <= knowledge.remaining_rounds(shell_type));
+            }
+        }
+
+        let round_number = round.number();
+        let actor = round.next_player();
+        let items = round.seats()[actor.seat_index()].items();
+        let next_shell = round.shells()[0].shell_type();
+        let action = if next_shell == ShellType::Dud && items.contains(&MAGNIFYING_GLASS) {
+            Action::UseUnaryItem(UnaryItem::MagnifyingGlass)
+        } else if next_shell != ShellType::Live
+            && next_shell != ShellType::Blank
+            && items.contains(&INVERTER)
+        {
+            Action::UseUnaryItem(UnaryItem::Inverter)
+        } else if next_shell == ShellType::Dud {
+            Action::Shoot(actor)
+        } else {
+            Action::Shoot(
+                round
+                    .living_players()
+                    .find(|seat| seat.player_number() != actor)
+                    .unwrap()
+                    .player_number(),
+            )
+        };
+
+        let target = match action {
+            Action::Shoot(target) => target,
+            _ => actor,
+        };
+        let (target_health, target_burn) = health_and_burn(&session, target);
+        let burns_before: Vec<u8> = PLAYERS
+            .iter()
+            .map(|player_number| health_and_burn(&session, *player_number).1)
+            .collect();
+
+        let outcome = session.submit(action).unwrap();
+        knowledge.record_outcome(&outcome, session.round().map(|round| round.loadout()));
+        fresh_loadout = outcome.new_loadout();
+        let round_ended = session.round().map(|round| round.number()) != Some(round_number);
+
+        if let Some(ItemUseResult::LearnedShell(learned_shell)) = &outcome.item_result {
+            assert!(
+                *learned_shell
+                    == LearnedShell {
+                        relative_index: 0,
+                        shell_type: ShellType::Live
+                    }
+            );
+            seen.duds_looked_at += 1;
+        }
+        if action == Action::UseUnaryItem(UnaryItem::Inverter) {
+            assert!(session.round().unwrap().shells()[0].shell_type() == ShellType::Blank);
+            seen.specials_inverted += 1;
+        }
+
+        // burns land as the turn is passed on, including any the shot just lit
+        for burn in &outcome.burns {
+            let ignited = outcome.shot_result.as_ref().is_some_and(|shot_result| {
+                shot_result.damage == ShotgunDamage::Incendiary
+                    && shot_result.target_player == burn.player
+            });
+            assert!(burn.damage == burns_before[burn.player.seat_index()] + u8::from(ignited));
+            assert!(burn.killed || health_and_burn(&session, burn.player).1 == 0 || round_ended);
+            seen.burned_out += 1;
+        }
+
+        let Some(shot_result) = &outcome.shot_result else {
+            continue;
+        };
+
+        match shot_result.damage {
+            ShotgunDamage::Slug(killed) => {
+                assert!(next_shell == ShellType::Slug);
+                if !killed && !round_ended {
+                    let (health, _) = health_and_burn(&session, target);
+                    let burned: u8 = outcome
+                        .burns
+                        .iter()
+                        .filter(|burn| burn.player == target)
+                        .map(|burn| burn.damage)
+                        .sum();
+                    assert!(health == target_health - i32::from(SLUG_DAMAGE + burned));
+                }
+                seen.slugs += 1;
+            }
+            ShotgunDamage::Incendiary => {
+                assert!(next_shell == ShellType::Incendiary);
+                let burned = outcome.burns.iter().any(|burn| burn.player == target);
+                if !round_ended {
+                    let round = session.round().unwrap();
+                    if let Some(player) = round.seats()[target.seat_index()].player() {
+                        assert!(player.burn() == if burned { 0 } else { target_burn + 1 });
+                    }
+                }
+                seen.burns += 1;
+            }
+            ShotgunDamage::Jammed => {
+                assert!(next_shell == ShellType::Dud);
+                if !round_ended {
+                    assert!(session.round().unwrap().next_player() != actor);
+                }
+                seen.jams += 1;
+            }
+            ShotgunDamage::Blank | ShotgunDamage::RegularShot(_) | ShotgunDamage::SawedShot(_) => {
+                assert!(next_shell == ShellType::Blank || next_shell == ShellType::Live);
+            }
+        }
+    }
+}
+
+#[test]
+fn special_shells_follow_their_rules() {
+    let mut seen = Seen::default();
+    for seed in 0..40 {
+        play(seed, &mut seen);
+    }
+
+    assert!(seen.slugs > 0);
+    assert!(seen.burns > 0);
+    assert!(seen.burned_out > 0);
+    assert!(seen.jams > 0);
+    assert!(seen.duds_looked_at > 0);
+    assert!(seen.specials_inverted > 0);
+}
+
+#[test]
+fn shell_types_deal_their_damage() {
+    assert!(ShellType::Slug.damage(false) == SLUG_DAMAGE);
+    assert!(ShellType::Slug.damage(true) == SLUG_DAMAGE);
+    assert!(ShellType::Incendiary.damage(true) == 2);
+    assert!(ShellType::Dud.damage(true) == 0);
+
+    assert!(ShellType::Dud.visible_type() == ShellType::Live);
+    for shell_type in [
+        ShellType::Live,
+        ShellType::Slug,
+        ShellType::Incendiary,
+        ShellType::Dud,
+    ] {
+        assert!(shell_type.inverted() == ShellType::Blank);
+    }
+    assert!(ShellType::Blank.inverted() == ShellType::Live);
+}
+
+#[test]
+fn special_shells_are_off_by_default() {
+    for seed in 0..10 {
+        let mut options = GameSessionOptions::with_stream_seed(seed);
+        options.rules = RoundRules::default();
+        let session: GameSession<StdRng> =
+            GameSession::with_options(MultiplayerCount::Two, options);
+        let loadout = session.round().unwrap().loadout();
+        assert!(loadout.special_rounds == SpecialShells::default());
+        assert!(
+            count(&session, ShellType::Live) + count(&session, ShellType::Blank)
+                == session.round().unwrap().shells().len()
+        );
+    }
+}
+
+/// Jams or cuffs burning players and shoots otherwise, burns have to land on the turns they sit out
+#[test]
+fn burns_land_on_skipped_turns() {
+    let mut stunned_burns = 0;
+    let mut cuffed_burns = 0;
+    for seed in 0..100 {
+        let mut options = GameSessionOptions::with_stream_seed(seed);
+        options.rules = RoundRules::default()
+            .with_handcuffs()
+            .with_special_shells(SpecialShells {
+                incendiary: 2,
+                ..Default::default()
+            });
+        let mut session: GameSession<StdRng> =
+            GameSession::with_options(MultiplayerCount::Four, options);
+
+        while let Some(round) = session.round() {
+            let actor = round.next_player();
+            let items = round.seats()[actor.seat_index()].items();
+            let others: Vec<&RoundPlayer> = round
+                .living_players()
+                .filter_map(|seat| seat.player())
+                .filter(|player| player.player_number() != actor)
+                .collect();
+            let jam_target = others
+                .iter()
+                .find(|player| player.burn() > 0 && player.stun_state() == StunState::Unstunned);
+            let cuff_target = others.iter().find(|player| {
+                player.burn() > 0
+                    && player.stun_state() == StunState::Unstunned
+                    && player.handcuff_state() == HandcuffState::Free
+            });
+            let action = match (jam_target, cuff_target) {
+                (Some(target), _) if items.contains(&JAMMER) => {
+                    Action::UseJammer(target.player_number())
+                }
+                (_, Some(target)) if items.contains(&HANDCUFFS) => {
+                    Action::UseHandcuffs(target.player_number())
+                }
+                _ => Action::Shoot(others[0].player_number()),
+            };
+
+            let before: Vec<Option<RoundPlayer>> = round
+                .seats()
+                .iter()
+                .map(|seat| seat.player().cloned())
+                .collect();
+            let outcome = session.submit(action).unwrap();
+            let next_player = session.round().map(|round| round.next_player());
+
+            for burn in &outcome.burns {
+                let player = before[burn.player.seat_index()].as_ref().unwrap();
+                if player.stun_state() == StunState::Stunned {
+                    stunned_burns += 1;
+                } else if player.handcuff_state() == HandcuffState::Cuffed {
+                    cuffed_burns += 1;
+                } else {
+                    continue;
+                }
+
+                assert!(next_player != Some(burn.player));
+                if let Some(round) = session.round() {
+                    if let Some(player) = round.seats()[burn.player.seat_index()].player() {
+                        assert!(player.burn() == 0);
+                    }
+                }
+            }
+        }
+    }
+
+    assert!(stunned_burns > 0);
+    assert!(cuffed_burns > 0);
+}
@@ -0,0 +1,63 @@
+use buckshot_roulette_gameplay_engine::{
+    game_session::GameSession,
+    multiplayer_count::MultiplayerCount,
+    spectator::{SpectatorFeed, SpectatorPolicy},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn caster_sees_shells_public_does_not() {
+    let session: GameSession<StdRng> =
+        GameSession::new(MultiplayerCount::Three, StdRng::seed_from_u64(42));
+
+    let mut caster_feed = SpectatorFeed::new(SpectatorPolicy::Caster, 0);
+    let mut public_feed = SpectatorFeed::new(SpectatorPolicy::Public, 0);
+
+    assert!(caster_feed.observe(&session).len() == 1);
+    assert!(public_feed.observe(&session).len() == 1);
+
+    let round = session.round().unwrap();
+    let caster_frame = caster_feed.latest().unwrap();
+    let shells = caster_frame.shells.as_ref().unwrap();
+    assert!(shells.len() == round.shells().len());
+    assert!(shells
+        .iter()
+        .zip(round.shells())
+        .all(|(shell_type, shell)| *shell_type == shell.shell_type()));
+    assert!(caster_frame.dealt_items.as_ref() == Some(round.dealt_items()));
+
+    let public_frame = public_feed.latest().unwrap();
+    assert!(public_frame.shells.is_none());
+    assert!(public_frame.dealt_items.is_none());
+    assert!(public_frame.seats == caster_frame.seats);
+    assert!(public_frame.shell_count == caster_frame.shell_count);
+}
+
+#[test]
+fn delayed_feed_lags_behind() {
+    let mut session: GameSession<StdRng> =
+        GameSession::new(MultiplayerCount::Two, StdRng::seed_from_u64(42));
+
+    let mut feed = SpectatorFeed::new(SpectatorPolicy::Public, 2);
+    assert!(feed.observe(&session).is_empty());
+    assert!(feed.latest().is_none());
+
+    for turn in 1..=4 {
+        let current_player = session.round().unwrap().next_player();
+        session
+            .with_turn(|turn| turn.shoot(current_player), |_| ())
+            .unwrap();
+
+        let released_frames = feed.observe(&session);
+        if turn < 2 {
+            assert!(released_frames.is_empty());
+        } else {
+            assert!(released_frames.len() == 1);
+            assert!(feed.latest().unwrap().turn_index == turn - 2);
+        }
+    }
+
+    let flushed_frames = feed.flush();
+    assert!(flushed_frames.len() == 2);
+    assert!(feed.latest().unwrap().turn_index == session.turns_taken());
+}
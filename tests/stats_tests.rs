@@ -0,0 +1,178 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    decision::ActionOutcome,
+    game_session::{GameSession, GameSessionOptions},
+    item::{Item, NotAdreneline, ALL_UNARY_ITEMS},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    round_number::RoundNumber,
+    round_rules::RoundRules,
+    shell::{ShotgunDamage, SpecialShells},
+    stats::{PlayerStats, StatsCollector},
+};
+use rand::rngs::StdRng;
+
+const PLAYERS: [PlayerNumber; 3] = [PlayerNumber::One, PlayerNumber::Two, PlayerNumber::Three];
+
+/// Steals and uses every item that works, then shoots the next living player. Observes after every action
+fn play(seed: u64, collector: &mut StatsCollector) -> (GameSession<StdRng>, Vec<ActionOutcome>) {
+    let mut session: GameSession<StdRng> = GameSession::with_options(
+        MultiplayerCount::Three,
+        GameSessionOptions::with_stream_seed(seed),
+    );
+
+    let mut outcomes = Vec::new();
+    while let Some(decision) = session.pending_decision() {
+        let steals = decision.other_seats.iter().flat_map(|seat| {
+            ALL_UNARY_ITEMS
+                .iter()
+                .map(move |unary_item| Action::UseAdreneline(seat.player_number, *unary_item))
+        });
+        let uses = ALL_UNARY_ITEMS
+            .iter()
+            .filter(|unary_item| {
+                decision
+                    .items
+                    .contains(&Item::NotAdreneline(NotAdreneline::UnaryItem(**unary_item)))
+            })
+            .map(|unary_item| Action::UseUnaryItem(*unary_item));
+        let item_outcome = steals
+            .chain(uses)
+            .find_map(|action| session.submit(action).ok());
+
+        let outcome = match item_outcome {
+            Some(outcome) => outcome,
+            None => {
+                let target = decision
+                    .other_seats
+                    .iter()
+                    .find(|seat| seat.stun_state.is_some())
+                    .unwrap()
+                    .player_number;
+                session.submit(Action::Shoot(target)).unwrap()
+            }
+        };
+        outcomes.push(outcome);
+        collector.observe(session.history());
+    }
+    collector.end_match();
+
+    (session, outcomes)
+}
+
+fn sum<F>(collector: &StatsCollector, stat: F) -> u64
+where
+    F: Fn(&PlayerStats) -> u64,
+{
+    PLAYERS
+        .iter()
+        .map(|player_number| stat(collector.player(*player_number)))
+        .sum()
+}
+
+#[test]
+fn stats_match_the_outcomes() {
+    let mut stolen = 0;
+    for seed in 0..20 {
+        let mut collector = StatsCollector::default();
+        let (session, outcomes) = play(seed, &mut collector);
+
+        let shots = outcomes
+            .iter()
+            .filter(|outcome| outcome.shot_result.is_some())
+            .count() as u64;
+        assert!(sum(&collector, PlayerStats::shots) == shots);
+        assert!(sum(&collector, |stats| stats.rounds_won) == 3);
+        assert!(sum(&collector, |stats| stats.first_deaths) == 3);
+        assert!(
+            sum(&collector, |stats| stats.damage_dealt.total())
+                <= sum(&collector, |stats| stats.damage_taken.total())
+        );
+        assert!(sum(&collector, |stats| stats.kills) <= sum(&collector, |stats| stats.deaths));
+        assert!(
+            sum(&collector, |stats| stats.stuns_applied)
+                == sum(&collector, |stats| stats.stuns_received)
+        );
+
+        for player in session.players().as_vec() {
+            let stats = collector.player(player.number());
+            assert!(stats.rounds_won == player.wins().len() as u64);
+            assert!(stats.health_at_win >= stats.rounds_won);
+
+            let actions = outcomes
+                .iter()
+                .filter(|outcome| outcome.actor == player.number() && outcome.item_result.is_some())
+                .count() as u64;
+            let steals = stats.items_stolen.values().sum::<u64>();
+            assert!(stats.items_used.values().sum::<u64>() == actions + steals);
+            assert!(stats.stolen_from.values().sum::<u64>() == steals);
+            assert!(!stats.stolen_from.contains_key(&player.number()));
+            stolen += steals;
+
+            let mut rounds = PlayerStats::default();
+            for round_number in [RoundNumber::One, RoundNumber::Two, RoundNumber::Three] {
+                rounds.merge(collector.round(round_number, player.number()));
+            }
+            assert!(rounds == *stats);
+        }
+
+        // observing once at the end gives the same numbers
+        let mut at_end = StatsCollector::default();
+        at_end.observe(session.history());
+        at_end.end_match();
+        assert!(at_end == collector);
+    }
+
+    assert!(stolen > 0);
+}
+
+#[test]
+fn collectors_merge_across_games() {
+    let mut together = StatsCollector::default();
+    let mut merged = StatsCollector::default();
+    for seed in 0..5 {
+        play(seed, &mut together);
+
+        let mut single = StatsCollector::default();
+        play(seed, &mut single);
+        merged.merge(&single);
+    }
+
+    assert!(together.matches() == 5);
+    assert!(merged == together);
+    assert!(sum(&merged, |stats| stats.rounds_won) == 15);
+}
+
+#[test]
+fn duds_are_not_counted_as_live() {
+    let mut duds = 0;
+    for seed in 0..10 {
+        let mut options = GameSessionOptions::with_stream_seed(seed);
+        options.rules = RoundRules::default().with_special_shells(SpecialShells {
+            dud: 2,
+            ..Default::default()
+        });
+        let mut session: GameSession<StdRng> =
+            GameSession::with_options(MultiplayerCount::Two, options);
+
+        let mut jammed = 0;
+        while let Some(round) = session.round() {
+            let target = round
+                .living_players()
+                .map(|seat| seat.player_number())
+                .find(|player_number| *player_number != round.next_player())
+                .unwrap();
+            let outcome = session.submit(Action::Shoot(target)).unwrap();
+            if outcome.shot_result.unwrap().damage == ShotgunDamage::Jammed {
+                jammed += 1;
+            }
+        }
+
+        let mut collector = StatsCollector::default();
+        collector.observe(session.history());
+        assert!(sum(&collector, |stats| stats.shots_at_others.dud) == jammed);
+        duds += jammed;
+    }
+
+    assert!(duds > 0);
+}
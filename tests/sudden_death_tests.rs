@@ -0,0 +1,211 @@
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    decision::SubmitError,
+    game_session::{GameSession, GameSessionOptions},
+    item::{Item, NotAdreneline, UnaryItem},
+    item_registry::{ItemBehavior, ItemContext, ItemLimits, ItemRegistry},
+    multiplayer_count::MultiplayerCount,
+    round_number::RoundNumber,
+    round_rules::RoundRules,
+    shell::{ShotgunDamage, SpecialShells},
+    turn::{HealResult, InvalidItemUseError, ItemUseResult},
+};
+use rand::rngs::StdRng;
+
+const CIGARETTES: Item = Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Cigarettes));
+
+/// Heals one without checking anything itself
+#[derive(Debug)]
+struct Bandage;
+
+impl ItemBehavior for Bandage {
+    fn name(&self) -> &str {
+        "Bandage"
+    }
+
+    fn limits(&self) -> ItemLimits {
+        ItemLimits {
+            player: 2,
+            global: 4,
+        }
+    }
+
+    fn use_item(&self, context: ItemContext) -> Result<ItemUseResult, InvalidItemUseError> {
+        let healed = context.player.gain_health(1, context.rules.healing);
+        Ok(ItemUseResult::Healed(HealResult {
+            requested: 1,
+            healed,
+        }))
+    }
+}
+
+fn session(rules: RoundRules, seed: u64) -> GameSession<StdRng> {
+    let mut options = GameSessionOptions::with_stream_seed(seed);
+    options.rules = rules;
+    GameSession::with_options(MultiplayerCount::Two, options)
+}
+
+/// Tries to smoke, then shoots the other player. Returns the number of times wires were cut
+fn play_game(session: &mut GameSession<StdRng>, threshold: Option<u8>) -> usize {
+    let mut cuts = 0;
+    while let Some(round) = session.round() {
+        let round_number = round.number();
+        let actor = round.next_player();
+        let seat = &round.seats()[actor.seat_index()];
+        let wires_cut = seat.player().unwrap().wires_cut();
+
+        if seat.items().contains(&CIGARETTES) {
+            let result = session.submit(Action::UseUnaryItem(UnaryItem::Cigarettes));
+            if wires_cut {
+                assert!(matches!(
+                    result,
+                    Err(SubmitError::InvalidItemUse(InvalidItemUseError::WiresCut))
+                ));
+            } else {
+                assert!(result.is_ok());
+                continue;
+            }
+        }
+
+        let round = session.round().unwrap();
+        let target = round
+            .living_players()
+            .find(|seat| seat.player_number() != actor)
+            .unwrap();
+        let target_number = target.player_number();
+        let target_wires_cut = target.player().unwrap().wires_cut();
+
+        let outcome = session.submit(Action::Shoot(target_number)).unwrap();
+        let shot_result = outcome.shot_result.unwrap();
+        if target_wires_cut && shot_result.damage != ShotgunDamage::Blank {
+            assert!(matches!(
+                shot_result.damage,
+                ShotgunDamage::RegularShot(true) | ShotgunDamage::SawedShot(true)
+            ));
+        }
+
+        if shot_result.wires_cut {
+            assert!(round_number == RoundNumber::Three);
+            let round = session.round().unwrap();
+            let player = round.seats()[target_number.seat_index()].player().unwrap();
+            assert!(player.wires_cut());
+            assert!(player.health() <= i32::from(threshold.unwrap()));
+            cuts += 1;
+        }
+    }
+
+    cuts
+}
+
+#[test]
+fn wires_are_cut_in_the_final_round() {
+    let mut cuts = 0;
+    for seed in 0..20 {
+        let mut session = session(RoundRules::default().with_sudden_death(2), seed);
+        cuts += play_game(&mut session, Some(2));
+    }
+
+    assert!(cuts > 0);
+}
+
+#[test]
+fn sudden_death_is_off_by_default() {
+    for seed in 0..20 {
+        let mut session = session(RoundRules::default(), seed);
+        assert!(play_game(&mut session, None) == 0);
+    }
+}
+
+#[test]
+fn burns_cut_wires_in_the_final_round() {
+    let mut cuts = 0;
+    for seed in 0..100 {
+        let mut session = session(
+            RoundRules::default()
+                .with_sudden_death(2)
+                .with_special_shells(SpecialShells {
+                    incendiary: 2,
+                    ..Default::default()
+                }),
+            seed,
+        );
+
+        while let Some(round) = session.round() {
+            let round_number = round.number();
+            let actor = round.next_player();
+            let target = round
+                .living_players()
+                .find(|seat| seat.player_number() != actor)
+                .unwrap()
+                .player_number();
+            let outcome = session.submit(Action::Shoot(target)).unwrap();
+
+            for burn in &outcome.burns {
+                if burn.killed || session.round().map(|round| round.number()) != Some(round_number)
+                {
+                    continue;
+                }
+
+                let round = session.round().unwrap();
+                let player = round.seats()[burn.player.seat_index()].player().unwrap();
+                let in_sudden_death = round_number == RoundNumber::Three && player.health() <= 2;
+                assert!(player.wires_cut() == in_sudden_death);
+                if burn.wires_cut {
+                    assert!(in_sudden_death);
+                    cuts += 1;
+                }
+            }
+        }
+    }
+
+    assert!(cuts > 0);
+}
+
+#[test]
+fn custom_items_cant_heal_cut_wires() {
+    let mut item_registry = ItemRegistry::built_in();
+    let bandage = item_registry.register(Bandage);
+    let bandage_item = Item::NotAdreneline(NotAdreneline::UnaryItem(UnaryItem::Custom(bandage)));
+
+    let mut blocked = 0;
+    for seed in 0..50 {
+        let mut options = GameSessionOptions::with_stream_seed(seed);
+        options.rules = RoundRules::default().with_sudden_death(2);
+        options.item_registry = item_registry.clone();
+        let mut session: GameSession<StdRng> =
+            GameSession::with_options(MultiplayerCount::Two, options);
+
+        while let Some(round) = session.round() {
+            let actor = round.next_player();
+            let seat = &round.seats()[actor.seat_index()];
+            let player = seat.player().unwrap();
+            let (wires_cut, health) = (player.wires_cut(), player.health());
+
+            if wires_cut && seat.items().contains(&bandage_item) {
+                let outcome = session
+                    .submit(Action::UseUnaryItem(UnaryItem::Custom(bandage)))
+                    .unwrap();
+                assert!(
+                    outcome.item_result
+                        == Some(ItemUseResult::Healed(HealResult {
+                            requested: 1,
+                            healed: 0
+                        }))
+                );
+                let round = session.round().unwrap();
+                assert!(round.seats()[actor.seat_index()].player().unwrap().health() == health);
+                blocked += 1;
+                continue;
+            }
+
+            let target = round
+                .living_players()
+                .find(|other| other.player_number() != actor)
+                .unwrap()
+                .player_number();
+            session.submit(Action::Shoot(target)).unwrap();
+        }
+    }
+
+    assert!(blocked > 0);
+}
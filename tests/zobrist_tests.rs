@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+
+use buckshot_roulette_gameplay_engine::{
+    action::Action,
+    compact_round::CompactRound,
+    game_session::GameSession,
+    item::{Item, NotAdreneline, UnaryItem, ALL_UNARY_ITEMS},
+    multiplayer_count::MultiplayerCount,
+    player_number::PlayerNumber,
+    rng_streams::RngStreams,
+    zobrist::TranspositionTable,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn start(seed: u64) -> (CompactRound, RngStreams<StdRng>) {
+    let session = GameSession::new(MultiplayerCount::Three, StdRng::seed_from_u64(seed));
+    let round = session.round().unwrap();
+    (
+        CompactRound::from_round(round).unwrap(),
+        round.rng().clone(),
+    )
+}
+
+#[test]
+fn incremental_hash_matches_recomputed() {
+    for seed in 0..20 {
+        let (mut compact, mut rng) = start(seed);
+        let mut policy_rng = StdRng::seed_from_u64(seed);
+        while compact.winner().is_none() {
+            let action = if policy_rng.gen_range(0, 3) == 0 {
+                let player = PlayerNumber::from_seat_index(policy_rng.gen_range(0, 3)).unwrap();
+                Action::Shoot(player)
+            } else {
+                Action::UseUnaryItem(ALL_UNARY_ITEMS[policy_rng.gen_range(0, 7)])
+            };
+
+            if compact.apply(action, &mut rng).is_ok() && compact.winner().is_none() {
+                let recomputed =
+                    CompactRound::from_round(&compact.into_round(rng.clone())).unwrap();
+                assert!(recomputed.zobrist_hash() == compact.zobrist_hash());
+            }
+        }
+    }
+}
+
+#[test]
+fn item_order_transposes() {
+    let magnifying_glass = UnaryItem::MagnifyingGlass;
+    let handsaw = UnaryItem::Handsaw;
+    let (compact, mut rng) = (0..)
+        .map(start)
+        .find(|(compact, _)| {
+            [magnifying_glass, handsaw].iter().all(|unary_item| {
+                compact.item_count(
+                    compact.next_player(),
+                    Item::NotAdreneline(NotAdreneline::UnaryItem(*unary_item)),
+                ) > 0
+            })
+        })
+        .unwrap();
+
+    let mut first = compact;
+    first
+        .apply(Action::UseUnaryItem(magnifying_glass), &mut rng)
+        .unwrap();
+    first
+        .apply(Action::UseUnaryItem(handsaw), &mut rng)
+        .unwrap();
+
+    let mut second = compact;
+    second
+        .apply(Action::UseUnaryItem(handsaw), &mut rng)
+        .unwrap();
+    second
+        .apply(Action::UseUnaryItem(magnifying_glass), &mut rng)
+        .unwrap();
+
+    assert!(first == second);
+    assert!(first.zobrist_hash() == second.zobrist_hash());
+    assert!(first.zobrist_hash() != compact.zobrist_hash());
+    assert!(HashSet::from([first, second]).len() == 1);
+
+    let mut table = TranspositionTable::new(10);
+    table.insert(first, 5);
+    assert!(table.get(&second) == Some(&5));
+    assert!(table.get(&compact).is_none());
+}